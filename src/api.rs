@@ -0,0 +1,89 @@
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::{config::Config, request};
+
+/// Run any GraphQL query or mutation and return the response as pretty printed JSON.
+/// Variables are key=value pairs, where a value that parses as JSON is passed as is, i.e. 3, true, or ["a", "b"],
+/// and anything else is passed as a string.
+pub fn run(
+    config: &Config,
+    token: &str,
+    query: &str,
+    variables: &[String],
+) -> Result<String, String> {
+    let variables = variables
+        .iter()
+        .map(|pair| variable(pair))
+        .collect::<Result<HashMap<String, Value>, String>>()?;
+    let response: Value = request::Gql::new(config, token, query)
+        .put_variables(variables)
+        .execute()?;
+    serde_json::to_string_pretty(&response).map_err(|e| e.to_string())
+}
+
+fn variable(pair: &str) -> Result<(String, Value), String> {
+    match pair.split_once('=') {
+        Some((key, value)) if !key.is_empty() => {
+            let value = serde_json::from_str(value).unwrap_or(Value::String(value.to_string()));
+            Ok((key.to_string(), value))
+        }
+        _ => Err(format!(
+            "Variable {pair} should be key=value, i.e. id=BE-3354"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn test_run() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"variables\":\\{\"first\":3,\"id\":\"BE-3354\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{\"data\":{\"issue\":{\"title\":\"Test\"}}}")
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            run(
+                &config,
+                "1234",
+                "query ($id: String!) { issue(id: $id) { title } }",
+                &[String::from("id=BE-3354"), String::from("first=3")]
+            ),
+            Ok(String::from(
+                "{\n  \"data\": {\n    \"issue\": {\n      \"title\": \"Test\"\n    }\n  }\n}"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn variable_should_parse_json_values() {
+        assert_eq!(
+            variable("labelIds=[\"1\",\"2\"]"),
+            Ok((String::from("labelIds"), json!(["1", "2"])))
+        );
+        assert_eq!(
+            variable("title=Fix the lair"),
+            Ok((String::from("title"), json!("Fix the lair")))
+        );
+        assert_eq!(
+            variable("title"),
+            Err(String::from(
+                "Variable title should be key=value, i.e. id=BE-3354"
+            ))
+        );
+    }
+}
@@ -0,0 +1,456 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::{color, config::Config, request, schema};
+
+const ATTACHMENT_CREATE_DOC: &str = "mutation (
+                    $input: AttachmentCreateInput!
+                ) {
+                attachmentCreate(
+                    input: $input
+                ) {
+                    success
+                    attachment {
+                        title
+                        url
+                        subtitle
+                        metadata
+                    }
+                }
+                }
+                ";
+
+const FILE_UPLOAD_DOC: &str = "mutation (
+                    $contentType: String!
+                    $filename: String!
+                    $size: Int!
+                ) {
+                fileUpload(
+                    contentType: $contentType
+                    filename: $filename
+                    size: $size
+                ) {
+                    success
+                    uploadFile {
+                        uploadUrl
+                        assetUrl
+                        headers {
+                            key
+                            value
+                        }
+                    }
+                }
+                }
+                ";
+
+/// Set in the metadata of attachments created by issue xref, so they can be told apart
+const XREF_SOURCE: &str = "lnr-xref";
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct Attachments {
+    nodes: Vec<Attachment>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Attachment {
+    title: String,
+    url: String,
+    subtitle: Option<String>,
+    metadata: Option<Value>,
+    source_type: Option<String>,
+}
+
+impl Attachment {
+    fn is_xref(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("source"))
+            .is_some_and(|source| source == XREF_SOURCE)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct FileUploadResponse {
+    data: Option<FileUploadData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct FileUploadData {
+    #[serde(rename = "fileUpload")]
+    file_upload: FileUpload,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct FileUpload {
+    success: bool,
+    upload_file: Option<UploadFile>,
+}
+
+/// Where to PUT a file, and the url it can be linked at once uploaded
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct UploadFile {
+    upload_url: String,
+    asset_url: String,
+    headers: Vec<UploadHeader>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct UploadHeader {
+    key: String,
+    value: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct AttachmentCreateResponse {
+    data: Option<AttachmentCreateData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct AttachmentCreateData {
+    #[serde(rename = "attachmentCreate")]
+    attachment_create: AttachmentCreate,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct AttachmentCreate {
+    success: bool,
+    attachment: Option<Attachment>,
+}
+
+/// Link an issue to an item in another tracker, i.e. a GitHub issue
+pub fn create_xref(
+    config: &Config,
+    token: &str,
+    issue_id: String,
+    url: String,
+    title: Option<String>,
+) -> Result<String, String> {
+    let title = title.unwrap_or_else(|| url.clone());
+    let input = json!({
+        "issueId": issue_id,
+        "url": url,
+        "title": title,
+        "subtitle": "Cross-reference",
+        "metadata": {"source": XREF_SOURCE},
+    });
+    let Attachment { title, url, .. } = create(config, token, input)?;
+    Ok(format!("Linked {title} {url}"))
+}
+
+/// Attach a url to an issue
+pub fn create_link(
+    config: &Config,
+    token: &str,
+    issue_id: String,
+    url: String,
+    title: Option<String>,
+) -> Result<String, String> {
+    let title = title.unwrap_or_else(|| url.clone());
+    let input = json!({
+        "issueId": issue_id,
+        "url": url,
+        "title": title,
+    });
+    let Attachment { title, url, .. } = create(config, token, input)?;
+    Ok(format!("Attached {title} {url}"))
+}
+
+/// Upload a local file to Linear and attach it to an issue
+pub fn create_file(
+    config: &Config,
+    token: &str,
+    issue_id: String,
+    path: &Path,
+    title: Option<String>,
+) -> Result<String, String> {
+    let contents = fs::read(path).map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("{} is not a file", path.display()))?;
+    let content_type = content_type(path);
+
+    let mut gql_variables = HashMap::new();
+    gql_variables.insert(String::from("contentType"), json!(content_type));
+    gql_variables.insert(String::from("filename"), json!(filename));
+    gql_variables.insert(String::from("size"), json!(contents.len()));
+    let response = request::Gql::new(config, token, FILE_UPLOAD_DOC)
+        .put_variables(gql_variables)
+        .run()?;
+    let UploadFile {
+        upload_url,
+        asset_url,
+        headers,
+    } = file_upload_response(response)?;
+
+    let headers = headers
+        .into_iter()
+        .map(|UploadHeader { key, value }| (key, value))
+        .collect::<Vec<(String, String)>>();
+    request::upload(config, &upload_url, &headers, content_type, contents)?;
+
+    let input = json!({
+        "issueId": issue_id,
+        "url": asset_url,
+        "title": title.unwrap_or(filename),
+    });
+    let Attachment { title, url, .. } = create(config, token, input)?;
+    Ok(format!("Uploaded {title} {url}"))
+}
+
+fn create(config: &Config, token: &str, input: Value) -> Result<Attachment, String> {
+    let mut gql_variables = HashMap::new();
+    gql_variables.insert(String::from("input"), input);
+    let response = request::Gql::new(config, token, ATTACHMENT_CREATE_DOC)
+        .put_variables(gql_variables)
+        .run()?;
+    attachment_create_response(response)
+}
+
+/// Content type for an upload, guessed from the file extension
+fn content_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "log" | "txt" | "md" => "text/plain",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "html" => "text/html",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Copy attachments onto an issue, skipping any whose url it already has.
+/// Returns the number of attachments copied.
+pub fn copy_missing(
+    config: &Config,
+    token: &str,
+    from: &Attachments,
+    to: &Attachments,
+    issue_id: &str,
+) -> Result<usize, String> {
+    let missing = from
+        .nodes
+        .iter()
+        .filter(|a| !to.nodes.iter().any(|existing| existing.url == a.url));
+
+    let mut copied = 0;
+    for attachment in missing {
+        let input = json!({
+            "issueId": issue_id,
+            "url": attachment.url,
+            "title": attachment.title,
+            "subtitle": attachment.subtitle,
+            "metadata": attachment.metadata,
+        });
+        create(config, token, input)?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Lines for issue view, with cross-references listed apart from other attachments
+pub fn describe(attachments: &Option<Attachments>) -> String {
+    let nodes = match attachments {
+        Some(Attachments { nodes }) => nodes,
+        None => return String::new(),
+    };
+    let (xrefs, others): (Vec<&Attachment>, Vec<&Attachment>) =
+        nodes.iter().partition(|a| a.is_xref());
+
+    let xrefs = xrefs.into_iter().map(|Attachment { title, url, .. }| {
+        let title = color::cyan_string(title);
+        format!("Cross-reference {title} {url}")
+    });
+    let others = others.into_iter().map(|attachment| {
+        let Attachment { title, url, .. } = attachment;
+        match &attachment.source_type {
+            Some(source) => format!("Attachment ({source}) {title} {url}"),
+            None => format!("Attachment {title} {url}"),
+        }
+    });
+
+    xrefs.chain(others).collect::<Vec<String>>().join("\n")
+}
+
+fn file_upload_response(response: String) -> Result<UploadFile, String> {
+    let data: Result<FileUploadResponse, _> = schema::parse(&response, "fileUpload");
+
+    match data {
+        Ok(FileUploadResponse {
+            data:
+                Some(FileUploadData {
+                    file_upload:
+                        FileUpload {
+                            success: true,
+                            upload_file: Some(upload_file),
+                        },
+                }),
+        }) => Ok(upload_file),
+        err => Err(format!(
+            "Could not parse response for fileUpload:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+fn attachment_create_response(response: String) -> Result<Attachment, String> {
+    let data: Result<AttachmentCreateResponse, _> = schema::parse(&response, "attachment");
+
+    match data {
+        Ok(AttachmentCreateResponse {
+            data:
+                Some(AttachmentCreateData {
+                    attachment_create:
+                        AttachmentCreate {
+                            success: true,
+                            attachment: Some(attachment),
+                        },
+                }),
+        }) => Ok(attachment),
+        err => Err(format!(
+            "Could not parse response for attachment:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_create_xref() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"metadata\":\\{\"source\":\"lnr-xref\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::attachment_create())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = create_xref(
+            &config,
+            "1234",
+            String::from("ENG-12"),
+            String::from("https://github.com/org/repo/issues/99"),
+            Some(String::from("GH#99")),
+        );
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "Linked GH#99 https://github.com/org/repo/issues/99"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn describe_should_list_xrefs_first() {
+        let attachments: Attachments = serde_json::from_str(
+            r#"{"nodes": [
+                {"title": "Design", "url": "https://figma.com/1", "sourceType": "figma"},
+                {"title": "GH#99", "url": "https://github.com/org/repo/issues/99", "metadata": {"source": "lnr-xref"}}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            describe(&Some(attachments)),
+            "Cross-reference GH#99 https://github.com/org/repo/issues/99\nAttachment (figma) Design https://figma.com/1"
+        );
+    }
+
+    #[test]
+    fn test_create_file() {
+        let mut server = mockito::Server::new();
+        let file_upload = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"contentType\":\"text/plain\",\"filename\":\"[^\"]+\\.log\",\"size\":11",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"data": {{"fileUpload": {{"success": true, "uploadFile": {{
+                    "uploadUrl": "{}/upload",
+                    "assetUrl": "https://uploads.linear.app/crash.log",
+                    "headers": [{{"key": "x-goog-content-length-range", "value": "11,11"}}]
+                }}}}}}}}"#,
+                server.url()
+            ))
+            .create();
+        let upload = server
+            .mock("PUT", "/upload")
+            .match_header("content-type", "text/plain")
+            .match_header("x-goog-content-length-range", "11,11")
+            .match_body("stack trace")
+            .with_status(200)
+            .create();
+        let attachment = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"title\":\"Crash log\",\"url\":\"https://uploads.linear.app/crash.log\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::attachment_create())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let path = std::env::temp_dir().join(format!("lnr-attach-{}.log", uuid::Uuid::new_v4()));
+        fs::write(&path, "stack trace").unwrap();
+
+        let result = create_file(
+            &config,
+            "1234",
+            String::from("ENG-12"),
+            &path,
+            Some(String::from("Crash log")),
+        );
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "Uploaded GH#99 https://github.com/org/repo/issues/99"
+            ))
+        );
+        file_upload.assert();
+        upload.assert();
+        attachment.assert();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn content_type_should_fall_back_to_binary() {
+        assert_eq!(content_type(Path::new("crash.LOG")), "text/plain");
+        assert_eq!(content_type(Path::new("screenshot.png")), "image/png");
+        assert_eq!(content_type(Path::new("core")), "application/octet-stream");
+    }
+}
@@ -0,0 +1,420 @@
+use std::io::{IsTerminal, Stdout, Write};
+use std::process::{Command, Stdio};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::{
+    config::Config,
+    hooks, input,
+    issue::{self, Changes, Issue},
+    team::State,
+};
+
+const HELP: &str = "←→ column  ↑↓ issue  H/L move  enter details  o open  q quit";
+const DETAILS_HELP: &str = "H/L move  o open  q back";
+/// Narrowest a column gets before the board scrolls sideways
+const MIN_COLUMN_WIDTH: usize = 24;
+/// Lines taken by the header, divider, and help line
+const CHROME_HEIGHT: usize = 4;
+
+/// A workflow state and the issues in it
+struct Column {
+    state: State,
+    issues: Vec<Issue>,
+}
+
+/// What the terminal loop should do after a key press
+#[derive(Debug, PartialEq)]
+enum Action {
+    Continue,
+    Quit,
+    /// Move the selected issue to the column with this index
+    Move(usize),
+    Open(String),
+}
+
+struct Board {
+    columns: Vec<Column>,
+    column: usize,
+    row: usize,
+    details: bool,
+    /// Shown instead of the help line until the next key press
+    message: Option<String>,
+    moved: usize,
+}
+
+impl Board {
+    /// Issues in a state that isn't one of the columns are left out
+    fn new(states: Vec<State>, issues: Vec<Issue>) -> Board {
+        let mut columns = states
+            .into_iter()
+            .map(|state| Column {
+                state,
+                issues: Vec::new(),
+            })
+            .collect::<Vec<Column>>();
+        for issue in issues {
+            if let Some(column) = columns.iter_mut().find(|c| c.state.id == issue.state.id) {
+                column.issues.push(issue);
+            }
+        }
+
+        Board {
+            columns,
+            column: 0,
+            row: 0,
+            details: false,
+            message: None,
+            moved: 0,
+        }
+    }
+
+    fn selected(&self) -> Option<&Issue> {
+        self.columns
+            .get(self.column)
+            .and_then(|column| column.issues.get(self.row))
+    }
+
+    fn handle(&mut self, code: KeyCode) -> Action {
+        self.message = None;
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc if self.details => self.details = false,
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Left | KeyCode::Char('h') if !self.details => self.select_column(-1),
+            KeyCode::Right | KeyCode::Char('l') if !self.details => self.select_column(1),
+            KeyCode::Up | KeyCode::Char('k') if !self.details => {
+                self.row = self.row.saturating_sub(1)
+            }
+            KeyCode::Down | KeyCode::Char('j') if !self.details => self.select_row(1),
+            KeyCode::Enter => self.details = !self.details && self.selected().is_some(),
+            KeyCode::Char('H') | KeyCode::Char('<') => return self.move_action(-1),
+            KeyCode::Char('L') | KeyCode::Char('>') => return self.move_action(1),
+            KeyCode::Char('o') => {
+                if let Some(issue) = self.selected() {
+                    return Action::Open(issue.url.clone());
+                }
+            }
+            _ => (),
+        }
+        Action::Continue
+    }
+
+    fn select_column(&mut self, offset: isize) {
+        let last = self.columns.len().saturating_sub(1);
+        self.column = self.column.saturating_add_signed(offset).min(last);
+        self.select_row(0);
+    }
+
+    /// Move the selection down by offset rows, staying within the column
+    fn select_row(&mut self, offset: usize) {
+        let count = self.columns.get(self.column).map_or(0, |c| c.issues.len());
+        self.row = (self.row + offset).min(count.saturating_sub(1));
+    }
+
+    fn move_action(&self, offset: isize) -> Action {
+        match self.column.checked_add_signed(offset) {
+            Some(to) if to < self.columns.len() && self.selected().is_some() => Action::Move(to),
+            _ => Action::Continue,
+        }
+    }
+
+    /// Move the selected issue to another column, once Linear has accepted the change
+    fn move_selected(&mut self, to: usize) {
+        let mut issue = self.columns[self.column].issues.remove(self.row);
+        issue.state = self.columns[to].state.clone();
+        self.columns[to].issues.push(issue);
+        self.column = to;
+        self.row = self.columns[to].issues.len() - 1;
+        self.moved += 1;
+    }
+
+    /// Lines of the board for a terminal of the given size
+    fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let body_height = height.saturating_sub(CHROME_HEIGHT).max(1);
+        let mut lines = match (self.details, self.selected()) {
+            (true, Some(issue)) => details(issue),
+            _ => self.columns_lines(width, body_height),
+        };
+        lines.truncate(height.saturating_sub(1));
+        lines.resize(height.saturating_sub(1), String::new());
+
+        let help = match self.details {
+            true => DETAILS_HELP,
+            false => HELP,
+        };
+        lines.push(fit(self.message.as_deref().unwrap_or(help), width));
+        lines
+    }
+
+    fn columns_lines(&self, width: usize, body_height: usize) -> Vec<String> {
+        if self.columns.is_empty() {
+            return vec![String::from("No states to show")];
+        }
+        // Scroll sideways and down so that the selected issue is always visible
+        let visible = (width / MIN_COLUMN_WIDTH).clamp(1, self.columns.len());
+        let first = (self.column + 1).saturating_sub(visible);
+        let columns = &self.columns[first..first + visible];
+        let column_width = width / visible;
+        let top = (self.row + 1).saturating_sub(body_height);
+
+        let header = columns
+            .iter()
+            .map(|c| {
+                let title = format!("{} ({})", c.state.name, c.issues.len());
+                fit(&title, column_width)
+            })
+            .collect::<String>();
+        let divider = columns
+            .iter()
+            .map(|_| fit(&"─".repeat(column_width.saturating_sub(1)), column_width))
+            .collect::<String>();
+
+        let mut lines = vec![header, divider];
+        for row in top..top + body_height {
+            let line = columns
+                .iter()
+                .enumerate()
+                .map(|(index, column)| {
+                    let selected = first + index == self.column && row == self.row;
+                    let marker = if selected { "> " } else { "  " };
+                    let cell = match column.issues.get(row) {
+                        Some(issue) => format!("{marker}{} {}", issue.identifier, issue.title),
+                        None => String::new(),
+                    };
+                    fit(&cell, column_width)
+                })
+                .collect::<String>();
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+fn details(issue: &Issue) -> Vec<String> {
+    let mut lines = vec![
+        format!("{} {}", issue.identifier, issue.title),
+        format!("{} | {}", issue.state.name, issue.url),
+        String::new(),
+    ];
+    let description = issue.description.as_deref().unwrap_or("<No description>");
+    lines.extend(description.lines().map(String::from));
+    lines
+}
+
+/// Truncate or pad text to exactly width characters, leaving a space between columns
+fn fit(text: &str, width: usize) -> String {
+    let text = text
+        .chars()
+        .take(width.saturating_sub(1))
+        .collect::<String>();
+    format!("{text:<width$}")
+}
+
+/// Show issues in a kanban board with a column for each state, until q is pressed
+pub fn run(
+    config: &Config,
+    token: &str,
+    states: Vec<State>,
+    issues: Vec<Issue>,
+) -> Result<String, String> {
+    input::require("an interactive terminal to show the board")?;
+    if !std::io::stdout().is_terminal() {
+        return Err(String::from("The board needs an interactive terminal"));
+    }
+    // A spinner would draw over the board while an issue is being moved
+    let config = &Config {
+        spinners: Some(false),
+        ..config.clone()
+    };
+    let mut board = Board::new(states, issues);
+    let mut stdout = std::io::stdout();
+    let _screen = Screen::enter(&mut stdout)?;
+
+    loop {
+        draw(&mut stdout, &board)?;
+        let code = match event::read().map_err(|e| e.to_string())? {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }) => break,
+            Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) => code,
+            _ => continue,
+        };
+
+        match board.handle(code) {
+            Action::Continue => (),
+            Action::Quit => break,
+            Action::Move(to) => {
+                let state = board.columns[to].state.clone();
+                board.message = Some(format!("Moving to {}...", state.name));
+                draw(&mut stdout, &board)?;
+                let id = board.selected().map(|i| i.id.clone()).unwrap_or_default();
+                let changes = Changes {
+                    state: Some(state.clone()),
+                    ..Default::default()
+                };
+                match issue::update(config, token, id.clone(), changes) {
+                    Ok(url) => {
+                        board.move_selected(to);
+                        // The issue has moved even when its hook fails
+                        board.message =
+                            hooks::state_changed(config, token, id, &Some(state), url).err();
+                    }
+                    Err(e) => board.message = Some(e),
+                }
+            }
+            Action::Open(url) => {
+                if let Err(e) = open_url(&url) {
+                    board.message = Some(e);
+                }
+            }
+        }
+    }
+    Ok(format!("{} issue(s) moved", board.moved))
+}
+
+fn draw(stdout: &mut Stdout, board: &Board) -> Result<(), String> {
+    let (width, height) = terminal::size().map_err(|e| e.to_string())?;
+    queue!(stdout, terminal::Clear(ClearType::All)).map_err(|e| e.to_string())?;
+    for (y, line) in board
+        .render(width as usize, height as usize)
+        .iter()
+        .enumerate()
+    {
+        queue!(stdout, cursor::MoveTo(0, y as u16), Print(line)).map_err(|e| e.to_string())?;
+    }
+    stdout.flush().map_err(|e| e.to_string())
+}
+
+/// Raw mode on the alternate screen, restored when dropped so that errors don't break the terminal
+struct Screen;
+
+impl Screen {
+    fn enter(stdout: &mut Stdout) -> Result<Screen, String> {
+        terminal::enable_raw_mode().map_err(|e| e.to_string())?;
+        execute!(stdout, EnterAlternateScreen, cursor::Hide).map_err(|e| e.to_string())?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(std::io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn open_url(url: &str) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Could not open {url}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn state(id: &str, name: &str) -> State {
+        State {
+            id: id.to_string(),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn issue(identifier: &str, state_id: &str) -> Issue {
+        serde_json::from_value(json!({
+            "id": identifier.to_lowercase(),
+            "identifier": identifier,
+            "title": "Fix",
+            "url": format!("https://linear.app/vardy/issue/{identifier}"),
+            "state": {"id": state_id},
+        }))
+        .unwrap()
+    }
+
+    fn board() -> Board {
+        Board::new(
+            vec![state("1", "Todo"), state("2", "Doing"), state("3", "Done")],
+            vec![
+                issue("ENG-1", "1"),
+                issue("ENG-2", "1"),
+                issue("ENG-3", "2"),
+                issue("ENG-4", "9"),
+            ],
+        )
+    }
+
+    #[test]
+    fn render_should_group_issues_by_state() {
+        let lines = board().render(72, 6);
+        assert_eq!(
+            lines[..5],
+            [
+                "Todo (2)                Doing (1)               Done (0)                ",
+                "─────────────────────── ─────────────────────── ─────────────────────── ",
+                "> ENG-1 Fix               ENG-3 Fix                                     ",
+                "  ENG-2 Fix                                                             ",
+                "",
+            ]
+        );
+        assert_eq!(lines[5], fit(HELP, 72));
+    }
+
+    #[test]
+    fn handle_should_move_and_open_selected_issue() {
+        let mut board = board();
+        assert_eq!(board.handle(KeyCode::Char('j')), Action::Continue);
+        assert_eq!(board.selected().unwrap().identifier, "ENG-2");
+        assert_eq!(board.handle(KeyCode::Char('H')), Action::Continue);
+        assert_eq!(board.handle(KeyCode::Char('L')), Action::Move(1));
+
+        board.move_selected(1);
+        assert_eq!(board.selected().unwrap().identifier, "ENG-2");
+        assert_eq!(board.selected().unwrap().state.name, "Doing");
+        assert_eq!(board.columns[0].issues.len(), 1);
+        assert_eq!(
+            board.handle(KeyCode::Char('o')),
+            Action::Open(String::from("https://linear.app/vardy/issue/ENG-2"))
+        );
+
+        assert_eq!(board.handle(KeyCode::Right), Action::Continue);
+        assert_eq!(board.selected().map(|i| &i.identifier), None);
+        assert_eq!(board.handle(KeyCode::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn render_should_scroll_to_selected_column() {
+        let mut board = board();
+        board.handle(KeyCode::Right);
+        board.handle(KeyCode::Right);
+        let lines = board.render(30, 5);
+        assert_eq!(lines[0], fit("Done (0)", 30));
+    }
+}
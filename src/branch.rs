@@ -0,0 +1,107 @@
+use regex::{Captures, Regex};
+
+/// Values that can be used in branch_format, i.e. "{{team_key|lower}}/{{number}}-{{slug}}"
+pub struct Variables {
+    pub identifier: String,
+    pub title: String,
+    /// Linear's own suggested branch name
+    pub branch_name: String,
+}
+
+/// Render a branch name from a format string
+pub fn render(format: &str, variables: &Variables) -> Result<String, String> {
+    let regex = Regex::new(r"\{\{\s*(\w+)\s*(?:\|\s*(\w+)\s*)?\}\}").unwrap();
+    let mut errors = Vec::new();
+
+    let name = regex.replace_all(format, |captures: &Captures| {
+        let name = &captures[1];
+        let value = match variable(name, variables) {
+            Some(value) => value,
+            None => {
+                errors.push(format!("Unknown variable {name} in branch_format"));
+                return String::new();
+            }
+        };
+        match captures.get(2).map(|f| f.as_str()) {
+            None => value,
+            Some("lower") => value.to_lowercase(),
+            Some("upper") => value.to_uppercase(),
+            Some(filter) => {
+                errors.push(format!("Unknown filter {filter} in branch_format"));
+                String::new()
+            }
+        }
+    });
+
+    match errors.first() {
+        Some(error) => Err(error.to_owned()),
+        None => Ok(name.to_string()),
+    }
+}
+
+fn variable(name: &str, variables: &Variables) -> Option<String> {
+    let (team_key, number) = variables
+        .identifier
+        .rsplit_once('-')
+        .unwrap_or((&variables.identifier, ""));
+
+    match name {
+        "identifier" => Some(variables.identifier.clone()),
+        "team_key" => Some(team_key.to_string()),
+        "number" => Some(number.to_string()),
+        "title" => Some(variables.title.clone()),
+        "slug" => Some(slugify(&variables.title)),
+        "branch_name" => Some(variables.branch_name.clone()),
+        _ => None,
+    }
+}
+
+/// Lowercase, with runs of anything but letters and numbers replaced by a single dash
+fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn variables() -> Variables {
+        Variables {
+            identifier: String::from("ENG-123"),
+            title: String::from("Fix the Batmobile's brakes!"),
+            branch_name: String::from("alfred/eng-123-fix-the-batmobiles-brakes"),
+        }
+    }
+
+    #[test]
+    fn render_should_fill_in_variables() {
+        let result = render("{{team_key|lower}}/{{number}}-{{slug}}", &variables());
+        assert_eq!(
+            result,
+            Ok(String::from("eng/123-fix-the-batmobile-s-brakes"))
+        );
+
+        let result = render("feature/{{ identifier | upper }}", &variables());
+        assert_eq!(result, Ok(String::from("feature/ENG-123")));
+    }
+
+    #[test]
+    fn render_should_reject_unknown_names() {
+        let result = render("{{nope}}", &variables());
+        assert_eq!(
+            result,
+            Err(String::from("Unknown variable nope in branch_format"))
+        );
+
+        let result = render("{{slug|reverse}}", &variables());
+        assert_eq!(
+            result,
+            Err(String::from("Unknown filter reverse in branch_format"))
+        );
+    }
+}
@@ -0,0 +1,455 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::IsTerminal;
+
+use crate::{
+    color,
+    config::Config,
+    hooks, input,
+    issue::{self, Issue},
+    label, state,
+    team::{self, State, Team},
+    user::User,
+    verbosity,
+};
+
+/// Width of the progress bar, in characters between the brackets
+const BAR_WIDTH: usize = 20;
+
+/// The changes made to every issue. Names are matched in each issue's own team,
+/// so issues from several teams can be updated at once.
+#[derive(Debug, Default)]
+pub struct Update {
+    pub state: Option<String>,
+    /// Labels to add, existing labels are kept
+    pub labels: Vec<String>,
+    /// Some(None) unassigns the issues
+    pub assignee_id: Option<Option<String>>,
+}
+
+/// What can be done to the issues picked in issue list --interactive
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    State,
+    Labels,
+    Assignee,
+}
+
+impl Display for Action {
+    /// This is for rendering in a select
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::State => write!(f, "Change state"),
+            Action::Labels => write!(f, "Add labels"),
+            Action::Assignee => write!(f, "Reassign"),
+        }
+    }
+}
+
+/// Identifiers separated by commas, spaces, or newlines, i.e. piped from another command.
+/// Duplicates are left out.
+pub fn identifiers(text: &str) -> Vec<String> {
+    let mut identifiers: Vec<String> = Vec::new();
+    for identifier in text.split(|c: char| c == ',' || c.is_whitespace()) {
+        if !identifier.is_empty() && !identifiers.iter().any(|i| i == identifier) {
+            identifiers.push(identifier.to_string());
+        }
+    }
+    identifiers
+}
+
+/// Prompt for any number of the listed issues, then for an action and what it sets,
+/// and apply it to all of them like update does
+pub fn interactive(config: &Config, token: &str, issues: Vec<Issue>) -> Result<String, String> {
+    if issues.is_empty() {
+        return Ok(String::from("No issues found"));
+    }
+    input::require("issue bulk --ids in place of --interactive")?;
+    let issues = input::multi_select("Select issues", issues)?;
+    if issues.is_empty() {
+        return Ok(String::from("No issues selected"));
+    }
+    let teams = teams(&issues);
+
+    let actions = vec![Action::State, Action::Labels, Action::Assignee];
+    input::require("issue bulk --state or --label")?;
+    let changes = match input::select("Select action", actions)? {
+        Action::State => {
+            let names = state_names(config, token, &teams)?;
+            input::require("issue bulk --state")?;
+            Update {
+                state: Some(input::select("Select state", names)?),
+                ..Update::default()
+            }
+        }
+        Action::Labels => {
+            let names = label_names(config, token, &teams)?;
+            input::require("issue bulk --label")?;
+            let labels = input::multi_select("Select labels", names)?;
+            if labels.is_empty() {
+                return Ok(String::from("No labels selected"));
+            }
+            Update {
+                labels,
+                ..Update::default()
+            }
+        }
+        Action::Assignee => Update {
+            assignee_id: Some(select_assignee(config, token, &teams)?),
+            ..Update::default()
+        },
+    };
+
+    let identifiers = issues.into_iter().map(|issue| issue.identifier).collect();
+    update(config, token, identifiers, &changes)
+}
+
+/// The teams of the issues, each once
+fn teams(issues: &[Issue]) -> Vec<Team> {
+    let mut teams: Vec<Team> = Vec::new();
+    for team in issues.iter().filter_map(|issue| issue.team.as_ref()) {
+        if !teams.iter().any(|t| t.id == team.id) {
+            teams.push(team.clone());
+        }
+    }
+    teams
+}
+
+/// Names of the states of every team in workflow order, a name the teams share is listed once
+fn state_names(config: &Config, token: &str, teams: &[Team]) -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = Vec::new();
+    for team in teams {
+        for state in team::get_states(config, token, team)? {
+            if !names.iter().any(|name| name == &state.name) {
+                names.push(state.name);
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Names of the labels any of the teams can use, each once
+fn label_names(config: &Config, token: &str, teams: &[Team]) -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = Vec::new();
+    for team in teams {
+        for label in label::for_team(config, token, team)? {
+            if !names.iter().any(|name| name == &label.name) {
+                names.push(label.name);
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Prompt for a member of any of the teams, None when the None option is picked
+fn select_assignee(config: &Config, token: &str, teams: &[Team]) -> Result<Option<String>, String> {
+    let mut members: Vec<User> = Vec::new();
+    for team in teams {
+        for member in team::get_members(config, token, team)? {
+            if !members.iter().any(|m| m.id == member.id) {
+                members.push(member);
+            }
+        }
+    }
+    let mut names = members
+        .iter()
+        .map(|m| m.name_with_status())
+        .collect::<Vec<String>>();
+    names.insert(0, String::from("None"));
+    if !input::prompts_enabled() {
+        return Err(String::from(
+            "Changing the assignee of several issues needs a prompt, issue bulk has no flag for it",
+        ));
+    }
+    let name = input::select("Select assignee", names.clone())?;
+    let index = names.iter().position(|n| n == &name).unwrap_or_default();
+    Ok(index
+        .checked_sub(1)
+        .and_then(|i| members.get(i))
+        .and_then(|m| m.id.clone()))
+}
+
+/// Apply the same update to each issue in turn. An issue that can't be updated is
+/// reported in the summary instead of stopping the rest.
+pub fn update(
+    config: &Config,
+    token: &str,
+    identifiers: Vec<String>,
+    update: &Update,
+) -> Result<String, String> {
+    if identifiers.is_empty() {
+        return Err(String::from("No issues to update"));
+    }
+    // Spinners would draw over the progress bar
+    let config = Config {
+        spinners: Some(false),
+        ..config.clone()
+    };
+
+    let total = identifiers.len();
+    let mut states: HashMap<String, Vec<State>> = HashMap::new();
+    let mut results = Vec::new();
+    for (index, identifier) in identifiers.iter().enumerate() {
+        draw(&progress_bar(index, total, identifier));
+        results.push(update_issue(
+            &config,
+            token,
+            identifier,
+            update,
+            &mut states,
+        ));
+    }
+    draw("");
+
+    let succeeded = results.iter().filter(|r| r.is_ok()).count();
+    let lines = identifiers
+        .iter()
+        .zip(results)
+        .map(|(identifier, result)| match result {
+            Ok(url) => format!("- {identifier} {} {url}", color::green_string("✓")),
+            Err(err) => format!("- {identifier} {} {err}", color::red_string("✗")),
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    let summary = format!("{lines}\n\n{succeeded} of {total} issues updated");
+
+    if succeeded == total {
+        Ok(summary)
+    } else {
+        Err(summary)
+    }
+}
+
+/// Update a single issue, fetching the states of its team unless they were fetched for an earlier issue
+fn update_issue(
+    config: &Config,
+    token: &str,
+    identifier: &str,
+    update: &Update,
+    states: &mut HashMap<String, Vec<State>>,
+) -> Result<String, String> {
+    let issue = issue::get(config, token, identifier.to_string())?;
+    let team = issue
+        .team
+        .ok_or_else(|| format!("{identifier} has no team"))?;
+
+    let state = match &update.state {
+        Some(name) => {
+            if !states.contains_key(&team.id) {
+                let team_states = team::get_states(config, token, &team)?;
+                states.insert(team.id.clone(), team_states);
+            }
+            Some(state::find(&team, &states[&team.id], name)?)
+        }
+        None => None,
+    };
+    let added_label_ids = label::ids(config, token, &team, update.labels.clone())?;
+
+    let changes = issue::Changes {
+        state: state.clone(),
+        added_label_ids,
+        assignee_id: update.assignee_id.clone(),
+        ..Default::default()
+    };
+    let url = issue::update(config, token, issue.id.clone(), changes)?;
+    hooks::state_changed(config, token, issue.id, &state, url)
+}
+
+/// i.e. [=====               ] 5/20 ENG-6
+fn progress_bar(done: usize, total: usize, identifier: &str) -> String {
+    let filled = done * BAR_WIDTH / total.max(1);
+    format!(
+        "[{}{}] {done}/{total} {identifier}",
+        "=".repeat(filled),
+        " ".repeat(BAR_WIDTH - filled)
+    )
+}
+
+/// Redraw the progress bar in place on stderr, only when it is a terminal. Left out with --quiet
+fn draw(bar: &str) {
+    if !verbosity::is_quiet() && std::io::stderr().is_terminal() {
+        eprint!("\r\x1b[K{bar}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn mock(
+        server: &mut mockito::ServerGuard,
+        pattern: &str,
+        body: String,
+        hits: usize,
+    ) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(pattern)))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .expect(hits)
+            .create()
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(
+            identifiers("ENG-1\nENG-2, ENG-3\n\n ENG-1 ,ENG-4\n"),
+            vec!["ENG-1", "ENG-2", "ENG-3", "ENG-4"]
+        );
+        assert_eq!(identifiers(" \n"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(
+            progress_bar(0, 4, "ENG-1"),
+            "[                    ] 0/4 ENG-1"
+        );
+        assert_eq!(
+            progress_bar(3, 4, "ENG-4"),
+            "[===============     ] 3/4 ENG-4"
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let mut server = mockito::Server::new();
+        let found = mock(
+            &mut server,
+            "\"variables\":\\{\"id\":\"BE-3354\"\\}",
+            test::responses::issue_with_team(),
+            2,
+        );
+        let missing = mock(
+            &mut server,
+            "\"variables\":\\{\"id\":\"BE-1\"\\}",
+            String::from("{\"data\":null,\"errors\":[{\"message\":\"Entity not found\"}]}"),
+            1,
+        );
+        let states = mock(&mut server, "team \\(id", test::responses::team_states(), 1);
+        let labels = mock(
+            &mut server,
+            "\"names\":\\[\"cats\"\\]",
+            test::responses::issue_labels(),
+            2,
+        );
+        let updated = mock(
+            &mut server,
+            "\"input\":\\{\"addedLabelIds\":\\[\"label-cats\"\\],\"stateId\":\"state-done\"\\}",
+            test::responses::issue_update(),
+            2,
+        );
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let update = Update {
+            state: Some(String::from("done")),
+            labels: vec![String::from("cats")],
+            ..Update::default()
+        };
+        let result = super::update(
+            &config,
+            "1234",
+            vec![
+                String::from("BE-3354"),
+                String::from("BE-1"),
+                String::from("BE-3354"),
+            ],
+            &update,
+        );
+        let Err(summary) = result else {
+            panic!("Expected BE-1 to fail, got {result:?}");
+        };
+        // The error for BE-1 is whatever Linear says about the missing issue
+        assert!(
+            summary
+                .starts_with("- BE-3354 ✓ https://linear.app/vardy/issue/BE-3354/test\n- BE-1 ✗ "),
+            "{summary}"
+        );
+        assert!(
+            summary.ends_with(
+                "- BE-3354 ✓ https://linear.app/vardy/issue/BE-3354/test\n\n2 of 3 issues updated"
+            ),
+            "{summary}"
+        );
+        found.assert();
+        missing.assert();
+        states.assert();
+        labels.assert();
+        updated.assert();
+    }
+
+    #[test]
+    fn test_interactive() {
+        let mut server = mockito::Server::new();
+        let list = mock(&mut server, "issues \\(", test::responses::issue_list(), 1);
+        let issue = mock(
+            &mut server,
+            "\"variables\":\\{\"id\":\"SHO-2148\"\\}",
+            test::responses::issue_with_team(),
+            1,
+        );
+        // Once for the prompt and once for the update
+        let states = mock(&mut server, "team \\(id", test::responses::team_states(), 2);
+        let updated = mock(
+            &mut server,
+            "\"input\":\\{\"stateId\":\"state-backlog\"\\}",
+            test::responses::issue_update(),
+            1,
+        );
+        let config = test::fixtures::config().mock_url(server.url());
+        input::set_backend(input::Answers {
+            select: Some(0),
+            multi_select: Some(vec![0]),
+            ..input::Answers::default()
+        });
+
+        let issues = issue::get_issues(&config, "1234", issue::Filter::default()).unwrap();
+        assert_eq!(
+            interactive(&config, "1234", issues),
+            Ok(String::from(
+                "- SHO-2148 ✓ https://linear.app/vardy/issue/BE-3354/test\n\n1 of 1 issues updated"
+            ))
+        );
+        list.assert();
+        issue.assert();
+        states.assert();
+        updated.assert();
+
+        assert_eq!(
+            interactive(&config, "1234", Vec::new()),
+            Ok(String::from("No issues found"))
+        );
+    }
+
+    #[test]
+    fn update_should_report_unknown_states() {
+        let mut server = mockito::Server::new();
+        let _issue = mock(
+            &mut server,
+            "\"variables\":\\{\"id\":\"BE-3354\"\\}",
+            test::responses::issue_with_team(),
+            1,
+        );
+        let _states = mock(&mut server, "team \\(id", test::responses::team_states(), 1);
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let update = Update {
+            state: Some(String::from("Closed")),
+            ..Update::default()
+        };
+        assert_eq!(
+            super::update(&config, "1234", vec![String::from("BE-3354")], &update),
+            Err(String::from(
+                "- BE-3354 ✗ Team Thundercats has no state named Closed, choose from Backlog, Todo, In Progress, Done\n\n0 of 1 issues updated"
+            ))
+        );
+        assert_eq!(
+            super::update(&config, "1234", Vec::new(), &update),
+            Err(String::from("No issues to update"))
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How long cached responses are used for when cache_ttl_seconds is not configured
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Responses are cached under $XDG_CACHE_HOME/lnr
+pub fn dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("lnr"))
+}
+
+/// Name of the cache file for a request, the token is included so organizations don't mix
+pub fn key(token: &str, body: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    token.hash(&mut hasher);
+    body.hash(&mut hasher);
+    format!("{:016x}.json", hasher.finish())
+}
+
+/// The cached response, if there is one younger than the ttl
+pub fn read(dir: &Path, key: &str, ttl: Duration) -> Option<String> {
+    let path = dir.join(key);
+    let age = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+
+    if age < ttl {
+        fs::read_to_string(path).ok()
+    } else {
+        None
+    }
+}
+
+/// Save a response, failures are ignored as the cache is only an optimization
+pub fn write(dir: &Path, key: &str, response: &str) {
+    if fs::create_dir_all(dir).is_ok() {
+        let _ = fs::write(dir.join(key), response);
+    }
+}
+
+/// Remove every cached response
+pub fn clear(dir: &Path) -> Result<(), String> {
+    match fs::remove_dir_all(dir) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Could not clear cache in {}: {e}", dir.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn test_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("lnr-cache-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn read_should_return_fresh_responses() {
+        let dir = test_dir();
+        let key = key("1234", "{\"query\":\"viewer\"}");
+        assert_eq!(read(&dir, &key, DEFAULT_TTL), None);
+
+        write(&dir, &key, "response");
+        assert_eq!(
+            read(&dir, &key, DEFAULT_TTL),
+            Some(String::from("response"))
+        );
+        assert_eq!(read(&dir, &key, Duration::ZERO), None);
+
+        clear(&dir).unwrap();
+        assert_eq!(read(&dir, &key, DEFAULT_TTL), None);
+        assert_eq!(clear(&dir), Ok(()));
+    }
+
+    #[test]
+    fn key_should_depend_on_token() {
+        assert_ne!(key("1234", "body"), key("5678", "body"));
+        assert_eq!(key("1234", "body"), key("1234", "body"));
+    }
+}
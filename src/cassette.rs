@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory of recorded responses, which are replayed instead of calling Linear
+const CASSETTE_VAR: &str = "LNR_CASSETTE";
+/// Set to 1 along with LNR_CASSETTE to call Linear and record each response
+const RECORD_VAR: &str = "LNR_RECORD";
+/// 64 bit FNV-1a, which unlike DefaultHasher gives the same names on every Rust version
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Whether requests are recorded to, or replayed from, a directory of responses
+#[derive(Debug, PartialEq)]
+pub enum Cassette {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+/// The cassette set by LNR_CASSETTE and LNR_RECORD, if there is one
+pub fn from_env() -> Option<Cassette> {
+    let dir = std::env::var_os(CASSETTE_VAR).filter(|dir| !dir.is_empty())?;
+    match std::env::var(RECORD_VAR).as_deref() {
+        Ok("1") => Some(Cassette::Record(PathBuf::from(dir))),
+        _ => Some(Cassette::Replay(PathBuf::from(dir))),
+    }
+}
+
+/// File name for a request, i.e. query-viewer-0a1b2c3d4e5f6a7b.json. The token is left out
+/// so that a cassette recorded with one token replays with any other.
+fn name(operation: &str, body: &str) -> String {
+    format!("{}-{:016x}.json", operation.replace(' ', "-"), fnv(body))
+}
+
+fn fnv(text: &str) -> u64 {
+    text.bytes().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// The recorded response for a request
+pub fn replay(dir: &Path, operation: &str, body: &str) -> Result<String, String> {
+    let path = dir.join(name(operation, body));
+    fs::read_to_string(&path).map_err(|_| {
+        format!(
+            "No recorded response for {operation} at {}, record it with {RECORD_VAR}=1",
+            path.display()
+        )
+    })
+}
+
+/// Save the response to a request so that it can be replayed
+pub fn record(dir: &Path, operation: &str, body: &str, response: &str) -> Result<(), String> {
+    let path = dir.join(name(operation, body));
+    fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&path, response))
+        .map_err(|e| format!("Could not record response to {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn replay_should_return_recorded_responses() {
+        let dir = std::env::temp_dir().join(format!("lnr-cassette-{}", uuid::Uuid::new_v4()));
+        let body = "{\"query\":\"{ viewer { id } }\"}";
+
+        assert_eq!(
+            replay(&dir, "query viewer", body).map_err(|e| e.starts_with("No recorded response")),
+            Err(true)
+        );
+        record(&dir, "query viewer", body, "{\"data\":{}}").unwrap();
+        assert_eq!(
+            replay(&dir, "query viewer", body),
+            Ok(String::from("{\"data\":{}}"))
+        );
+        assert!(replay(&dir, "query viewer", "{\"query\":\"other\"}").is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn fnv_should_be_stable() {
+        assert_eq!(fnv(""), 0xcbf29ce484222325);
+        assert_eq!(fnv("a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv("foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn name_should_include_the_operation() {
+        assert!(name("mutation issueCreate", "{}").starts_with("mutation-issueCreate-"));
+        assert_ne!(
+            name("query viewer", "{}"),
+            name("query viewer", "{\"a\":1}")
+        );
+    }
+}
@@ -24,6 +24,14 @@ pub fn blue_string(str: &str) -> String {
     String::from(str).blue().to_string()
 }
 
+pub fn red_string(str: &str) -> String {
+    if cfg!(test) {
+        return normal_string(str);
+    }
+
+    String::from(str).red().to_string()
+}
+
 pub fn normal_string(str: &str) -> String {
     String::from(str).normal().to_string()
 }
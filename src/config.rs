@@ -1,50 +1,179 @@
 use rand::distributions::{Alphanumeric, DistString};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::fs;
 use std::io::{Read, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
 
-use crate::color;
+use crate::{color, input, verbosity};
 
-/// App configuration, serialized as json in $XDG_CONFIG_HOME/lnr.cfg
-#[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Debug)]
+const LOCK_ATTEMPTS: u32 = 50;
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+const STALE_LOCK_AGE: Duration = Duration::from_secs(30);
+
+/// Fields that config get and set leave alone, organizations are managed with org add and org remove
+const INTERNAL_KEYS: [&str; 3] = ["organizations", "path", "mock_url"];
+
+/// Workflow states left out of issue lists when excluded_states is not configured
+const DEFAULT_EXCLUDED_STATES: [&str; 6] = [
+    "Done",
+    "Backlog",
+    "Triage",
+    "Canceled",
+    "Closed",
+    "Merged to Dev",
+];
+
+/// Names configured per organization and team, the most specific match wins
+/// (team, then organization, then default)
+#[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Debug, Default)]
+pub struct ScopedNames {
+    pub default: Option<Vec<String>>,
+    /// Keyed by organization name
+    #[serde(default)]
+    pub organizations: HashMap<String, Vec<String>>,
+    /// Keyed by team name
+    #[serde(default)]
+    pub teams: HashMap<String, Vec<String>>,
+}
+
+impl ScopedNames {
+    fn resolve(
+        &self,
+        org_name: Option<&String>,
+        team_name: Option<&String>,
+    ) -> Option<&Vec<String>> {
+        let team_names = team_name.and_then(|name| self.teams.get(name));
+        let org_names = org_name.and_then(|name| self.organizations.get(name));
+
+        team_names.or(org_names).or(self.default.as_ref())
+    }
+
+    fn rename_organization(&mut self, name: &str, new_name: &str) {
+        if let Some(names) = self.organizations.remove(name) {
+            self.organizations.insert(new_name.to_string(), names);
+        }
+    }
+}
+
+/// Organizations and settings used in place of the shared ones when a profile is selected
+#[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Debug, Default)]
+pub struct Profile {
+    pub organizations: Option<HashMap<String, String>>,
+    pub description_processor: Option<String>,
+    pub branch_format: Option<String>,
+    pub excluded_states: Option<ScopedNames>,
+    pub date_format: Option<String>,
+    pub default_labels: Option<ScopedNames>,
+}
+
+/// A named snippet that issue create --template pre-fills the prompts with
+#[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Debug, Default)]
+pub struct IssueTemplate {
+    /// Text the title starts with, i.e. "Bug: "
+    pub title: Option<String>,
+    /// Skeleton opened in the editor for the description
+    pub description: Option<String>,
+    /// Label names added to the default_labels
+    pub labels: Option<Vec<String>>,
+    /// 1 (Low), 2 (Normal), 3 (High), or 4 (Urgent), used when --priority isn't given
+    pub priority: Option<u8>,
+}
+
+/// App configuration, serialized as TOML in $XDG_CONFIG_HOME/lnr.toml.
+/// Paths that don't end in .toml, such as the legacy lnr.cfg, are read and written as JSON.
+#[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Debug, Default)]
 pub struct Config {
     /// List of organizations and their tokens
     pub organizations: HashMap<String, String>,
     /// Path to config file
     pub path: String,
     pub mock_url: Option<String>,
-    pub mock_string: Option<String>,
-    pub mock_select: Option<usize>,
     // Whether spinners are enabled
     pub spinners: Option<bool>,
+    /// Shell command that descriptions are piped through before submission
+    pub description_processor: Option<String>,
+    /// Template for branch names created by issue checkout, i.e. "{{team_key|lower}}/{{number}}-{{slug}}"
+    pub branch_format: Option<String>,
+    /// State names left out of issue lists, replacing the built in list
+    pub excluded_states: Option<ScopedNames>,
+    /// Format for displaying dates, one of iso, us, eu, or a strftime pattern such as %d %b %Y
+    pub date_format: Option<String>,
+    /// Label names applied to issues created by issue create and template evaluate
+    pub default_labels: Option<ScopedNames>,
+    /// Seconds that teams, states, and projects are cached for, 0 turns the cache off. Defaults to an hour
+    pub cache_ttl_seconds: Option<u64>,
+    /// Seconds to wait for a response from Linear before giving up. Defaults to 30
+    pub request_timeout_seconds: Option<u64>,
+    /// Proxy for requests to Linear and crates.io, i.e. http://proxy:8080 or socks5://proxy:1080. HTTPS_PROXY, HTTP_PROXY, and ALL_PROXY are used when this is not set
+    pub proxy: Option<String>,
+    /// Whether to check crates.io for a newer version, at most once a day. Defaults to true
+    pub version_check: Option<bool>,
+    /// Number of options shown at once in select prompts, which can be narrowed by typing. Defaults to 7
+    pub select_page_size: Option<usize>,
+    /// Seconds between refreshes of issue view --watch. Defaults to 30
+    pub watch_interval_seconds: Option<u64>,
+    /// Named snippets for issue create --template, i.e. [issue_templates.bug]
+    pub issue_templates: Option<HashMap<String, IssueTemplate>>,
+    /// Default flags per command, i.e. [defaults.issue.list]
+    pub defaults: Option<Value>,
+    /// Shell command run with the issue as JSON on stdin after issue create and issue duplicate
+    pub on_issue_create: Option<String>,
+    /// Shell command run with the issue as JSON on stdin when it moves to a started state
+    pub on_issue_start: Option<String>,
+    /// Shell command run with the issue as JSON on stdin when it moves to a completed or canceled state
+    pub on_issue_close: Option<String>,
+    /// Named sets of organizations and settings, selected with --profile or LNR_PROFILE
+    pub profiles: Option<HashMap<String, Profile>>,
+    /// Organization to queue mutations for when Linear cannot be reached, set by --queue and never saved
+    #[serde(skip)]
+    pub offline_queue: Option<String>,
+    /// Profile selected with --profile or LNR_PROFILE, organizations are added to and removed from it
+    #[serde(skip)]
+    pub profile: Option<String>,
 }
 
 impl Config {
     pub fn add_organization(&mut self, name: String, token: String) {
-        let projects = &mut self.organizations;
-        projects.insert(name, token);
+        match self.profile.clone() {
+            Some(profile) => {
+                let profile = self
+                    .profiles
+                    .get_or_insert_with(HashMap::new)
+                    .entry(profile)
+                    .or_default();
+                profile
+                    .organizations
+                    .get_or_insert_with(HashMap::new)
+                    .insert(name, token);
+            }
+            None => {
+                self.organizations.insert(name, token);
+            }
+        }
     }
 
     pub fn create(self) -> Result<Config, String> {
-        let json = json!(self).to_string();
+        let contents = Format::of(&self.path).serialize(&self)?;
         let mut file = fs::File::create(&self.path).or(Err("Could not create file"))?;
-        file.write_all(json.as_bytes())
+        file.write_all(contents.as_bytes())
             .or(Err("Could not write to file"))?;
-        println!("Config successfully created in {}", &self.path);
+        verbosity::progress(&format!("Config successfully created in {}", &self.path));
         Ok(self)
     }
 
     pub fn load(path: &str) -> Result<Config, String> {
-        let mut json = String::new();
+        let mut contents = String::new();
 
         fs::File::open(path)
             .or(Err("Could not find file"))?
-            .read_to_string(&mut json)
+            .read_to_string(&mut contents)
             .or(Err("Could not read to string"))?;
 
-        serde_json::from_str::<Config>(&json).map_err(|_| format!("Could not parse JSON:\n{json}"))
+        Format::of(path).parse(&contents)
     }
 
     pub fn new() -> Result<Config, String> {
@@ -52,15 +181,116 @@ impl Config {
         Ok(Config {
             path: generate_path()?,
             spinners: Some(true),
-            mock_url: None,
-            mock_string: None,
-            mock_select: None,
             organizations,
+            ..Default::default()
         })
     }
 
     pub fn remove_organization(&mut self, name: &String) {
-        self.organizations.remove(name);
+        let profile = self.profile.as_ref().and_then(|profile| {
+            self.profiles
+                .as_mut()
+                .and_then(|profiles| profiles.get_mut(profile))
+        });
+        match profile {
+            Some(profile) => {
+                if let Some(organizations) = profile.organizations.as_mut() {
+                    organizations.remove(name);
+                }
+            }
+            None => {
+                self.organizations.remove(name);
+            }
+        }
+    }
+
+    /// Rename an organization in the selected profile, or the shared organizations without one.
+    /// Its token and the settings scoped to it, such as excluded_states, move to the new name.
+    pub fn rename_organization(&mut self, name: &str, new_name: String) {
+        let profile = self.profile.as_ref().and_then(|profile| {
+            self.profiles
+                .as_mut()
+                .and_then(|profiles| profiles.get_mut(profile))
+        });
+        match profile {
+            Some(profile) => {
+                if let Some(token) = profile
+                    .organizations
+                    .as_mut()
+                    .and_then(|organizations| organizations.remove(name))
+                {
+                    profile
+                        .organizations
+                        .get_or_insert_with(HashMap::new)
+                        .insert(new_name.clone(), token);
+                }
+                for scoped in [&mut profile.excluded_states, &mut profile.default_labels] {
+                    if let Some(scoped) = scoped.as_mut() {
+                        scoped.rename_organization(name, &new_name);
+                    }
+                }
+            }
+            None => {
+                if let Some(token) = self.organizations.remove(name) {
+                    self.organizations.insert(new_name.clone(), token);
+                }
+            }
+        }
+        // Shared settings also apply to organizations in profiles
+        for scoped in [&mut self.excluded_states, &mut self.default_labels] {
+            if let Some(scoped) = scoped.as_mut() {
+                scoped.rename_organization(name, &new_name);
+            }
+        }
+    }
+
+    /// Use a profile's organizations and settings in place of the shared ones.
+    /// Settings the profile leaves out are shared, organizations never are.
+    pub fn with_profile(self, name: Option<String>) -> Result<Config, String> {
+        let Some(name) = name else {
+            return Ok(self);
+        };
+        let mut names = self
+            .profiles
+            .clone()
+            .unwrap_or_default()
+            .into_keys()
+            .collect::<Vec<String>>();
+        names.sort();
+        let profile = self
+            .profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(&name))
+            .cloned()
+            .ok_or_else(|| match names.is_empty() {
+                true => format!(
+                    "Profile {name} not found, create it with {}",
+                    color::cyan_string(&format!("--profile {name} org add"))
+                ),
+                false => format!(
+                    "Profile {name} not found, options are: {}",
+                    names.join(", ")
+                ),
+            })?;
+
+        let Profile {
+            organizations,
+            description_processor,
+            branch_format,
+            excluded_states,
+            date_format,
+            default_labels,
+        } = profile;
+        Ok(Config {
+            organizations: organizations.unwrap_or_default(),
+            description_processor: description_processor.or(self.description_processor),
+            branch_format: branch_format.or(self.branch_format),
+            excluded_states: excluded_states.or(self.excluded_states),
+            date_format: date_format.or(self.date_format),
+            default_labels: default_labels.or(self.default_labels),
+            profile: Some(name),
+            ..self
+        })
     }
 
     pub fn organization_names(&self) -> Vec<String> {
@@ -80,43 +310,360 @@ impl Config {
         }
     }
 
-    pub fn save(&mut self) -> std::result::Result<String, String> {
-        let json = json!(self);
-        let string = serde_json::to_string_pretty(&json).or(Err("Could not convert to JSON"))?;
+    /// State names to leave out of issue lists for an organization and team
+    pub fn excluded_states(
+        &self,
+        org_name: Option<&String>,
+        team_name: Option<&String>,
+    ) -> Vec<String> {
+        let configured = self.excluded_states.clone().unwrap_or_default();
 
-        fs::OpenOptions::new()
-            .write(true)
-            .read(true)
-            .truncate(true)
-            .open(&self.path)
-            .or(Err("Could not find config"))?
+        match configured.resolve(org_name, team_name) {
+            Some(states) => states.clone(),
+            None => DEFAULT_EXCLUDED_STATES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+
+    /// Label names to apply to new issues for an organization and team
+    pub fn default_labels(
+        &self,
+        org_name: Option<&String>,
+        team_name: Option<&String>,
+    ) -> Vec<String> {
+        self.default_labels
+            .as_ref()
+            .and_then(|labels| labels.resolve(org_name, team_name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// An issue template by name, the error lists the templates that are configured
+    pub fn issue_template(&self, name: &str) -> Result<IssueTemplate, String> {
+        let templates = self.issue_templates.clone().unwrap_or_default();
+        if let Some(template) = templates.get(name) {
+            return Ok(template.clone());
+        }
+        let mut names = templates.keys().cloned().collect::<Vec<String>>();
+        names.sort();
+        match names.is_empty() {
+            true => Err(format!(
+                "Issue template {name} not found, add it to issue_templates in config"
+            )),
+            false => Err(format!(
+                "Issue template {name} not found, choose from {}",
+                names.join(", ")
+            )),
+        }
+    }
+
+    /// Apply a change on top of the latest config on disk while holding the lock,
+    /// so that changes saved by another lnr process in the meantime are kept
+    pub fn update<F: FnOnce(&mut Config)>(&mut self, change: F) -> Result<String, String> {
+        let _lock = Lock::acquire(&self.path)?;
+        let mut latest = match fs::File::open(&self.path) {
+            Ok(_) => Config {
+                path: self.path.clone(),
+                profile: self.profile.clone(),
+                ..Config::load(&self.path)?
+            },
+            Err(_) => self.clone(),
+        };
+        change(&mut latest);
+        *self = latest;
+        self.write()
+    }
+
+    /// The value of a config field, strings are shown without quotes
+    pub fn get(&self, key: &str) -> Result<String, String> {
+        check_key(self, key)?;
+        match json!(self).get(key) {
+            None | Some(Value::Null) => Ok(String::from("Not set")),
+            Some(Value::String(string)) => Ok(string.clone()),
+            Some(value) => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Set a config field from the command line. The value is read as JSON, so false, 30, and
+    /// null (which unsets the field) work, falling back to a plain string, i.e. for date_format us
+    pub fn set(&mut self, key: &str, value: &str) -> Result<String, String> {
+        check_key(self, key)?;
+        let value = serde_json::from_str::<Value>(value)
+            .ok()
+            .filter(|parsed| with_value(self, key, parsed.clone()).is_ok())
+            .unwrap_or_else(|| Value::String(value.to_string()));
+        with_value(self, key, value.clone())?;
+
+        self.update(|config| {
+            if let Ok(updated) = with_value(config, key, value) {
+                *config = updated;
+            }
+        })
+    }
+
+    /// Edit the config file, saving it pretty printed once it parses.
+    /// Nothing is saved if the edit is discarded.
+    pub fn edit(&mut self) -> Result<String, String> {
+        let format = Format::of(&self.path);
+        let mut contents = fs::read_to_string(&self.path)
+            .map_err(|e| format!("Could not read {}: {e}", self.path))?;
+        loop {
+            contents = input::editor_with_extension("Edit config", &contents, format.extension())?;
+            match format.parse(&contents) {
+                Ok(edited) => {
+                    let _lock = Lock::acquire(&self.path)?;
+                    *self = Config {
+                        path: self.path.clone(),
+                        ..edited
+                    };
+                    self.write()?;
+                    return Ok(format!("Saved {}", self.path));
+                }
+                Err(e) => {
+                    println!("{}", color::red_string(&format!("Invalid config: {e}")));
+                    let options = vec![EditRetry::EditAgain, EditRetry::Discard];
+                    if input::select("What now?", options)? == EditRetry::Discard {
+                        return Err(String::from("Discarded changes to config"));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Write to a temporary file and rename it over the config, so readers never see a partial file
+    fn write(&self) -> Result<String, String> {
+        let string = Format::of(&self.path).serialize(self)?;
+        let temp_path = format!("{}.{}.tmp", self.path, std::process::id());
+
+        fs::File::create(&temp_path)
+            .or(Err("Could not create temporary config"))?
             .write_all(string.as_bytes())
             .or(Err("Could not write to file"))?;
+        fs::rename(&temp_path, &self.path).or(Err("Could not replace config"))?;
 
         Ok(color::green_string("✓"))
     }
 }
 
-pub fn get_or_create(config_path: Option<String>) -> Result<Config, String> {
-    let path: String = match config_path {
-        None => generate_path()?,
-        Some(path) => path.trim().to_owned(),
+/// File formats the config can be stored in, chosen by the extension of its path
+#[derive(Debug, PartialEq)]
+enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    fn of(path: &str) -> Format {
+        if path.ends_with(".toml") {
+            Format::Toml
+        } else {
+            Format::Json
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Toml => ".toml",
+            Format::Json => ".json",
+        }
+    }
+
+    fn parse(&self, contents: &str) -> Result<Config, String> {
+        match self {
+            Format::Toml => {
+                toml::from_str::<Config>(contents).map_err(|e| format!("Could not parse TOML: {e}"))
+            }
+            Format::Json => serde_json::from_str::<Config>(contents)
+                .map_err(|e| format!("Could not parse JSON: {e}")),
+        }
+    }
+
+    fn serialize(&self, config: &Config) -> Result<String, String> {
+        match self {
+            Format::Toml => toml::to_string_pretty(config)
+                .map_err(|e| format!("Could not convert to TOML: {e}")),
+            Format::Json => serde_json::to_string_pretty(config)
+                .or(Err(String::from("Could not convert to JSON"))),
+        }
+    }
+}
+
+/// Names of the fields that config get and set can change
+fn settable_keys(config: &Config) -> Vec<String> {
+    let fields = match json!(config) {
+        Value::Object(fields) => fields.keys().cloned().collect::<Vec<String>>(),
+        _ => Vec::new(),
     };
+    let mut keys = fields
+        .into_iter()
+        .filter(|key| !INTERNAL_KEYS.contains(&key.as_str()))
+        .collect::<Vec<String>>();
+    keys.sort();
+    keys
+}
+
+fn check_key(config: &Config, key: &str) -> Result<(), String> {
+    let keys = settable_keys(config);
+    if keys.iter().any(|k| k == key) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown config key {key}, options are: {}",
+            keys.join(", ")
+        ))
+    }
+}
+
+/// A copy of the config with one field changed, which fails when the value has the wrong type
+fn with_value(config: &Config, key: &str, value: Value) -> Result<Config, String> {
+    let mut json = json!(config);
+    json[key] = value;
+    let updated = serde_json::from_value::<Config>(json)
+        .map_err(|e| format!("Invalid value for {key}: {e}"))?;
+    Ok(Config {
+        offline_queue: config.offline_queue.clone(),
+        ..updated
+    })
+}
+
+/// Options after an edit of the config that doesn't parse
+#[derive(Debug, PartialEq)]
+enum EditRetry {
+    EditAgain,
+    Discard,
+}
+
+impl std::fmt::Display for EditRetry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditRetry::EditAgain => write!(f, "Edit again"),
+            EditRetry::Discard => write!(f, "Discard changes"),
+        }
+    }
+}
+
+/// Lock file held while a file such as the config is being written, removed when dropped
+pub struct Lock {
+    path: String,
+    /// Written to the lock file, so that a lock that was taken over isn't removed by its old owner
+    id: String,
+}
+
+impl Lock {
+    pub fn acquire(config_path: &str) -> Result<Lock, String> {
+        let path = format!("{config_path}.lock");
+        let id = Alphanumeric.sample_string(&mut rand::thread_rng(), 30);
+
+        for _ in 0..LOCK_ATTEMPTS {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    file.write_all(id.as_bytes())
+                        .map_err(|e| format!("Could not write {path}: {e}"))?;
+                    return Ok(Lock { path, id });
+                }
+                Err(_) if Lock::is_stale(&path) => Lock::remove_stale(&path),
+                Err(_) => thread::sleep(LOCK_RETRY_INTERVAL),
+            }
+        }
+
+        Err(format!(
+            "Could not lock config, remove {path} if no other lnr process is running"
+        ))
+    }
+
+    /// A lock left behind by a process that crashed
+    fn is_stale(path: &str) -> bool {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > STALE_LOCK_AGE)
+    }
+
+    /// Move the lock aside before removing it. Only one process can rename it, and a fresh lock
+    /// that took its place after the age check is put back rather than removed.
+    fn remove_stale(path: &str) {
+        let taken = format!(
+            "{path}.{}",
+            Alphanumeric.sample_string(&mut rand::thread_rng(), 30)
+        );
+        if fs::rename(path, &taken).is_err() {
+            return;
+        }
+        if !Lock::is_stale(&taken) {
+            _ = fs::hard_link(&taken, path);
+        }
+        _ = fs::remove_file(&taken);
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        if fs::read_to_string(&self.path).is_ok_and(|id| id == self.id) {
+            _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+pub fn get_or_create(config_path: Option<String>) -> Result<Config, String> {
+    let path = resolve_path(config_path)?;
 
     match fs::File::open(&path) {
         Ok(_) => Config::load(&path),
-        Err(_) => Config::new()?.create(),
+        Err(_) => Config {
+            path,
+            ..Config::new()?
+        }
+        .create(),
+    }
+}
+
+/// Load the config, or start from a new one without writing it when there is no file yet
+pub fn get_or_default(config_path: Option<String>) -> Result<Config, String> {
+    let path = resolve_path(config_path)?;
+
+    match fs::File::open(&path) {
+        Ok(_) => Config::load(&path),
+        Err(_) => Ok(Config {
+            path,
+            ..Config::new()?
+        }),
+    }
+}
+
+/// The path given with --config, or the default path after migrating a legacy config to it
+pub fn resolve_path(config_path: Option<String>) -> Result<String, String> {
+    match config_path {
+        Some(path) => Ok(path.trim().to_owned()),
+        None => {
+            let path = generate_path()?;
+            migrate(&legacy_path()?, &path)?;
+            Ok(path)
+        }
     }
 }
 
 pub fn generate_path() -> Result<String, String> {
-    let config_directory = dirs::config_dir()
-        .ok_or_else(|| String::from("Could not find config directory"))?
-        .to_str()
-        .ok_or_else(|| String::from("Could not convert config directory to string"))?
-        .to_owned();
+    let config_directory = config_directory()?;
     if cfg!(test) {
         _ = fs::create_dir(format!("{config_directory}/lnr_test"));
+        let random_string = Alphanumeric.sample_string(&mut rand::thread_rng(), 30);
+        Ok(format!("tests/{random_string}.toml"))
+    } else {
+        Ok(format!("{config_directory}/lnr.toml"))
+    }
+}
+
+/// Where the config was kept, as JSON, before lnr.toml
+fn legacy_path() -> Result<String, String> {
+    let config_directory = config_directory()?;
+    if cfg!(test) {
         let random_string = Alphanumeric.sample_string(&mut rand::thread_rng(), 30);
         Ok(format!("tests/{random_string}.testcfg"))
     } else {
@@ -124,6 +671,30 @@ pub fn generate_path() -> Result<String, String> {
     }
 }
 
+fn config_directory() -> Result<String, String> {
+    Ok(dirs::config_dir()
+        .ok_or_else(|| String::from("Could not find config directory"))?
+        .to_str()
+        .ok_or_else(|| String::from("Could not convert config directory to string"))?
+        .to_owned())
+}
+
+/// Write a legacy config to the new path, keeping the old file, when there is no config at the new path yet
+fn migrate(legacy_path: &str, path: &str) -> Result<(), String> {
+    if Path::new(path).exists() || !Path::new(legacy_path).exists() {
+        return Ok(());
+    }
+    let config = Config {
+        path: path.to_owned(),
+        ..Config::load(legacy_path)?
+    };
+    config.write()?;
+    verbosity::progress(&format!(
+        "Migrated config from {legacy_path} to {path}, the old file was kept"
+    ));
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -135,22 +706,6 @@ mod tests {
                 ..self
             }
         }
-
-        // /// Mock out the string response
-        // pub fn mock_string(self, string: &str) -> Config {
-        //     Config {
-        //         mock_string: Some(string.to_string()),
-        //         ..self
-        //     }
-        // }
-
-        // /// Mock out the select response, setting the index of the response
-        // pub fn mock_select(self, index: usize) -> Config {
-        //     Config {
-        //         mock_select: Some(index),
-        //         ..self
-        //     }
-        // }
     }
 
     use matches::assert_matches;
@@ -176,9 +731,7 @@ mod tests {
                 path: config.path.clone(),
                 organizations: organizations.clone(),
                 spinners: Some(true),
-                mock_url: None,
-                mock_string: None,
-                mock_select: None,
+                ..Default::default()
             }
         );
         config.add_organization(String::from("test"), "sometoken".to_string());
@@ -189,9 +742,7 @@ mod tests {
                 path: config.path.clone(),
                 spinners: Some(true),
                 organizations,
-                mock_url: None,
-                mock_string: None,
-                mock_select: None,
+                ..Default::default()
             }
         );
     }
@@ -205,9 +756,7 @@ mod tests {
             path: generate_path().unwrap(),
             spinners: Some(true),
             organizations: organizations.clone(),
-            mock_url: None,
-            mock_string: None,
-            mock_select: None,
+            ..Default::default()
         };
 
         assert_eq!(
@@ -216,9 +765,7 @@ mod tests {
                 path: config_with_two_projects.path.clone(),
                 spinners: Some(true),
                 organizations: organizations.clone(),
-                mock_url: None,
-                mock_string: None,
-                mock_select: None,
+                ..Default::default()
             }
         );
         config_with_two_projects.remove_organization(&String::from("test"));
@@ -230,13 +777,113 @@ mod tests {
                 path: config_with_two_projects.path.clone(),
                 organizations,
                 spinners: Some(true),
-                mock_url: None,
-                mock_string: None,
-                mock_select: None,
+                ..Default::default()
             }
         );
     }
 
+    #[test]
+    fn excluded_states_should_use_most_specific() {
+        let org = String::from("Wayne Enterprises");
+        let team = String::from("Thundercats");
+        let config = test::fixtures::config();
+        assert_eq!(
+            config.excluded_states(Some(&org), Some(&team)),
+            vec![
+                "Done",
+                "Backlog",
+                "Triage",
+                "Canceled",
+                "Closed",
+                "Merged to Dev"
+            ]
+        );
+
+        let mut excluded_states = ScopedNames {
+            default: Some(vec![String::from("Done")]),
+            ..Default::default()
+        };
+        excluded_states
+            .organizations
+            .insert(org.clone(), vec![String::from("Shipped")]);
+        excluded_states
+            .teams
+            .insert(team.clone(), vec![String::from("Released")]);
+        let config = Config {
+            excluded_states: Some(excluded_states),
+            ..config
+        };
+
+        assert_eq!(config.excluded_states(None, None), vec!["Done"]);
+        assert_eq!(config.excluded_states(Some(&org), None), vec!["Shipped"]);
+        assert_eq!(
+            config.excluded_states(Some(&org), Some(&team)),
+            vec!["Released"]
+        );
+    }
+
+    #[test]
+    fn issue_template_should_parse_from_toml() {
+        let config = test::fixtures::config();
+        assert_eq!(
+            config.issue_template("bug"),
+            Err(String::from(
+                "Issue template bug not found, add it to issue_templates in config"
+            ))
+        );
+
+        let templates = toml::from_str::<HashMap<String, IssueTemplate>>(
+            "[bug]\ntitle = \"Bug: \"\ndescription = \"## Steps to reproduce\"\nlabels = [\"bug\"]\npriority = 3\n\n[chore]\n",
+        )
+        .unwrap();
+        let config = Config {
+            issue_templates: Some(templates),
+            ..config
+        };
+        assert_eq!(
+            config.issue_template("bug"),
+            Ok(IssueTemplate {
+                title: Some(String::from("Bug: ")),
+                description: Some(String::from("## Steps to reproduce")),
+                labels: Some(vec![String::from("bug")]),
+                priority: Some(3),
+            })
+        );
+        assert_eq!(
+            config.issue_template("feature"),
+            Err(String::from(
+                "Issue template feature not found, choose from bug, chore"
+            ))
+        );
+    }
+
+    #[test]
+    fn default_labels_should_use_most_specific() {
+        let org = String::from("Wayne Enterprises");
+        let team = String::from("Thundercats");
+        let config = test::fixtures::config();
+        assert!(config.default_labels(Some(&org), Some(&team)).is_empty());
+
+        let mut default_labels = ScopedNames {
+            default: Some(vec![String::from("cli-created")]),
+            ..Default::default()
+        };
+        default_labels.teams.insert(
+            team.clone(),
+            vec![String::from("cli-created"), String::from("cats")],
+        );
+        let config = Config {
+            default_labels: Some(default_labels),
+            ..config
+        };
+
+        assert_eq!(config.default_labels(Some(&org), None), vec!["cli-created"]);
+        assert_eq!(
+            config.default_labels(Some(&org), Some(&team)),
+            vec!["cli-created", "cats"]
+        );
+    }
+
     #[test]
     fn config_tests() {
         // These need to be run sequentially as they write to the filesystem.
@@ -258,13 +905,17 @@ mod tests {
                 organizations: HashMap::new(),
                 path: config.clone().unwrap().path,
                 spinners: Some(true),
-                mock_url: None,
-                mock_string: None,
-                mock_select: None,
+                ..Default::default()
             })
         );
         delete_config(&config.unwrap().path);
 
+        // get_or_default (new) does not write a file
+        let path = format!("{}.missing", test::fixtures::config().path);
+        let config = get_or_default(Some(path.clone())).unwrap();
+        assert_eq!(config.path, path);
+        assert!(fs::File::open(&path).is_err());
+
         // get_or_create (load)
         test::fixtures::config()
             .mock_url(server.url())
@@ -279,14 +930,292 @@ mod tests {
                 organizations: HashMap::new(),
                 path: config.clone().unwrap().path,
                 spinners: Some(true),
-                mock_url: None,
-                mock_string: None,
-                mock_select: None,
+                ..Default::default()
             })
         );
         delete_config(&config.unwrap().path);
     }
 
+    #[test]
+    fn update_should_keep_concurrent_changes() {
+        let config = test::fixtures::config().create().unwrap();
+        let mut first = Config::load(&config.path).unwrap();
+        let mut second = Config::load(&config.path).unwrap();
+
+        first
+            .update(|c| c.add_organization(String::from("first"), String::from("token1")))
+            .unwrap();
+        second
+            .update(|c| c.add_organization(String::from("second"), String::from("token2")))
+            .unwrap();
+
+        let mut names = Config::load(&config.path).unwrap().organization_names();
+        names.sort();
+        assert_eq!(names, vec!["first", "second"]);
+        assert_eq!(second.organization_names().len(), 2);
+        assert!(!Path::new(&format!("{}.lock", config.path)).exists());
+
+        second
+            .update(|c| c.remove_organization(&String::from("first")))
+            .unwrap();
+        let names = Config::load(&config.path).unwrap().organization_names();
+        assert_eq!(names, vec!["second"]);
+        delete_config(&config.path);
+    }
+
+    #[test]
+    fn acquire_should_take_over_stale_lock() {
+        let config = test::fixtures::config();
+        let path = format!("{}.lock", config.path);
+        let stale = fs::File::create(&path).unwrap();
+        stale
+            .set_modified(std::time::SystemTime::now() - STALE_LOCK_AGE * 2)
+            .unwrap();
+
+        let lock = Lock::acquire(&config.path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), lock.id);
+
+        // A lock that was taken over is left to its new owner
+        fs::write(&path, "other").unwrap();
+        drop(lock);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "other");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn update_should_wait_for_lock() {
+        let mut config = test::fixtures::config().create().unwrap();
+        let lock = Lock::acquire(&config.path).unwrap();
+        let path = config.path.clone();
+        let handle = thread::spawn(move || {
+            config.update(|c| c.add_organization(String::from("test"), String::from("token")))
+        });
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(Config::load(&path).unwrap().organization_names().len(), 0);
+        drop(lock);
+
+        assert_eq!(handle.join().unwrap(), Ok(String::from("✓")));
+        assert_eq!(Config::load(&path).unwrap().organization_names().len(), 1);
+        delete_config(&path);
+    }
+
+    #[test]
+    fn edit_should_save_valid_config() {
+        let config = test::fixtures::config().create().unwrap();
+        let edited = Config {
+            date_format: Some(String::from("us")),
+            ..config.clone()
+        };
+        let toml = toml::to_string(&edited).unwrap();
+        let mut config = config;
+        input::set_backend(input::Answers {
+            string: Some(toml),
+            ..input::Answers::default()
+        });
+
+        assert_eq!(config.edit(), Ok(format!("Saved {}", config.path)));
+        let saved = fs::read_to_string(&config.path).unwrap();
+        assert!(saved.contains("\ndate_format = \"us\"\n"));
+        assert_eq!(Config::load(&config.path).unwrap(), edited);
+        delete_config(&config.path);
+    }
+
+    #[test]
+    fn edit_should_discard_invalid_config() {
+        let config = test::fixtures::config().create().unwrap();
+        let before = fs::read_to_string(&config.path).unwrap();
+        let mut config = config;
+        input::set_backend(input::Answers {
+            string: Some(String::from("{")),
+            select: Some(1),
+            ..input::Answers::default()
+        });
+
+        assert_eq!(
+            config.edit(),
+            Err(String::from("Discarded changes to config"))
+        );
+        assert_eq!(fs::read_to_string(&config.path).unwrap(), before);
+        delete_config(&config.path);
+    }
+
+    #[test]
+    fn get_should_show_value() {
+        let config = Config {
+            date_format: Some(String::from("us")),
+            ..test::fixtures::config()
+        };
+        assert_eq!(config.get("date_format"), Ok(String::from("us")));
+        assert_eq!(config.get("proxy"), Ok(String::from("Not set")));
+        assert!(config
+            .get("default_team")
+            .unwrap_err()
+            .starts_with("Unknown config key default_team, options are: branch_format"));
+        assert!(config.get("organizations").is_err());
+    }
+
+    #[test]
+    fn set_should_check_types() {
+        let mut config = test::fixtures::config().create().unwrap();
+
+        assert_eq!(config.set("spinners", "false"), Ok(String::from("✓")));
+        assert_eq!(config.set("date_format", "us"), Ok(String::from("✓")));
+        assert_eq!(
+            config.set("request_timeout_seconds", "60"),
+            Ok(String::from("✓"))
+        );
+        assert!(config
+            .set("cache_ttl_seconds", "soon")
+            .unwrap_err()
+            .starts_with("Invalid value for cache_ttl_seconds"));
+
+        let saved = Config::load(&config.path).unwrap();
+        assert_eq!(saved.spinners, Some(false));
+        assert_eq!(saved.date_format, Some(String::from("us")));
+        assert_eq!(saved.request_timeout_seconds, Some(60));
+        assert_eq!(saved.cache_ttl_seconds, None);
+
+        assert_eq!(config.set("date_format", "null"), Ok(String::from("✓")));
+        assert_eq!(Config::load(&config.path).unwrap().date_format, None);
+        delete_config(&config.path);
+    }
+
+    #[test]
+    fn migrate_should_keep_legacy_config() {
+        let legacy = Config {
+            path: legacy_path().unwrap(),
+            date_format: Some(String::from("eu")),
+            ..test::fixtures::config()
+        }
+        .create()
+        .unwrap();
+        let path = generate_path().unwrap();
+
+        migrate(&legacy.path, &path).unwrap();
+        let migrated = Config::load(&path).unwrap();
+        assert_eq!(
+            migrated,
+            Config {
+                path: path.clone(),
+                ..legacy.clone()
+            }
+        );
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("[organizations]"));
+        assert_eq!(Config::load(&legacy.path).unwrap(), legacy);
+
+        // An existing config is left alone
+        fs::write(&legacy.path, "{}").unwrap();
+        migrate(&legacy.path, &path).unwrap();
+        assert_eq!(Config::load(&path).unwrap(), migrated);
+        delete_config(&path);
+        delete_config(&legacy.path);
+    }
+
+    #[test]
+    fn with_profile_should_replace_organizations_and_settings() {
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            String::from("work"),
+            Profile {
+                organizations: Some(HashMap::from([(
+                    String::from("acme"),
+                    String::from("token2"),
+                )])),
+                date_format: Some(String::from("eu")),
+                ..Default::default()
+            },
+        );
+        let mut config = test::fixtures::config();
+        config.add_organization(String::from("personal"), String::from("token1"));
+        let config = Config {
+            branch_format: Some(String::from("{{identifier}}")),
+            date_format: Some(String::from("us")),
+            profiles: Some(profiles),
+            ..config
+        };
+
+        assert_eq!(config.clone().with_profile(None), Ok(config.clone()));
+        let work = config
+            .clone()
+            .with_profile(Some(String::from("work")))
+            .unwrap();
+        assert_eq!(work.organization_names(), vec!["acme"]);
+        assert_eq!(work.date_format, Some(String::from("eu")));
+        assert_eq!(work.branch_format, Some(String::from("{{identifier}}")));
+        assert_eq!(
+            config.with_profile(Some(String::from("home"))),
+            Err(String::from("Profile home not found, options are: work"))
+        );
+    }
+
+    #[test]
+    fn rename_organization_should_move_token_and_settings() {
+        let mut config = test::fixtures::config();
+        config.add_organization(String::from("acme"), String::from("token1"));
+        config.add_organization(String::from("other"), String::from("token2"));
+        let mut config = Config {
+            excluded_states: Some(ScopedNames {
+                organizations: HashMap::from([(
+                    String::from("acme"),
+                    vec![String::from("Shipped")],
+                )]),
+                ..Default::default()
+            }),
+            ..config
+        };
+
+        config.rename_organization("acme", String::from("Acme Corp"));
+        let mut names = config.organization_names();
+        names.sort();
+        assert_eq!(names, vec!["Acme Corp", "other"]);
+        assert_eq!(
+            config.token(&String::from("Acme Corp")),
+            Ok(String::from("token1"))
+        );
+        assert_eq!(
+            config.excluded_states(Some(&String::from("Acme Corp")), None),
+            vec!["Shipped"]
+        );
+        assert_eq!(
+            config.excluded_states(Some(&String::from("acme")), None),
+            DEFAULT_EXCLUDED_STATES.to_vec()
+        );
+    }
+
+    #[test]
+    fn update_should_add_organizations_to_profile() {
+        let mut config = Config {
+            profile: Some(String::from("work")),
+            ..test::fixtures::config().create().unwrap()
+        };
+        config
+            .update(|c| c.add_organization(String::from("acme"), String::from("token")))
+            .unwrap();
+
+        let saved = Config::load(&config.path).unwrap();
+        assert!(saved.organizations.is_empty());
+        let work = saved.with_profile(Some(String::from("work"))).unwrap();
+        assert_eq!(work.token(&String::from("acme")), Ok(String::from("token")));
+
+        config
+            .update(|c| c.remove_organization(&String::from("acme")))
+            .unwrap();
+        let work = Config::load(&config.path)
+            .unwrap()
+            .with_profile(Some(String::from("work")))
+            .unwrap();
+        assert!(work.organization_names().is_empty());
+        delete_config(&config.path);
+    }
+
+    #[test]
+    fn format_should_follow_extension() {
+        assert_eq!(Format::of("/home/bruce/.config/lnr.toml"), Format::Toml);
+        assert_eq!(Format::of("/home/bruce/.config/lnr.cfg"), Format::Json);
+    }
+
     fn delete_config(path: &str) {
         assert_matches!(fs::remove_file(path), Ok(_));
     }
@@ -0,0 +1,279 @@
+use std::fmt::Display;
+use std::ops::Range;
+
+use similar::{Algorithm, DiffTag, TextDiff};
+
+use crate::{color, input};
+
+const THEIRS_MARKER: &str = "<<<<<<< theirs\n";
+const SEPARATOR_MARKER: &str = "=======\n";
+const MINE_MARKER: &str = ">>>>>>> yours\n";
+
+/// What to do when a description was changed by someone else while it was being edited
+#[derive(Debug, PartialEq)]
+enum Resolution {
+    Merge,
+    EditMerge,
+    KeepMine,
+    KeepTheirs,
+}
+
+impl Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Resolution::Merge => "Use the merged description",
+            Resolution::EditMerge => "Edit the merged description",
+            Resolution::KeepMine => "Keep mine, discarding their changes",
+            Resolution::KeepTheirs => "Keep theirs, discarding my changes",
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// A run of base lines replaced by other lines on one side
+#[derive(Debug)]
+struct Hunk {
+    base: Range<usize>,
+    lines: Vec<String>,
+}
+
+/// Result of a three-way merge, conflicting regions are wrapped in markers
+#[derive(Debug, PartialEq)]
+struct Merged {
+    text: String,
+    conflicts: usize,
+}
+
+/// Reconcile an edited description with the latest one on the server.
+/// base is the description the editor was opened with, theirs is the latest, mine is the edit.
+pub fn resolve(base: &str, theirs: &str, mine: String) -> Result<String, String> {
+    if theirs == base || theirs == mine {
+        return Ok(mine);
+    }
+
+    let merged = merge(base, theirs, &mine);
+    println!(
+        "\n{}\n\n{}\n{}\n{}",
+        color::red_string("The description was changed by someone else while you were editing it"),
+        diff(base, theirs, "theirs"),
+        diff(base, &mine, "yours"),
+        match merged.conflicts {
+            0 => String::from("The changes merge cleanly"),
+            n => format!("{n} conflicting change(s) need to be resolved"),
+        }
+    );
+
+    let options = match merged.conflicts {
+        0 => vec![
+            Resolution::Merge,
+            Resolution::EditMerge,
+            Resolution::KeepMine,
+            Resolution::KeepTheirs,
+        ],
+        _ => vec![
+            Resolution::EditMerge,
+            Resolution::KeepMine,
+            Resolution::KeepTheirs,
+        ],
+    };
+    input::require("an interactive terminal to resolve the conflict")?;
+    match input::select("Resolve the conflict", options)? {
+        Resolution::Merge => Ok(merged.text),
+        Resolution::EditMerge => input::editor("Resolve the conflict", &merged.text),
+        Resolution::KeepMine => Ok(mine),
+        Resolution::KeepTheirs => Ok(theirs.to_string()),
+    }
+}
+
+/// Unified diff from the base to one side
+fn diff(base: &str, other: &str, name: &str) -> String {
+    TextDiff::from_lines(base, other)
+        .unified_diff()
+        .header("original", name)
+        .to_string()
+}
+
+/// Line based three-way merge. Changes that touch different lines are both applied,
+/// changes to the same lines are kept when identical and marked as a conflict otherwise.
+fn merge(base: &str, theirs: &str, mine: &str) -> Merged {
+    let base_lines = lines(base);
+    let their_hunks = hunks(&base_lines, &lines(theirs));
+    let my_hunks = hunks(&base_lines, &lines(mine));
+
+    let mut text = String::new();
+    let mut conflicts = 0;
+    let mut position = 0;
+    let (mut t, mut m) = (0, 0);
+
+    while t < their_hunks.len() || m < my_hunks.len() {
+        let start = match (their_hunks.get(t), my_hunks.get(m)) {
+            (Some(th), Some(mh)) => th.base.start.min(mh.base.start),
+            (Some(th), None) => th.base.start,
+            (None, Some(mh)) => mh.base.start,
+            (None, None) => break,
+        };
+
+        // Grow the region until no hunk from either side overlaps its end
+        let (t_first, m_first) = (t, m);
+        let mut end = start;
+        loop {
+            let overlaps = |h: &Hunk| h.base.start < end || h.base.start == start;
+            if let Some(h) = their_hunks.get(t).filter(|h| overlaps(h)) {
+                end = end.max(h.base.end);
+                t += 1;
+            } else if let Some(h) = my_hunks.get(m).filter(|h| overlaps(h)) {
+                end = end.max(h.base.end);
+                m += 1;
+            } else {
+                break;
+            }
+        }
+
+        text.extend(base_lines[position..start].iter().copied());
+        let their_region = apply(&base_lines, start..end, &their_hunks[t_first..t]);
+        let my_region = apply(&base_lines, start..end, &my_hunks[m_first..m]);
+        if t == t_first {
+            text.push_str(&my_region);
+        } else if m == m_first || their_region == my_region {
+            text.push_str(&their_region);
+        } else {
+            conflicts += 1;
+            text.push_str(THEIRS_MARKER);
+            text.push_str(&with_newline(their_region));
+            text.push_str(SEPARATOR_MARKER);
+            text.push_str(&with_newline(my_region));
+            text.push_str(MINE_MARKER);
+        }
+        position = end;
+    }
+    text.extend(base_lines[position..].iter().copied());
+
+    Merged { text, conflicts }
+}
+
+fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// The changes needed to turn base into other, adjacent changes are joined
+fn hunks(base: &[&str], other: &[&str]) -> Vec<Hunk> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for op in similar::capture_diff_slices(Algorithm::Myers, base, other) {
+        let (tag, base_range, other_range) = op.as_tag_tuple();
+        if tag == DiffTag::Equal {
+            continue;
+        }
+        let lines = other[other_range].iter().map(|l| l.to_string());
+        match hunks.last_mut() {
+            Some(last) if last.base.end == base_range.start => {
+                last.base.end = base_range.end;
+                last.lines.extend(lines);
+            }
+            _ => hunks.push(Hunk {
+                base: base_range,
+                lines: lines.collect(),
+            }),
+        }
+    }
+    hunks
+}
+
+/// The text of a region of base with one side's hunks applied
+fn apply(base: &[&str], region: Range<usize>, hunks: &[Hunk]) -> String {
+    let mut text = String::new();
+    let mut position = region.start;
+    for hunk in hunks {
+        text.extend(base[position..hunk.base.start].iter().copied());
+        text.extend(hunk.lines.iter().map(String::as_str));
+        position = hunk.base.end;
+    }
+    text.extend(base[position..region.end].iter().copied());
+    text
+}
+
+/// Markers have to start on their own line, even when the last line has no newline
+fn with_newline(mut text: String) -> String {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn merge_should_apply_changes_to_different_lines() {
+        let base = "one\ntwo\nthree\nfour\n";
+        let theirs = "ONE\ntwo\nthree\nfour\n";
+        let mine = "one\ntwo\nthree\nFOUR\nfive\n";
+
+        assert_eq!(
+            merge(base, theirs, mine),
+            Merged {
+                text: String::from("ONE\ntwo\nthree\nFOUR\nfive\n"),
+                conflicts: 0
+            }
+        );
+    }
+
+    #[test]
+    fn merge_should_mark_conflicting_changes() {
+        let base = "one\ntwo\nthree\n";
+        let theirs = "one\n2\nthree\n";
+        let mine = "one\nTWO\nthree\n";
+
+        assert_eq!(
+            merge(base, theirs, mine),
+            Merged {
+                text: String::from("one\n<<<<<<< theirs\n2\n=======\nTWO\n>>>>>>> yours\nthree\n"),
+                conflicts: 1
+            }
+        );
+    }
+
+    #[test]
+    fn merge_should_accept_identical_changes() {
+        let base = "one\ntwo\n";
+        let changed = "one\n2\n";
+
+        assert_eq!(
+            merge(base, changed, changed),
+            Merged {
+                text: String::from(changed),
+                conflicts: 0
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_should_keep_mine_when_theirs_is_unchanged() {
+        assert_eq!(
+            resolve("base", "base", String::from("mine")),
+            Ok(String::from("mine"))
+        );
+    }
+
+    #[test]
+    fn resolve_should_offer_merge() {
+        input::set_backend(input::Answers {
+            select: Some(0),
+            ..input::Answers::default()
+        });
+        assert_eq!(
+            resolve("a\nb\n", "A\nb\n", String::from("a\nB\n")),
+            Ok(String::from("A\nB\n"))
+        );
+
+        input::set_backend(input::Answers {
+            select: Some(2),
+            ..input::Answers::default()
+        });
+        assert_eq!(
+            resolve("a\n", "A\n", String::from("aa\n")),
+            Ok(String::from("A\n"))
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{color, config::Config, date, request, schema};
+
+const ISSUE_CUSTOMER_NEEDS_DOC: &str = "query (
+                    $id: String!
+                ) {
+                issue(id: $id) {
+                    identifier
+                    customerNeeds {
+                        nodes {
+                            body
+                            priority
+                            createdAt
+                            customer {
+                                name
+                            }
+                            creator {
+                                displayName
+                            }
+                        }
+                    }
+                }
+                }
+                ";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CustomerNeedsResponse {
+    data: Option<CustomerNeedsData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CustomerNeedsData {
+    issue: CustomerNeedsIssue,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct CustomerNeedsIssue {
+    identifier: String,
+    customer_needs: Needs,
+}
+
+/// Customer requests attached to an issue, created in Linear or by an integration such as Asks
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct Needs {
+    nodes: Vec<Need>,
+}
+
+impl Needs {
+    pub fn count(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct Need {
+    body: Option<String>,
+    /// 1 when the customer marked it as important, otherwise 0
+    priority: f32,
+    created_at: String,
+    customer: Option<Customer>,
+    creator: Option<Creator>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct Customer {
+    name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct Creator {
+    #[serde(rename = "displayName")]
+    display_name: String,
+}
+
+impl Need {
+    fn format(&self, date_format: &str) -> String {
+        let customer = match &self.customer {
+            Some(Customer { name }) => color::green_string(name),
+            None => color::green_string("<No customer>"),
+        };
+        let important = match self.priority > 0.0 {
+            true => format!(" | {}", color::red_string("Important")),
+            false => String::new(),
+        };
+        let creator = match &self.creator {
+            Some(Creator { display_name }) => format!(" | by {display_name}"),
+            None => String::new(),
+        };
+        let created = date::display(&self.created_at, date_format);
+        let body = self
+            .body
+            .as_deref()
+            .filter(|body| !body.trim().is_empty())
+            .map(|body| {
+                body.trim()
+                    .lines()
+                    .map(|line| format!("\n  {line}"))
+                    .collect::<String>()
+            })
+            .unwrap_or_default();
+        format!("- {customer}{important} | {created}{creator}{body}")
+    }
+}
+
+/// List the customer requests attached to an issue, most important first
+pub fn list(config: &Config, token: &str, issue_id: String) -> Result<String, String> {
+    let response = request::Gql::new(config, token, ISSUE_CUSTOMER_NEEDS_DOC)
+        .put_string("id", issue_id)
+        .run()?;
+    let CustomerNeedsIssue {
+        identifier,
+        customer_needs,
+    } = customer_needs_response(response)?;
+
+    if customer_needs.nodes.is_empty() {
+        return Ok(format!("{identifier} has no customer requests"));
+    }
+
+    let date_format = date::pattern(&config.date_format);
+    let mut needs = customer_needs.nodes;
+    needs.sort_by(|a, b| b.priority.total_cmp(&a.priority));
+    let needs = needs
+        .iter()
+        .map(|need| need.format(&date_format))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let title = color::green_string(&format!("Customer requests for {identifier}"));
+    Ok(format!("\n{title}\n\n{needs}"))
+}
+
+fn customer_needs_response(response: String) -> Result<CustomerNeedsIssue, String> {
+    let data: Result<CustomerNeedsResponse, _> = schema::parse(&response, "customer requests");
+
+    match data {
+        Ok(CustomerNeedsResponse {
+            data: Some(CustomerNeedsData { issue }),
+        }) => Ok(issue),
+        err => Err(format!(
+            "Could not parse response for customer requests:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_list() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::customer_needs())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            list(&config, "1234", String::from("BE-3354")),
+            Ok(String::from(
+                "\nCustomer requests for BE-3354\n\n\
+                 - Wayne Enterprises | Important | 2025-10-02 14:30 | by Alfred\n  \
+                 Needs this before the board meeting\n  Second line\n\
+                 - Daily Planet | 2025-10-01 09:00"
+            ))
+        );
+        mock.assert();
+    }
+}
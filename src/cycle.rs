@@ -0,0 +1,583 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use crate::{
+    color,
+    config::Config,
+    date,
+    request::{self, PageInfo},
+    schema,
+    stats::{count, points_label},
+    team::Team,
+};
+
+const CYCLES_DOC: &str = "query (
+                    $teamId: ID!
+                ) {
+                cycles(
+                    filter: { team: { id: { eq: $teamId } }, isPast: { eq: false } }
+                ) {
+                    nodes {
+                        id
+                        number
+                        name
+                        startsAt
+                        endsAt
+                    }
+                }
+                }
+                ";
+
+const ACTIVE_CYCLE_DOC: &str = "query (
+                    $teamId: String!
+                ) {
+                team(id: $teamId) {
+                    activeCycle {
+                        id
+                        number
+                        name
+                        startsAt
+                        endsAt
+                        issues(first: 250) {
+                            nodes {
+                                identifier
+                                title
+                                estimate
+                                addedToCycleAt
+                                state {
+                                    type
+                                }
+                                assignee {
+                                    displayName
+                                }
+                            }
+                            pageInfo {
+                                hasNextPage
+                                endCursor
+                            }
+                        }
+                    }
+                }
+                }
+                ";
+
+const CYCLE_ISSUES_DOC: &str = "query (
+                    $id: String!
+                    $after: String
+                ) {
+                cycle(id: $id) {
+                    issues(first: 250, after: $after) {
+                        nodes {
+                            identifier
+                            title
+                            estimate
+                            addedToCycleAt
+                            state {
+                                type
+                            }
+                            assignee {
+                                displayName
+                            }
+                        }
+                        pageInfo {
+                            hasNextPage
+                            endCursor
+                        }
+                    }
+                }
+                }
+                ";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CyclesResponse {
+    data: Option<CyclesData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CyclesData {
+    cycles: Cycles,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct Cycles {
+    nodes: Vec<Cycle>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Cycle {
+    pub id: String,
+    #[serde(default)]
+    pub number: f32,
+    pub name: Option<String>,
+    #[serde(rename = "startsAt", default)]
+    starts_at: String,
+    #[serde(rename = "endsAt", default)]
+    ends_at: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ActiveCycleResponse {
+    data: Option<ActiveCycleData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ActiveCycleData {
+    team: ActiveCycleTeam,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ActiveCycleTeam {
+    active_cycle: Option<ActiveCycle>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct ActiveCycle {
+    #[serde(flatten)]
+    cycle: Cycle,
+    issues: CycleIssues,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct CycleIssues {
+    nodes: Vec<CycleIssue>,
+    page_info: Option<PageInfo>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CycleIssuesResponse {
+    data: CycleIssuesData,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CycleIssuesData {
+    cycle: CycleIssuesCycle,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CycleIssuesCycle {
+    issues: CycleIssues,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct CycleIssue {
+    identifier: String,
+    #[serde(default)]
+    title: String,
+    estimate: Option<f32>,
+    added_to_cycle_at: Option<String>,
+    #[serde(default)]
+    state: CycleIssueState,
+    assignee: Option<CycleAssignee>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct CycleIssueState {
+    #[serde(rename = "type")]
+    state_type: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct CycleAssignee {
+    display_name: String,
+}
+
+impl CycleIssue {
+    fn is_completed(&self) -> bool {
+        self.state.state_type == "completed"
+    }
+
+    fn points(&self) -> f32 {
+        self.estimate.unwrap_or_default()
+    }
+}
+
+/// Issues and points, with how many of them are completed
+#[derive(Debug, Default)]
+struct Load {
+    issues: usize,
+    completed_issues: usize,
+    points: f32,
+    completed_points: f32,
+}
+
+impl Load {
+    fn add(&mut self, issue: &CycleIssue) {
+        self.issues += 1;
+        self.points += issue.points();
+        if issue.is_completed() {
+            self.completed_issues += 1;
+            self.completed_points += issue.points();
+        }
+    }
+}
+
+impl Display for Cycle {
+    /// This is for rendering in a select
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dates = [&self.starts_at, &self.ends_at].map(|value| {
+            date::timestamp_date(value).map_or(String::new(), |d| date::to_string(&d))
+        });
+        match &self.name {
+            Some(name) => write!(f, "{name} ({} to {})", dates[0], dates[1]),
+            None => write!(f, "Cycle {} ({} to {})", self.number, dates[0], dates[1]),
+        }
+    }
+}
+
+/// The current and upcoming cycles of a team, in order
+pub fn upcoming(config: &Config, token: &str, team: &Team) -> Result<Vec<Cycle>, String> {
+    let response = request::Gql::new(config, token, CYCLES_DOC)
+        .cached()
+        .put_string("teamId", team.id.clone())
+        .run()?;
+    let mut cycles = cycles_response(response)?;
+    cycles.sort_by(|a, b| a.number.total_cmp(&b.number));
+    Ok(cycles)
+}
+
+/// Dashboard of the active cycle: its scope, completed and remaining points, issues added after it started,
+/// and the load on each person. Canceled issues are left out.
+pub fn report(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    today: NaiveDate,
+) -> Result<String, String> {
+    let response = request::Gql::new(config, token, ACTIVE_CYCLE_DOC)
+        .put_string("teamId", team.id.clone())
+        .run()?;
+    let Some(ActiveCycle { cycle, issues }) = active_cycle_response(response)? else {
+        return Ok(format!("Team {} has no active cycle", team.name));
+    };
+    let issues = remaining_issues(config, token, &cycle, issues)?
+        .into_iter()
+        .filter(|issue| issue.state.state_type != "canceled")
+        .collect::<Vec<CycleIssue>>();
+
+    let days_left = match date::timestamp_date(&cycle.ends_at).map(|ends| (ends - today).num_days())
+    {
+        Some(1) => String::from(", 1 day left"),
+        Some(days) if days > 1 => format!(", {days} days left"),
+        Some(_) => String::from(", ends today"),
+        None => String::new(),
+    };
+
+    let mut scope = Load::default();
+    issues.iter().for_each(|issue| scope.add(issue));
+    let percent = match scope.points {
+        0.0 if scope.issues == 0 => 0.0,
+        0.0 => scope.completed_issues as f32 / scope.issues as f32 * 100.0,
+        points => scope.completed_points / points * 100.0,
+    };
+    let starts_at = cycle.starts_at.clone();
+    let added = issues
+        .iter()
+        .filter(|issue| {
+            issue
+                .added_to_cycle_at
+                .as_ref()
+                .is_some_and(|added| *added > starts_at)
+        })
+        .collect::<Vec<&CycleIssue>>();
+    let added_points = added.iter().map(|issue| issue.points()).sum::<f32>();
+
+    let totals = [
+        ("Scope", scope.issues, scope.points, String::new()),
+        (
+            "Completed",
+            scope.completed_issues,
+            scope.completed_points,
+            format!(" ({percent:.0}%)"),
+        ),
+        (
+            "Remaining",
+            scope.issues - scope.completed_issues,
+            scope.points - scope.completed_points,
+            String::new(),
+        ),
+        (
+            "Added",
+            added.len(),
+            added_points,
+            String::from(" after the start"),
+        ),
+    ]
+    .map(|(name, issues, points, suffix)| {
+        format!(
+            "{name: <9}  {} | {}{suffix}",
+            count(issues),
+            points_label(points)
+        )
+    })
+    .join("\n");
+
+    let added_list = match added.is_empty() {
+        true => String::new(),
+        false => {
+            let lines = added
+                .iter()
+                .map(|issue| format!("- {} {}", issue.identifier, issue.title))
+                .collect::<Vec<String>>()
+                .join("\n");
+            format!(
+                "\n\n{}\n{lines}",
+                color::green_string("Added after the start")
+            )
+        }
+    };
+
+    let mut people: Vec<(String, Load)> = Vec::new();
+    for issue in &issues {
+        let name = issue
+            .assignee
+            .as_ref()
+            .map_or_else(|| String::from("Unassigned"), |a| a.display_name.clone());
+        match people.iter_mut().find(|(person, _)| *person == name) {
+            Some((_, load)) => load.add(issue),
+            None => {
+                let mut load = Load::default();
+                load.add(issue);
+                people.push((name, load));
+            }
+        }
+    }
+    people.sort_by(|(a, _), (b, _)| {
+        (a == "Unassigned")
+            .cmp(&(b == "Unassigned"))
+            .then(a.to_lowercase().cmp(&b.to_lowercase()))
+    });
+    let width = people.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let load = people
+        .iter()
+        .map(|(name, load)| {
+            format!(
+                "- {name: <width$}  {}/{} issues | {}/{} points",
+                load.completed_issues, load.issues, load.completed_points, load.points
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    Ok(format!(
+        "\n{}{days_left}\n\n{totals}{added_list}\n\n{}\n{load}",
+        color::green_string(&cycle.to_string()),
+        color::green_string("Load")
+    ))
+}
+
+/// The issues of the first page, followed by those of any later pages
+fn remaining_issues(
+    config: &Config,
+    token: &str,
+    cycle: &Cycle,
+    first_page: CycleIssues,
+) -> Result<Vec<CycleIssue>, String> {
+    let CycleIssues {
+        nodes: mut issues,
+        page_info,
+    } = first_page;
+    let mut after = page_info.and_then(|p| p.next_cursor());
+    while after.is_some() {
+        let response: CycleIssuesResponse = request::Gql::new(config, token, CYCLE_ISSUES_DOC)
+            .put_string("id", cycle.id.clone())
+            .maybe_put_string("after", after)
+            .execute()?;
+        let CycleIssues { nodes, page_info } = response.data.cycle.issues;
+        issues.extend(nodes);
+        after = page_info.and_then(|p| p.next_cursor());
+    }
+    Ok(issues)
+}
+
+/// Find a cycle by number or name, i.e. 12 or "Sprint 12"
+pub fn find(cycles: Vec<Cycle>, query: &str) -> Result<Cycle, String> {
+    let number = query.parse::<f32>().ok();
+    cycles
+        .into_iter()
+        .find(|cycle| {
+            Some(cycle.number) == number
+                || cycle
+                    .name
+                    .as_ref()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(query))
+        })
+        .ok_or_else(|| format!("Cycle {query} not found in the current or upcoming cycles"))
+}
+
+fn cycles_response(response: String) -> Result<Vec<Cycle>, String> {
+    let data: Result<CyclesResponse, _> = schema::parse(&response, "cycles");
+
+    match data {
+        Ok(CyclesResponse {
+            data: Some(CyclesData {
+                cycles: Cycles { nodes },
+            }),
+        }) => Ok(nodes),
+        err => Err(format!(
+            "Could not parse response for cycles:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+fn active_cycle_response(response: String) -> Result<Option<ActiveCycle>, String> {
+    let data: Result<ActiveCycleResponse, _> = schema::parse(&response, "active cycle");
+
+    match data {
+        Ok(ActiveCycleResponse {
+            data:
+                Some(ActiveCycleData {
+                    team: ActiveCycleTeam { active_cycle },
+                }),
+        }) => Ok(active_cycle),
+        err => Err(format!(
+            "Could not parse response for active cycle:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_upcoming() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"teamId\":\"123456\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::cycles())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let cycles = upcoming(&config, "1234", &test::fixtures::team()).unwrap();
+        let names = cycles
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            names,
+            vec![
+                "Cycle 12 (2025-10-01 to 2025-10-15)",
+                "Hardening (2025-10-15 to 2025-10-29)"
+            ]
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_report() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"teamId\":\"123456\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::active_cycle(false))
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let today = NaiveDate::from_ymd_opt(2025, 10, 10).unwrap();
+
+        assert_eq!(
+            report(&config, "1234", &test::fixtures::team(), today),
+            Ok(String::from(
+                "\nCycle 12 (2025-10-01 to 2025-10-15), 5 days left\n\n\
+                 Scope      4 issues | 10 points\n\
+                 Completed  2 issues | 5 points (50%)\n\
+                 Remaining  2 issues | 5 points\n\
+                 Added      1 issue | 2 points after the start\n\n\
+                 Added after the start\n\
+                 - BE-4 Hotfix\n\n\
+                 Load\n\
+                 - Alfred      0/1 issues | 0/2 points\n\
+                 - Bruce       2/2 issues | 5/5 points\n\
+                 - Unassigned  0/1 issues | 0/3 points"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn report_should_fetch_every_page_of_issues() {
+        let mut server = mockito::Server::new();
+        let first_page = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"teamId\":\"123456\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::active_cycle(true))
+            .create();
+        let second_page = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"after\":\"issues-1\",\"id\":\"c12\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::cycle_issues())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let today = NaiveDate::from_ymd_opt(2025, 10, 10).unwrap();
+
+        let report = report(&config, "1234", &test::fixtures::team(), today).unwrap();
+        assert!(report.contains("Scope      5 issues | 11 points\n"));
+        assert!(report.contains("- Robin       0/1 issues | 0/1 points"));
+        first_page.assert();
+        second_page.assert();
+    }
+
+    #[test]
+    fn find_should_match_number_or_name() {
+        let cycles = || {
+            vec![
+                Cycle {
+                    id: String::from("c12"),
+                    number: 12.0,
+                    ..Default::default()
+                },
+                Cycle {
+                    id: String::from("c13"),
+                    number: 13.0,
+                    name: Some(String::from("Hardening")),
+                    ..Default::default()
+                },
+            ]
+        };
+
+        assert_eq!(find(cycles(), "12").map(|c| c.id), Ok(String::from("c12")));
+        assert_eq!(
+            find(cycles(), "hardening").map(|c| c.id),
+            Ok(String::from("c13"))
+        );
+        assert_eq!(
+            find(cycles(), "14"),
+            Err(String::from(
+                "Cycle 14 not found in the current or upcoming cycles"
+            ))
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+use std::fmt::Write;
+
+const FORMAT: &str = "%Y-%m-%d";
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Parse a date such as 2025-10-01, today, tomorrow, friday, +3d, or +2w
+pub fn parse(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let input = input.trim().to_lowercase();
+    let invalid = || {
+        format!("Could not parse date {input}, use i.e. 2025-10-01, tomorrow, friday, +3d, or +2w")
+    };
+
+    if let Ok(date) = NaiveDate::parse_from_str(&input, FORMAT) {
+        return Ok(date);
+    }
+
+    match input.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + Duration::days(1)),
+        _ => (),
+    }
+
+    if let Ok(weekday) = input.parse::<Weekday>() {
+        let days_ahead = (7 + weekday.num_days_from_monday() as i64
+            - today.weekday().num_days_from_monday() as i64)
+            % 7;
+        return Ok(today + Duration::days(days_ahead));
+    }
+
+    if let Some(offset) = input.strip_prefix('+') {
+        let (number, unit) = offset.split_at(offset.len().saturating_sub(1));
+        let number = number.parse::<i64>().map_err(|_| invalid())?;
+        return match unit {
+            "d" => Ok(today + Duration::days(number)),
+            "w" => Ok(today + Duration::weeks(number)),
+            _ => Err(invalid()),
+        };
+    }
+
+    Err(invalid())
+}
+
+/// Parse a date in the past such as 2025-10-01, today, yesterday, friday, -3d, or -2w.
+/// A weekday is the most recent one before today.
+pub fn parse_since(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let input = input.trim().to_lowercase();
+    let invalid = || {
+        format!("Could not parse date {input}, use i.e. 2025-10-01, yesterday, friday, -3d, or -2w")
+    };
+
+    if let Ok(date) = NaiveDate::parse_from_str(&input, FORMAT) {
+        return Ok(date);
+    }
+
+    match input.as_str() {
+        "today" => return Ok(today),
+        "yesterday" => return Ok(today - Duration::days(1)),
+        _ => (),
+    }
+
+    if let Ok(weekday) = input.parse::<Weekday>() {
+        let days_back = (7 + today.weekday().num_days_from_monday() as i64
+            - weekday.num_days_from_monday() as i64
+            - 1)
+            % 7
+            + 1;
+        return Ok(today - Duration::days(days_back));
+    }
+
+    if let Some(offset) = input.strip_prefix('-') {
+        let (number, unit) = offset.split_at(offset.len().saturating_sub(1));
+        let number = number.parse::<i64>().map_err(|_| invalid())?;
+        return match unit {
+            "d" => Ok(today - Duration::days(number)),
+            "w" => Ok(today - Duration::weeks(number)),
+            _ => Err(invalid()),
+        };
+    }
+
+    Err(invalid())
+}
+
+/// Format a date the way the Linear API expects it
+pub fn to_string(date: &NaiveDate) -> String {
+    date.format(FORMAT).to_string()
+}
+
+/// The strftime pattern for the date_format in config, which can name a preset
+pub fn pattern(date_format: &Option<String>) -> String {
+    match date_format.as_deref() {
+        None | Some("iso") => String::from(FORMAT),
+        Some("us") => String::from("%m/%d/%Y"),
+        Some("eu") => String::from("%d/%m/%Y"),
+        Some(pattern) => pattern.to_string(),
+    }
+}
+
+/// Render a date or timestamp from the Linear API, timestamps are shown in UTC with the time.
+/// Values that can't be parsed, or patterns chrono rejects, fall back to the original value.
+pub fn display(value: &str, pattern: &str) -> String {
+    let mut output = String::new();
+    let written = if let Ok(date) = NaiveDate::parse_from_str(value, FORMAT) {
+        write!(output, "{}", date.format(pattern))
+    } else if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        write!(
+            output,
+            "{}",
+            timestamp.naive_utc().format(&format!("{pattern} %H:%M"))
+        )
+    } else {
+        return value.to_string();
+    };
+
+    match written {
+        Ok(_) => output,
+        Err(_) => value.to_string(),
+    }
+}
+
+/// The date of a timestamp from the Linear API, in UTC
+pub fn timestamp_date(value: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|timestamp| timestamp.naive_utc().date())
+}
+
+/// The Monday of the week a date falls in
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Whether a date from the Linear API is before today
+pub fn is_overdue(date: &str, today: NaiveDate) -> bool {
+    NaiveDate::parse_from_str(date, FORMAT).is_ok_and(|date| date < today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn date(string: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(string, FORMAT).unwrap()
+    }
+
+    #[test]
+    fn parse_should_handle_formats() {
+        // A Wednesday
+        let today = date("2025-10-01");
+        assert_eq!(parse("2025-12-25", today), Ok(date("2025-12-25")));
+        assert_eq!(parse("today", today), Ok(date("2025-10-01")));
+        assert_eq!(parse("Tomorrow", today), Ok(date("2025-10-02")));
+        assert_eq!(parse("friday", today), Ok(date("2025-10-03")));
+        assert_eq!(parse("wed", today), Ok(date("2025-10-01")));
+        assert_eq!(parse("monday", today), Ok(date("2025-10-06")));
+        assert_eq!(parse("+3d", today), Ok(date("2025-10-04")));
+        assert_eq!(parse("+2w", today), Ok(date("2025-10-15")));
+    }
+
+    #[test]
+    fn parse_should_reject_invalid() {
+        let today = date("2025-10-01");
+        let error = Err(String::from(
+            "Could not parse date +3y, use i.e. 2025-10-01, tomorrow, friday, +3d, or +2w",
+        ));
+        assert_eq!(parse("+3y", today), error);
+        assert!(parse("someday", today).is_err());
+        assert!(parse("+", today).is_err());
+    }
+
+    #[test]
+    fn parse_since_should_look_back() {
+        // A Wednesday
+        let today = date("2025-10-01");
+        assert_eq!(parse_since("2025-09-01", today), Ok(date("2025-09-01")));
+        assert_eq!(parse_since("Yesterday", today), Ok(date("2025-09-30")));
+        assert_eq!(parse_since("monday", today), Ok(date("2025-09-29")));
+        assert_eq!(parse_since("friday", today), Ok(date("2025-09-26")));
+        assert_eq!(parse_since("wed", today), Ok(date("2025-09-24")));
+        assert_eq!(parse_since("-3d", today), Ok(date("2025-09-28")));
+        assert_eq!(parse_since("-2w", today), Ok(date("2025-09-17")));
+        assert!(parse_since("+3d", today).is_err());
+    }
+
+    #[test]
+    fn display_should_use_pattern() {
+        assert_eq!(display("2025-10-01", &pattern(&None)), "2025-10-01");
+        assert_eq!(
+            display("2025-10-01", &pattern(&Some(String::from("us")))),
+            "10/01/2025"
+        );
+        assert_eq!(
+            display(
+                "2024-05-02T13:45:00.000Z",
+                &pattern(&Some(String::from("eu")))
+            ),
+            "02/05/2024 13:45"
+        );
+        assert_eq!(display("2025-10-01", "%d %b %Y"), "01 Oct 2025");
+        assert_eq!(display("2025-10-01", "%Q"), "2025-10-01");
+        assert_eq!(display("soon", "%d %b %Y"), "soon");
+    }
+
+    #[test]
+    fn week_start_should_be_monday() {
+        assert_eq!(week_start(date("2025-10-01")), date("2025-09-29"));
+        assert_eq!(week_start(date("2025-09-29")), date("2025-09-29"));
+        assert_eq!(week_start(date("2025-10-05")), date("2025-09-29"));
+    }
+
+    #[test]
+    fn is_overdue_should_compare_to_today() {
+        let today = date("2025-10-01");
+        assert!(is_overdue("2025-09-30", today));
+        assert!(!is_overdue("2025-10-01", today));
+        assert!(!is_overdue("nonsense", today));
+    }
+}
@@ -0,0 +1,224 @@
+use clap::{error::ErrorKind, parser::ValueSource, Arg, ArgMatches, Command};
+use serde_json::Value;
+use std::ffi::OsString;
+
+/// The command line with flags from the defaults config added, for the subcommand being run.
+/// Defaults are nested by subcommand, i.e. {"issue": {"list": {"noproject": true}}}, and
+/// are skipped when the flag, or one that conflicts with it, is already on the command line,
+/// or when it requires a flag that isn't. They go before any -- so they aren't taken as values.
+/// Arguments that don't parse are returned as is so that clap can report the error.
+pub fn apply(
+    command: Command,
+    args: Vec<OsString>,
+    defaults: &Value,
+) -> Result<Vec<OsString>, String> {
+    let Ok(matches) = command.clone().try_get_matches_from(&args) else {
+        return Ok(args);
+    };
+
+    let root = command;
+    let mut command = &root;
+    let mut matches = &matches;
+    let mut table = defaults;
+    let mut path = vec![String::from("defaults")];
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        let Some(subcommand) = command.find_subcommand(name) else {
+            return Ok(args);
+        };
+        let Some(sub_table) = table.get(name) else {
+            return Ok(args);
+        };
+        command = subcommand;
+        matches = sub_matches;
+        table = sub_table;
+        path.push(name.to_string());
+    }
+    let path = path.join(".");
+    let Value::Object(flags) = table else {
+        return Err(format!("{path} in config should be a table of flags"));
+    };
+
+    let mut args = args;
+    for (key, value) in flags {
+        let id = key.replace('-', "_");
+        let Some(arg) = command.get_arguments().find(|a| a.get_id() == id.as_str()) else {
+            return Err(format!("Unknown flag {key} in {path}"));
+        };
+        let Some(long) = arg.get_long() else {
+            return Err(format!("{key} in {path} is not a flag"));
+        };
+        if on_command_line(matches, &id) || conflicts_with_command_line(command, matches, arg) {
+            continue;
+        }
+
+        let flag = OsString::from(format!("--{long}"));
+        let mut flag_args = Vec::new();
+        match value {
+            Value::Bool(true) => flag_args.push(flag),
+            Value::Bool(false) => (),
+            Value::Array(values) => {
+                for value in values {
+                    flag_args.push(flag.clone());
+                    flag_args.push(scalar(value, key, &path)?);
+                }
+            }
+            value => {
+                flag_args.push(flag);
+                flag_args.push(scalar(value, key, &path)?);
+            }
+        }
+
+        let end = args
+            .iter()
+            .position(|arg| arg.as_os_str() == "--")
+            .unwrap_or(args.len());
+        let mut with_default = args.clone();
+        with_default.splice(end..end, flag_args);
+        match root.clone().try_get_matches_from(&with_default) {
+            Err(e) if e.kind() == ErrorKind::MissingRequiredArgument => continue,
+            _ => args = with_default,
+        }
+    }
+    Ok(args)
+}
+
+fn on_command_line(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+/// Conflicts are declared on one side only, so check both directions
+fn conflicts_with_command_line(command: &Command, matches: &ArgMatches, arg: &Arg) -> bool {
+    let conflicts = |a: &Arg, b: &Arg| {
+        command
+            .get_arg_conflicts_with(a)
+            .iter()
+            .any(|conflict| conflict.get_id() == b.get_id())
+    };
+    command.get_arguments().any(|other| {
+        on_command_line(matches, other.get_id().as_str())
+            && (conflicts(arg, other) || conflicts(other, arg))
+    })
+}
+
+fn scalar(value: &Value, key: &str, path: &str) -> Result<OsString, String> {
+    match value {
+        Value::String(string) => Ok(OsString::from(string)),
+        Value::Number(number) => Ok(OsString::from(number.to_string())),
+        _ => Err(format!(
+            "{key} in {path} should be true, false, a string, a number, or a list"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, Parser, Subcommand};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(subcommand)]
+        command: Commands,
+    }
+
+    #[derive(Subcommand)]
+    enum Commands {
+        List {
+            #[arg(short, long)]
+            noproject: bool,
+            #[arg(short, long, conflicts_with = "noproject")]
+            project: Option<String>,
+            #[arg(short, long)]
+            sort: Option<String>,
+            #[arg(short, long)]
+            label: Vec<String>,
+            #[arg(short, long)]
+            watch: bool,
+            #[arg(short, long, requires = "watch")]
+            interval: Option<u64>,
+            args: Vec<String>,
+        },
+    }
+
+    fn apply_to(args: &[&str], defaults: Value) -> Result<Vec<String>, String> {
+        let args = args.iter().map(OsString::from).collect();
+        apply(Cli::command(), args, &defaults).map(|args| {
+            args.into_iter()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn apply_should_append_defaults() {
+        let defaults =
+            json!({"list": {"noproject": true, "sort": "priority", "label": ["Bug", "UI"]}});
+        assert_eq!(
+            apply_to(&["lnr", "list"], defaults),
+            Ok(vec![
+                "lnr",
+                "list",
+                "--label",
+                "Bug",
+                "--label",
+                "UI",
+                "--noproject",
+                "--sort",
+                "priority"
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect())
+        );
+    }
+
+    #[test]
+    fn apply_should_skip_flags_on_the_command_line() {
+        let defaults = json!({"list": {"noproject": true, "sort": "priority"}});
+        assert_eq!(
+            apply_to(&["lnr", "list", "-p", "Lair", "-s", "title"], defaults),
+            Ok(vec!["lnr", "list", "-p", "Lair", "-s", "title"]
+                .into_iter()
+                .map(String::from)
+                .collect())
+        );
+    }
+
+    #[test]
+    fn apply_should_insert_defaults_before_double_dash() {
+        let defaults = json!({"list": {"sort": "priority"}});
+        assert_eq!(
+            apply_to(&["lnr", "list", "--", "-x"], defaults),
+            Ok(vec!["lnr", "list", "--sort", "priority", "--", "-x"]
+                .into_iter()
+                .map(String::from)
+                .collect())
+        );
+    }
+
+    #[test]
+    fn apply_should_skip_defaults_that_require_missing_flags() {
+        let defaults = json!({"list": {"interval": 10}});
+        assert_eq!(
+            apply_to(&["lnr", "list"], defaults.clone()),
+            Ok(vec![String::from("lnr"), String::from("list")])
+        );
+        assert_eq!(
+            apply_to(&["lnr", "list", "--watch"], defaults),
+            Ok(vec!["lnr", "list", "--watch", "--interval", "10"]
+                .into_iter()
+                .map(String::from)
+                .collect())
+        );
+    }
+
+    #[test]
+    fn apply_should_reject_unknown_flags() {
+        assert_eq!(
+            apply_to(&["lnr", "list"], json!({"list": {"colour": "red"}})),
+            Err(String::from("Unknown flag colour in defaults.list"))
+        );
+    }
+}
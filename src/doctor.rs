@@ -0,0 +1,229 @@
+use serde::Deserialize;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    color,
+    config::{self, Config},
+    request, schema,
+};
+
+const VIEWER_NAME_DOC: &str = "query {
+                viewer {
+                    id
+                    name
+                }
+                }
+                ";
+
+#[derive(Deserialize, Debug)]
+struct ViewerNameResponse {
+    data: Option<ViewerNameData>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ViewerNameData {
+    viewer: ViewerName,
+}
+
+#[derive(Deserialize, Debug)]
+struct ViewerName {
+    name: String,
+}
+
+/// The outcome of one diagnostic
+struct Check {
+    name: String,
+    result: Result<String, String>,
+}
+
+impl Check {
+    fn new(name: &str, result: Result<String, String>) -> Check {
+        Check {
+            name: name.to_string(),
+            result,
+        }
+    }
+
+    fn format(&self) -> String {
+        match &self.result {
+            Ok(detail) => format!("{} {}: {detail}", color::green_string("✓"), self.name),
+            Err(detail) => format!("{} {}: {detail}", color::red_string("✗"), self.name),
+        }
+    }
+}
+
+/// Check the config, tokens, git, the editor, and whether Linear can be reached, printing a report
+pub fn run(config_path: Option<String>, timeout: Option<u64>) -> Result<String, String> {
+    let mut checks = Vec::new();
+
+    let path = config::resolve_path(config_path)?;
+    let config = if Path::new(&path).exists() {
+        let config = Config::load(&path);
+        checks.push(Check::new(
+            "Config",
+            config
+                .as_ref()
+                .map(|_| format!("{path} is valid"))
+                .map_err(String::clone),
+        ));
+        config.ok()
+    } else {
+        checks.push(Check::new(
+            "Config",
+            Err(format!(
+                "{path} does not exist, add an organization with org add"
+            )),
+        ));
+        None
+    };
+    let config = match config {
+        Some(config) => Config {
+            request_timeout_seconds: timeout.or(config.request_timeout_seconds),
+            spinners: Some(false),
+            ..config
+        },
+        None => Config {
+            path,
+            request_timeout_seconds: timeout,
+            spinners: Some(false),
+            ..Config::new()?
+        },
+    };
+
+    checks.push(Check::new("Network", reachable(&config)));
+
+    let mut tokens = config
+        .organizations
+        .iter()
+        .map(|(org_name, token)| (org_name.clone(), token.clone()))
+        .collect::<Vec<(String, String)>>();
+    for (profile_name, profile) in config.profiles.clone().unwrap_or_default() {
+        for (org_name, token) in profile.organizations.unwrap_or_default() {
+            tokens.push((format!("{org_name} in profile {profile_name}"), token));
+        }
+    }
+    tokens.sort();
+    for (name, token) in tokens {
+        checks.push(Check::new(
+            &format!("Token for {name}"),
+            token_owner(&config, &token),
+        ));
+    }
+
+    checks.push(Check::new("Git", git_version()));
+    checks.push(Check::new("Editor", editor()));
+
+    println!(
+        "{}",
+        checks
+            .iter()
+            .map(Check::format)
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    match checks.iter().filter(|c| c.result.is_err()).count() {
+        0 => Ok(String::from("\nNo problems found")),
+        failed => Err(format!("\n{failed} problem(s) found")),
+    }
+}
+
+fn reachable(config: &Config) -> Result<String, String> {
+    request::reachable(config).map(|_| String::from("Linear API can be reached"))
+}
+
+/// Name of the user a token belongs to, which fails when the token is invalid or revoked
+fn token_owner(config: &Config, token: &str) -> Result<String, String> {
+    let response = request::Gql::new(config, token, VIEWER_NAME_DOC).run()?;
+    let data: Result<ViewerNameResponse, _> = schema::parse(&response, "viewer");
+
+    match data {
+        Ok(ViewerNameResponse {
+            data: Some(ViewerNameData { viewer }),
+        }) => Ok(format!("valid, belongs to {}", viewer.name)),
+        _ => Err(format!("token was rejected: {response}")),
+    }
+}
+
+fn git_version() -> Result<String, String> {
+    let output = std::process::Command::new("git")
+        .arg("--version")
+        .output()
+        .map_err(|_| String::from("git was not found, branch based commands won't work"))?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The editor used for descriptions, found the same way as the editor prompt finds it
+fn editor() -> Result<String, String> {
+    let command = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| String::from(default_editor()));
+    let program = command.split_whitespace().next().unwrap_or_default();
+
+    match find_executable(program, &env::var_os("PATH").unwrap_or_default()) {
+        Some(path) => Ok(format!("{command} ({})", path.display())),
+        None => Err(format!("{program} was not found, set $EDITOR or $VISUAL")),
+    }
+}
+
+fn default_editor() -> &'static str {
+    if cfg!(windows) {
+        "notepad"
+    } else {
+        "nano"
+    }
+}
+
+/// The path of a program, looking in PATH unless it is already a path
+fn find_executable(program: &str, path_var: &std::ffi::OsStr) -> Option<PathBuf> {
+    let program_path = Path::new(program);
+    if program_path.components().count() > 1 {
+        return program_path.is_file().then(|| program_path.to_path_buf());
+    }
+    env::split_paths(path_var)
+        .flat_map(|dir| {
+            let candidate = dir.join(program);
+            [candidate.with_extension("exe"), candidate]
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_token_owner() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::viewer_page_2())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            token_owner(&config, "1234"),
+            Ok(String::from("valid, belongs to Bruce Wayne"))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn find_executable_should_search_path() {
+        let dir = std::env::temp_dir().join(format!("lnr-doctor-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("myeditor"), "").unwrap();
+        let path_var = env::join_paths([dir.clone()]).unwrap();
+
+        assert_eq!(
+            find_executable("myeditor", &path_var),
+            Some(dir.join("myeditor"))
+        );
+        assert_eq!(find_executable("missing", &path_var), None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,406 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::{color, config::Config, date, request, schema, team::Team};
+
+const DOCUMENTS_DOC: &str = "
+        query ($filter: DocumentFilter) {
+            documents (first: 100, filter: $filter, orderBy: updatedAt) {
+                nodes {
+                    id
+                    slugId
+                    title
+                    updatedAt
+                    project {
+                        name
+                    }
+                    team {
+                        name
+                    }
+                }
+            }
+        }";
+
+const DOCUMENT_DOC: &str = "
+        query ($id: String!) {
+            document (id: $id) {
+                id
+                slugId
+                title
+                content
+                url
+                createdAt
+                updatedAt
+                creator {
+                    displayName
+                }
+                project {
+                    name
+                }
+            }
+        }";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct DocumentsResponse {
+    data: Option<DocumentsData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct DocumentsData {
+    documents: Documents,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct Documents {
+    nodes: Vec<Document>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct DocumentResponse {
+    data: Option<DocumentData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct DocumentData {
+    document: Document,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    id: String,
+    /// The short id at the end of the document url, accepted by doc view
+    #[serde(default)]
+    slug_id: String,
+    #[serde(default)]
+    title: String,
+    /// Markdown, only fetched for a single document
+    content: Option<String>,
+    #[serde(default)]
+    url: String,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    creator: Option<Person>,
+    project: Option<DocumentProject>,
+    /// Set for documents that belong to a team rather than a project
+    team: Option<DocumentTeam>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct Person {
+    display_name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct DocumentProject {
+    name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct DocumentTeam {
+    name: String,
+}
+
+/// Documents, most recently updated first. Filters by project name and by team, which matches
+/// documents of the team and of projects the team is part of.
+pub fn list(
+    config: &Config,
+    token: &str,
+    project: &Option<String>,
+    team: &Option<Team>,
+) -> Result<String, String> {
+    let filter = filter(project, team);
+    let mut gql_variables = HashMap::new();
+    gql_variables.insert(String::from("filter"), filter);
+    let response = request::Gql::new(config, token, DOCUMENTS_DOC)
+        .put_variables(gql_variables)
+        .run()?;
+    let documents = documents_response(response)?;
+    if documents.is_empty() {
+        return Ok(String::from("No documents"));
+    }
+
+    let date_format = date::pattern(&config.date_format);
+    let width = documents.iter().map(|d| d.slug_id.len()).max().unwrap_or(0);
+    let lines = documents
+        .iter()
+        .map(|document| {
+            let details = [
+                document
+                    .project
+                    .as_ref()
+                    .map(|p| p.name.clone())
+                    .or_else(|| document.team.as_ref().map(|t| t.name.clone())),
+                document.updated_at.as_ref().map(|updated_at| {
+                    format!("Updated {}", date::display(updated_at, &date_format))
+                }),
+            ]
+            .into_iter()
+            .flatten()
+            .map(|detail| format!(" | {detail}"))
+            .collect::<String>();
+            format!(
+                "- {: <width$} | {}{details}",
+                document.slug_id,
+                color::green_string(&document.title)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    Ok(format!("\n{}\n\n{lines}", color::green_string("Documents")))
+}
+
+/// The DocumentFilter for doc list, null when there is nothing to filter by
+fn filter(project: &Option<String>, team: &Option<Team>) -> Value {
+    let in_team =
+        |team: &Team| json!({ "accessibleTeams": { "some": { "id": { "eq": team.id } } } });
+    match (project, team) {
+        (None, None) => Value::Null,
+        (Some(project), None) => json!({ "project": { "name": { "eqIgnoreCase": project } } }),
+        (Some(project), Some(team)) => {
+            let mut project_filter = in_team(team);
+            project_filter["name"] = json!({ "eqIgnoreCase": project });
+            json!({ "project": project_filter })
+        }
+        (None, Some(team)) => json!({
+            "or": [
+                { "team": { "id": { "eq": team.id } } },
+                { "project": in_team(team) }
+            ]
+        }),
+    }
+}
+
+/// A document with its content rendered for the terminal. Takes an id, slug id, or url.
+pub fn view(config: &Config, token: &str, id: &str) -> Result<String, String> {
+    let response = request::Gql::new(config, token, DOCUMENT_DOC)
+        .put_string("id", slug_id(id))
+        .run()?;
+    let document = document_response(response)?;
+
+    let date_format = date::pattern(&config.date_format);
+    let date = |value: &Option<String>| value.as_ref().map(|v| date::display(v, &date_format));
+    let metadata = [
+        ("Project", document.project.as_ref().map(|p| p.name.clone())),
+        ("Team", document.team.as_ref().map(|t| t.name.clone())),
+        (
+            "Creator",
+            document.creator.as_ref().map(|c| c.display_name.clone()),
+        ),
+        ("Created", date(&document.created_at)),
+        ("Updated", date(&document.updated_at)),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| value.map(|v| format!("{name}: {v}\n")))
+    .collect::<String>();
+    let content = match document.content.as_deref().map(str::trim) {
+        Some(content) if !content.is_empty() => render(content),
+        _ => String::from("<No Content>"),
+    };
+
+    Ok(format!(
+        "{}\n{metadata}{}\n\n{content}",
+        color::green_string(&document.title),
+        document.url
+    ))
+}
+
+/// The slug id at the end of a document url, i.e. https://linear.app/wayne/document/lair-specs-8f2c1a9b3d4e
+/// becomes 8f2c1a9b3d4e. Anything that isn't a url is passed through.
+fn slug_id(id: &str) -> String {
+    match id.split_once("/document/") {
+        Some((_, path)) => {
+            let slug = path.split(['/', '?', '#']).next().unwrap_or_default();
+            slug.rsplit('-').next().unwrap_or(slug).to_string()
+        }
+        None => id.to_string(),
+    }
+}
+
+/// Markdown for the terminal: headings are colored, list markers become bullets,
+/// code blocks are indented, and links show their url after the text.
+fn render(markdown: &str) -> String {
+    let link = Regex::new(r"\[([^\]]*)\]\(([^)]*)\)").unwrap();
+    let emphasis = Regex::new(r"\*\*([^*]+)\*\*|__([^_]+)__").unwrap();
+    let code = Regex::new(r"`([^`]+)`").unwrap();
+    let heading = Regex::new(r"^#{1,6}\s+(.*)$").unwrap();
+    let list_item = Regex::new(r"^(\s*)(?:[-*+]|(\d+\.))\s+(\[[ xX]\]\s+)?(.*)$").unwrap();
+
+    let inline = |text: &str| {
+        let text = link.replace_all(text, "$1 ($2)");
+        let text = emphasis.replace_all(&text, "$1$2");
+        code.replace_all(&text, |captures: &regex::Captures| {
+            color::cyan_string(&captures[1])
+        })
+        .to_string()
+    };
+
+    let mut in_code = false;
+    markdown
+        .lines()
+        .filter_map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code = !in_code;
+                return None;
+            }
+            if in_code {
+                return Some(format!("    {}", color::blue_string(line)));
+            }
+            let trimmed = line.trim();
+            if trimmed == "---" || trimmed == "***" {
+                return Some("─".repeat(40));
+            }
+            if let Some(captures) = heading.captures(line) {
+                return Some(color::green_string(&inline(&captures[1])));
+            }
+            if let Some(quote) = trimmed.strip_prefix('>') {
+                return Some(format!("│ {}", inline(quote.trim_start())));
+            }
+            if let Some(captures) = list_item.captures(line) {
+                let indent = &captures[1];
+                let marker = match (captures.get(2), captures.get(3)) {
+                    (_, Some(checkbox)) if checkbox.as_str().starts_with("[ ]") => "☐",
+                    (_, Some(_)) => "☑",
+                    (Some(number), None) => number.as_str(),
+                    (None, None) => "•",
+                };
+                return Some(format!("{indent}{marker} {}", inline(&captures[4])));
+            }
+            Some(inline(line))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn documents_response(response: String) -> Result<Vec<Document>, String> {
+    let data: Result<DocumentsResponse, _> = schema::parse(&response, "documents");
+
+    match data {
+        Ok(DocumentsResponse {
+            data:
+                Some(DocumentsData {
+                    documents: Documents { nodes },
+                }),
+        }) => Ok(nodes),
+        err => Err(format!(
+            "Could not parse response for documents:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+fn document_response(response: String) -> Result<Document, String> {
+    let data: Result<DocumentResponse, _> = schema::parse(&response, "document");
+
+    match data {
+        Ok(DocumentResponse {
+            data: Some(DocumentData { document }),
+        }) => Ok(document),
+        err => Err(format!(
+            "Could not parse response for document:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_list() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"filter\":\\{\"or\":\\[\\{\"team\":\\{\"id\":\\{\"eq\":\"123456\"\\}\\}\\},\\{\"project\":\\{\"accessibleTeams\":\\{\"some\":\\{\"id\":\\{\"eq\":\"123456\"\\}\\}\\}\\}\\}\\]\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::documents())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            list(&config, "1234", &None, &Some(test::fixtures::team())),
+            Ok(String::from(
+                "\nDocuments\n\n- 8f2c1a9b3d4e | Lair Specs | Lair | Updated 2025-10-01 14:30\n- 1b2c3d       | Gadget Ideas | Thundercats | Updated 2025-09-12 08:00"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn filter_should_combine_project_and_team() {
+        let team = test::fixtures::team();
+        assert_eq!(filter(&None, &None), Value::Null);
+        assert_eq!(
+            filter(&Some(String::from("Lair")), &Some(team)),
+            json!({ "project": {
+                "name": { "eqIgnoreCase": "Lair" },
+                "accessibleTeams": { "some": { "id": { "eq": "123456" } } }
+            } })
+        );
+    }
+
+    #[test]
+    fn test_view() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"id\":\"8f2c1a9b3d4e\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::document())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            view(
+                &config,
+                "1234",
+                "https://linear.app/wayne/document/lair-specs-8f2c1a9b3d4e"
+            ),
+            Ok(String::from(
+                "Lair Specs\nProject: Lair\nCreator: Bruce\nCreated: 2025-09-01 09:00\nUpdated: 2025-10-01 14:30\nhttps://linear.app/wayne/document/lair-specs-8f2c1a9b3d4e\n\nRequirements\n• Hidden entrance\n☐ Sonar\n\nSee the blueprints (https://wayne.com/lair)"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn render_should_format_markdown() {
+        let markdown = "# Title\n\nSome **bold** and `code` text\n\n1. First\n  - [x] Done\n> Quoted\n\n```\nlet x = 1;\n```\n---";
+        assert_eq!(
+            render(markdown),
+            format!(
+                "Title\n\nSome bold and code text\n\n1. First\n  ☑ Done\n│ Quoted\n\n    let x = 1;\n{}",
+                "─".repeat(40)
+            )
+        );
+    }
+
+    #[test]
+    fn slug_id_should_accept_urls() {
+        assert_eq!(slug_id("8f2c1a9b3d4e"), "8f2c1a9b3d4e");
+        assert_eq!(
+            slug_id("https://linear.app/wayne/document/lair-specs-8f2c1a9b3d4e#heading"),
+            "8f2c1a9b3d4e"
+        );
+    }
+}
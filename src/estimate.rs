@@ -0,0 +1,191 @@
+use std::fmt::Display;
+
+use crate::team::Team;
+
+/// A point on a team's estimation scale, a value of None is no estimate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub value: Option<u8>,
+    pub label: Option<String>,
+}
+
+impl Display for Estimate {
+    /// This is for rendering in a select
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.value, &self.label) {
+            (None, _) => write!(f, "None"),
+            (Some(value), Some(label)) => write!(f, "{label} ({value} points)"),
+            (Some(value), None) => write!(f, "{value} points"),
+        }
+    }
+}
+
+/// The estimates a team can choose from, empty when the team does not use estimates
+pub fn scale(team: &Team) -> Vec<Estimate> {
+    let extended = team.issue_estimation_extended.unwrap_or_default();
+    let allow_zero = team.issue_estimation_allow_zero.unwrap_or_default();
+
+    let (values, labels): (Vec<u8>, Vec<&str>) =
+        match team.issue_estimation_type.as_deref().unwrap_or("notUsed") {
+            "exponential" => (vec![1, 2, 4, 8, 16, 32, 64], vec![]),
+            "fibonacci" => (vec![1, 2, 3, 5, 8, 13, 21], vec![]),
+            "linear" => (vec![1, 2, 3, 4, 5, 6, 7], vec![]),
+            "tShirt" => (
+                vec![1, 2, 3, 5, 8, 13, 21],
+                vec!["XS", "S", "M", "L", "XL", "XXL", "XXXL"],
+            ),
+            _ => return Vec::new(),
+        };
+    let length = if extended { 7 } else { 5 };
+
+    let mut scale = values
+        .into_iter()
+        .take(length)
+        .enumerate()
+        .map(|(index, value)| Estimate {
+            value: Some(value),
+            label: labels.get(index).map(|l| l.to_string()),
+        })
+        .collect::<Vec<Estimate>>();
+
+    if allow_zero {
+        scale.insert(
+            0,
+            Estimate {
+                value: Some(0),
+                label: None,
+            },
+        );
+    }
+    scale
+}
+
+/// Options for selecting an estimate, with no estimate first. Empty when the team does not use estimates
+pub fn all_estimates(team: &Team) -> Vec<Estimate> {
+    let mut estimates = scale(team);
+    if !estimates.is_empty() {
+        estimates.insert(
+            0,
+            Estimate {
+                value: None,
+                label: None,
+            },
+        );
+    }
+    estimates
+}
+
+/// Points for an estimate on the team's scale, given as points or as a t-shirt size such as M
+pub fn parse(team: &Team, input: &str) -> Result<u8, String> {
+    let scale = scale(team);
+    if scale.is_empty() {
+        return Err(format!("Team {} does not use estimates", team.name));
+    }
+    let input = input.trim();
+    let number = input.parse::<u8>().ok();
+    scale
+        .iter()
+        .find(|estimate| {
+            number.is_some() && estimate.value == number
+                || estimate
+                    .label
+                    .as_ref()
+                    .is_some_and(|label| label.eq_ignore_ascii_case(input))
+        })
+        .and_then(|estimate| estimate.value)
+        .ok_or_else(|| {
+            let choices = scale
+                .iter()
+                .map(|estimate| match (&estimate.label, estimate.value) {
+                    (Some(label), _) => label.clone(),
+                    (None, value) => value.unwrap_or_default().to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!(
+                "Estimate {input} is not on the {} scale of team {}, choose from {choices}",
+                team.issue_estimation_type.as_deref().unwrap_or_default(),
+                team.name
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn team(estimation_type: &str, extended: bool, allow_zero: bool) -> Team {
+        Team {
+            issue_estimation_type: Some(estimation_type.to_string()),
+            issue_estimation_extended: Some(extended),
+            issue_estimation_allow_zero: Some(allow_zero),
+            ..test::fixtures::team()
+        }
+    }
+
+    fn values(scale: Vec<Estimate>) -> Vec<u8> {
+        scale.into_iter().filter_map(|e| e.value).collect()
+    }
+
+    #[test]
+    fn scale_should_follow_team_settings() {
+        assert_eq!(scale(&test::fixtures::team()), Vec::new());
+        assert_eq!(scale(&team("notUsed", false, false)), Vec::new());
+        assert_eq!(
+            values(scale(&team("fibonacci", false, false))),
+            vec![1, 2, 3, 5, 8]
+        );
+        assert_eq!(
+            values(scale(&team("exponential", true, true))),
+            vec![0, 1, 2, 4, 8, 16, 32, 64]
+        );
+        assert_eq!(
+            values(scale(&team("linear", false, true))),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn all_estimates_should_start_with_none() {
+        assert_eq!(all_estimates(&test::fixtures::team()), Vec::new());
+
+        let estimates = all_estimates(&team("linear", false, false));
+        assert_eq!(estimates.first().unwrap().to_string(), "None");
+        assert_eq!(estimates.len(), 6);
+    }
+
+    #[test]
+    fn parse_should_follow_the_team_scale() {
+        let t_shirt = team("tShirt", false, false);
+        assert_eq!(parse(&t_shirt, "m"), Ok(3));
+        assert_eq!(parse(&t_shirt, "5"), Ok(5));
+        assert_eq!(
+            parse(&t_shirt, "XXL"),
+            Err(format!(
+                "Estimate XXL is not on the tShirt scale of team {}, choose from XS, S, M, L, XL",
+                t_shirt.name
+            ))
+        );
+        assert_eq!(parse(&team("fibonacci", false, true), "0"), Ok(0));
+        assert!(parse(&team("fibonacci", false, false), "4").is_err());
+        assert!(parse(&team("notUsed", false, false), "1").is_err());
+    }
+
+    #[test]
+    fn scale_should_label_t_shirt_sizes() {
+        let scale = scale(&team("tShirt", false, false));
+        let labels = scale.iter().map(|e| e.to_string()).collect::<Vec<String>>();
+        assert_eq!(
+            labels,
+            vec![
+                "XS (1 points)",
+                "S (2 points)",
+                "M (3 points)",
+                "L (5 points)",
+                "XL (8 points)"
+            ]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+/// Fill in the {placeholders} of a --format string, i.e. "{identifier}\t{state}\t{title}".
+/// value returns None for names that aren't placeholders. Use {{ and }} for literal braces,
+/// and \t, \n, and \\ for tabs, newlines, and backslashes, as shells don't expand them in quotes.
+pub fn render(format: &str, value: impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut output = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("Unclosed placeholder {{{name} in --format")),
+                    }
+                }
+                match value(name.trim()) {
+                    Some(value) => output.push_str(&value),
+                    None => return Err(format!("Unknown placeholder {{{name}}} in --format")),
+                }
+            }
+            '}' => {
+                return Err(String::from(
+                    "Unmatched } in --format, use }} for a literal brace",
+                ))
+            }
+            '\\' => match chars.next() {
+                Some('t') => output.push('\t'),
+                Some('n') => output.push('\n'),
+                Some('\\') => output.push('\\'),
+                Some(other) => {
+                    output.push('\\');
+                    output.push(other);
+                }
+                None => output.push('\\'),
+            },
+            c => output.push(c),
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn value(name: &str) -> Option<String> {
+        match name {
+            "identifier" => Some(String::from("ENG-1")),
+            "title" => Some(String::from("Fix the Batmobile")),
+            "estimate" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn render_should_fill_in_placeholders() {
+        assert_eq!(
+            render("{identifier}\\t{ title }\\n", value),
+            Ok(String::from("ENG-1\tFix the Batmobile\n"))
+        );
+        assert_eq!(
+            render("{{{identifier}}} [{estimate}] C:\\\\", value),
+            Ok(String::from("{ENG-1} [] C:\\"))
+        );
+    }
+
+    #[test]
+    fn render_should_reject_bad_placeholders() {
+        assert_eq!(
+            render("{nope}", value),
+            Err(String::from("Unknown placeholder {nope} in --format"))
+        );
+        assert_eq!(
+            render("{title", value),
+            Err(String::from("Unclosed placeholder {title in --format"))
+        );
+        assert_eq!(
+            render("title}", value),
+            Err(String::from(
+                "Unmatched } in --format, use }} for a literal brace"
+            ))
+        );
+    }
+}
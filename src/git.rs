@@ -1,3 +1,8 @@
+use std::path::PathBuf;
+
+/// Remote that existing branches are looked for on
+const REMOTE: &str = "origin";
+
 pub fn get_branch() -> Result<String, String> {
     let output = std::process::Command::new("git")
         .arg("branch")
@@ -13,3 +18,140 @@ pub fn get_branch() -> Result<String, String> {
         Err(String::from_utf8(output.stderr).unwrap())
     }
 }
+
+/// Messages of the most recent commits, newest first
+pub fn recent_commit_messages() -> Result<String, String> {
+    // Tests shouldn't depend on the history of the repository they run in
+    if cfg!(test) {
+        return Ok(String::new());
+    }
+    git(&["log", "-n", "20", "--format=%B"])
+}
+
+/// Messages of the commits after since up to and including until, i.e. v1.2.0 and HEAD
+pub fn commit_messages_between(since: &str, until: &str) -> Result<String, String> {
+    git(&["log", "--format=%B", &format!("{since}..{until}")])
+}
+
+/// Where the branch for an issue comes from
+#[derive(Debug, PartialEq)]
+enum Source {
+    Local,
+    Remote,
+    New,
+}
+
+/// Switch to a branch, using the local branch or the one on origin when it exists and creating it otherwise
+pub fn checkout_branch(name: &str) -> Result<String, String> {
+    let remote = format!("{REMOTE}/{name}");
+    let (args, message) = match source(name)? {
+        Source::Local => (vec!["checkout", name], format!("Switched to {name}")),
+        Source::Remote => (
+            vec!["checkout", "-b", name, "--track", &remote],
+            format!("Checked out {name} from {REMOTE}"),
+        ),
+        Source::New => (vec!["checkout", "-b", name], format!("Created {name}")),
+    };
+    git(&args)?;
+    Ok(message)
+}
+
+/// Check out a branch in its own worktree, next to the repository unless a path is given.
+/// A branch that already has a worktree is left where it is.
+pub fn checkout_worktree(name: &str, path: &Option<String>) -> Result<String, String> {
+    if let Some(existing) = worktree_for_branch(&git(&["worktree", "list", "--porcelain"])?, name) {
+        return Ok(format!("{name} is already checked out at {existing}"));
+    }
+    let path = match path {
+        Some(path) => path.clone(),
+        None => default_worktree_path(name)?,
+    };
+    let remote = format!("{REMOTE}/{name}");
+    let args = match source(name)? {
+        Source::Local => vec!["worktree", "add", &path, name],
+        Source::Remote => vec!["worktree", "add", "--track", "-b", name, &path, &remote],
+        Source::New => vec!["worktree", "add", "-b", name, &path],
+    };
+    git(&args)?;
+    Ok(format!("Checked out {name} at {path}"))
+}
+
+fn source(name: &str) -> Result<Source, String> {
+    let local = std::process::Command::new("git")
+        .arg("show-ref")
+        .arg("--verify")
+        .arg("--quiet")
+        .arg(format!("refs/heads/{name}"))
+        .status()
+        .map_err(|e| e.to_string())?
+        .success();
+    if local {
+        return Ok(Source::Local);
+    }
+    // Fetching also fails without a remote, in which case the branch is new
+    let fetched = std::process::Command::new("git")
+        .arg("fetch")
+        .arg("--quiet")
+        .arg(REMOTE)
+        .arg(format!("{name}:refs/remotes/{REMOTE}/{name}"))
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_err(|e| e.to_string())?
+        .success();
+    match fetched {
+        true => Ok(Source::Remote),
+        false => Ok(Source::New),
+    }
+}
+
+/// Beside the repository, named after it and the branch, i.e. ../lnr-eng-12-fix-login
+fn default_worktree_path(name: &str) -> Result<String, String> {
+    let top_level = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?.trim());
+    let repository = top_level
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let parent = top_level.parent().unwrap_or(&top_level);
+    let path = parent.join(format!("{repository}-{}", name.replace('/', "-")));
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// The path of the worktree a branch is checked out in, from git worktree list --porcelain
+fn worktree_for_branch(porcelain: &str, name: &str) -> Option<String> {
+    let branch = format!("branch refs/heads/{name}");
+    porcelain.split("\n\n").find_map(|worktree| {
+        let mut lines = worktree.lines();
+        let path = lines.next()?.strip_prefix("worktree ")?;
+        lines.any(|line| line == branch).then(|| path.to_string())
+    })
+}
+
+/// Run git, returning stdout
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|e| e.to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn worktree_for_branch_should_find_the_path() {
+        let porcelain = "worktree /code/lnr\nHEAD 1a2b\nbranch refs/heads/main\n\nworktree /code/lnr-eng-12\nHEAD 3c4d\nbranch refs/heads/eng-12\n";
+        assert_eq!(
+            worktree_for_branch(porcelain, "eng-12"),
+            Some(String::from("/code/lnr-eng-12"))
+        );
+        assert_eq!(worktree_for_branch(porcelain, "eng-1"), None);
+    }
+}
@@ -0,0 +1,244 @@
+use serde::Deserialize;
+
+use crate::{config::Config, request};
+
+/// Most issues GitHub returns in one page
+const PAGE_SIZE: usize = 100;
+
+/// An open pull request, as reported by the GitHub CLI
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+}
+
+/// An issue from the GitHub REST API
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub labels: Vec<Label>,
+    pub assignees: Vec<Account>,
+    /// Only set for pull requests, which the issues endpoint also returns
+    pub pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct Account {
+    pub login: String,
+    /// Only set when the user has made it public
+    pub email: Option<String>,
+}
+
+/// Token for the GitHub API from GITHUB_TOKEN or GH_TOKEN, falling back to the GitHub CLI.
+/// None when there isn't one, which is fine for public repositories.
+pub fn token() -> Option<String> {
+    let from_env = ["GITHUB_TOKEN", "GH_TOKEN"]
+        .into_iter()
+        .find_map(|key| std::env::var(key).ok().filter(|t| !t.is_empty()));
+    from_env.or_else(|| {
+        let output = std::process::Command::new("gh")
+            .arg("auth")
+            .arg("token")
+            .output()
+            .ok()?;
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !token.is_empty()).then_some(token)
+    })
+}
+
+/// Every open issue in a repository, i.e. owner/name, oldest first and without pull requests
+pub fn open_issues(
+    config: &Config,
+    repo: &str,
+    token: &Option<String>,
+) -> Result<Vec<Issue>, String> {
+    if repo.split('/').filter(|part| !part.is_empty()).count() != 2 {
+        return Err(format!(
+            "Repository {repo} should be in the form owner/name"
+        ));
+    }
+    let mut issues = Vec::new();
+    for page in 1.. {
+        let path = format!(
+            "/repos/{repo}/issues?state=open&direction=asc&per_page={PAGE_SIZE}&page={page}"
+        );
+        let json = request::github_get(config, &path, token)?;
+        let page: Vec<Issue> = serde_json::from_str(&json)
+            .map_err(|e| format!("Could not parse issues from GitHub: {e}\n{json}"))?;
+        let last_page = page.len() < PAGE_SIZE;
+        issues.extend(page.into_iter().filter(|i| i.pull_request.is_none()));
+        if last_page {
+            break;
+        }
+    }
+    Ok(issues)
+}
+
+/// Public email of a GitHub user, None when they haven't made one public
+pub fn email(
+    config: &Config,
+    login: &str,
+    token: &Option<String>,
+) -> Result<Option<String>, String> {
+    let json = request::github_get(config, &format!("/users/{login}"), token)?;
+    let account: Account = serde_json::from_str(&json)
+        .map_err(|e| format!("Could not parse user {login} from GitHub: {e}\n{json}"))?;
+    Ok(account.email.filter(|email| !email.is_empty()))
+}
+
+/// Find the pull request for a branch using gh
+pub fn pull_request(branch: &str) -> Result<PullRequest, String> {
+    let output = std::process::Command::new("gh")
+        .arg("pr")
+        .arg("view")
+        .arg(branch)
+        .arg("--json")
+        .arg("number,title,url")
+        .output()
+        .map_err(|e| format!("Could not run gh, is the GitHub CLI installed? {e}"))?;
+
+    if output.status.success() {
+        let json = String::from_utf8(output.stdout).map_err(|e| e.to_string())?;
+        pull_request_response(&json)
+    } else {
+        Err(format!(
+            "Could not find a pull request for {branch}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Open a pull request for the current branch using gh, returning its url
+pub fn create_pull_request(
+    title: &str,
+    body: &str,
+    base: &Option<String>,
+    draft: bool,
+) -> Result<String, String> {
+    let mut command = std::process::Command::new("gh");
+    command
+        .arg("pr")
+        .arg("create")
+        .arg("--title")
+        .arg(title)
+        .arg("--body")
+        .arg(body);
+    if let Some(base) = base {
+        command.arg("--base").arg(base);
+    }
+    if draft {
+        command.arg("--draft");
+    }
+    let output = command
+        .output()
+        .map_err(|e| format!("Could not run gh, is the GitHub CLI installed? {e}"))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(format!(
+            "Could not create pull request: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Pull request title for an issue, i.e. ENG-123: Fix login
+pub fn title(identifier: &str, issue_title: &str) -> String {
+    format!("{identifier}: {issue_title}")
+}
+
+/// Pull request body for an issue, ending with the magic words that let Linear close it on merge
+pub fn body(identifier: &str, description: &Option<String>) -> String {
+    match description.as_deref().map(str::trim) {
+        Some(description) if !description.is_empty() => {
+            format!("{description}\n\nCloses {identifier}")
+        }
+        _ => format!("Closes {identifier}"),
+    }
+}
+
+fn pull_request_response(json: &str) -> Result<PullRequest, String> {
+    serde_json::from_str(json).map_err(|e| format!("Could not parse gh output: {e}\n{json}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_open_issues() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/repos/wayne/batcave/issues")
+            .match_query(mockito::Matcher::UrlEncoded(
+                String::from("page"),
+                String::from("1"),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::github_issues())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let issues = open_issues(&config, "wayne/batcave", &None).unwrap();
+        let numbers = issues.iter().map(|i| i.number).collect::<Vec<u64>>();
+        assert_eq!(numbers, vec![1, 3]);
+        assert_eq!(issues[0].labels[0].name, "Cats");
+        assert_eq!(issues[0].assignees[0].login, "bruce");
+        mock.assert();
+
+        assert_eq!(
+            open_issues(&config, "batcave", &None),
+            Err(String::from(
+                "Repository batcave should be in the form owner/name"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_title() {
+        assert_eq!(title("ENG-123", "Fix login"), "ENG-123: Fix login");
+    }
+
+    #[test]
+    fn test_body() {
+        assert_eq!(
+            body("ENG-123", &Some(String::from("Users can't log in\n"))),
+            "Users can't log in\n\nCloses ENG-123"
+        );
+        assert_eq!(body("ENG-123", &Some(String::new())), "Closes ENG-123");
+        assert_eq!(body("ENG-123", &None), "Closes ENG-123");
+    }
+
+    #[test]
+    fn test_pull_request_response() {
+        let json =
+            r#"{"number":42,"title":"Fix login","url":"https://github.com/org/repo/pull/42"}"#;
+        assert_eq!(
+            pull_request_response(json),
+            Ok(PullRequest {
+                number: 42,
+                title: String::from("Fix login"),
+                url: String::from("https://github.com/org/repo/pull/42"),
+            })
+        );
+        assert!(pull_request_response("no pull requests found")
+            .unwrap_err()
+            .starts_with("Could not parse gh output"));
+    }
+}
@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color,
+    config::Config,
+    date, priority,
+    request::{self, PageInfo},
+    schema,
+};
+
+const ISSUE_HISTORY_DOC: &str = "query (
+                    $id: String!
+                    $after: String
+                ) {
+                issue(
+                    id: $id
+                )   {
+                        history (
+                            first: 100
+                            after: $after
+                        ) {
+                            nodes {
+                                createdAt
+                                actor {
+                                    displayName
+                                }
+                                fromState {
+                                    name
+                                }
+                                toState {
+                                    name
+                                }
+                                fromAssignee {
+                                    displayName
+                                }
+                                toAssignee {
+                                    displayName
+                                }
+                                fromPriority
+                                toPriority
+                                fromTitle
+                                toTitle
+                                updatedDescription
+                            }
+                            pageInfo {
+                                hasNextPage
+                                endCursor
+                            }
+                        }
+                    }
+                }
+                ";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct HistoryResponse {
+    data: Option<HistoryData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct HistoryData {
+    issue: HistoryIssue,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct HistoryIssue {
+    history: HistoryConnection,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct HistoryConnection {
+    nodes: Vec<Entry>,
+    #[serde(rename = "pageInfo")]
+    page_info: Option<PageInfo>,
+}
+
+/// A single change to an issue, only the fields that changed are set
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Entry {
+    created_at: String,
+    /// None for changes made by Linear itself, such as auto-closing
+    actor: Option<Named>,
+    from_state: Option<Named>,
+    to_state: Option<Named>,
+    from_assignee: Option<Named>,
+    to_assignee: Option<Named>,
+    from_priority: Option<f32>,
+    to_priority: Option<f32>,
+    from_title: Option<String>,
+    to_title: Option<String>,
+    updated_description: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(default)]
+struct Named {
+    name: Option<String>,
+    #[serde(rename = "displayName")]
+    display_name: Option<String>,
+}
+
+impl Named {
+    fn label(named: &Option<Named>) -> Option<String> {
+        named
+            .as_ref()
+            .and_then(|n| n.display_name.clone().or_else(|| n.name.clone()))
+    }
+}
+
+impl Entry {
+    /// What changed, i.e. "moved from Todo to In Progress", None for changes that aren't shown
+    fn describe(&self) -> Option<String> {
+        let mut changes = Vec::new();
+        if let (Some(from), Some(to)) =
+            (Named::label(&self.from_state), Named::label(&self.to_state))
+        {
+            changes.push(format!("moved from {from} to {to}"));
+        }
+        match (
+            Named::label(&self.from_assignee),
+            Named::label(&self.to_assignee),
+        ) {
+            (None, Some(to)) => changes.push(format!("assigned to {to}")),
+            (Some(from), None) => changes.push(format!("unassigned {from}")),
+            (Some(from), Some(to)) => changes.push(format!("reassigned from {from} to {to}")),
+            (None, None) => (),
+        }
+        if let (Some(from), Some(to)) = (self.from_priority, self.to_priority) {
+            let from = priority::from_int(from as u8);
+            let to = priority::from_int(to as u8);
+            changes.push(format!("changed priority from {from} to {to}"));
+        }
+        if let (Some(from), Some(_)) = (&self.from_title, &self.to_title) {
+            changes.push(format!("renamed from \"{from}\""));
+        }
+        if self.updated_description == Some(true) {
+            changes.push(String::from("edited the description"));
+        }
+
+        match changes.is_empty() {
+            true => None,
+            false => Some(changes.join(", ")),
+        }
+    }
+}
+
+/// Every change to an issue, oldest first
+pub fn get(config: &Config, token: &str, issue_id: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    let mut after = None;
+    loop {
+        let response = request::Gql::new(config, token, ISSUE_HISTORY_DOC)
+            .put_string("id", issue_id.to_string())
+            .maybe_put_string("after", after)
+            .run()?;
+        let HistoryConnection { nodes, page_info } = history_response(response)?;
+        entries.extend(nodes);
+
+        after = page_info.and_then(|p| p.next_cursor());
+        if after.is_none() {
+            entries.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+            return Ok(entries);
+        }
+    }
+}
+
+/// One line per change, i.e. "2024-05-01 00:00 Bruce moved from Todo to In Progress"
+pub fn describe(entries: &[Entry], date_format: &str) -> String {
+    let lines = entries
+        .iter()
+        .filter_map(|entry| {
+            let change = entry.describe()?;
+            let when = date::display(&entry.created_at, date_format);
+            let actor = Named::label(&entry.actor).unwrap_or_else(|| String::from("Linear"));
+            Some(format!("{} {actor} {change}", color::blue_string(&when)))
+        })
+        .collect::<Vec<String>>();
+
+    match lines.is_empty() {
+        true => String::from("<No History>"),
+        false => lines.join("\n"),
+    }
+}
+
+fn history_response(response: String) -> Result<HistoryConnection, String> {
+    let data: Result<HistoryResponse, _> = schema::parse(&response, "issue history");
+
+    match data {
+        Ok(HistoryResponse {
+            data: Some(HistoryData {
+                issue: HistoryIssue { history },
+            }),
+        }) => Ok(history),
+        err => Err(format!(
+            "Could not parse response for issue history:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_get() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("history")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_history())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let entries = get(&config, "1234", "BE-3354").unwrap();
+        assert_eq!(
+            describe(&entries, "%Y-%m-%d"),
+            "2024-05-01 00:00 Bruce assigned to Bruce, changed priority from None to High\n\
+             2024-05-02 00:00 Alfred moved from In Progress to Todo\n\
+             2024-05-03 00:00 Linear moved from Todo to Canceled"
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn describe_should_skip_unshown_changes() {
+        assert_eq!(describe(&[Entry::default()], "%Y-%m-%d"), "<No History>");
+    }
+}
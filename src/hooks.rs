@@ -0,0 +1,149 @@
+use crate::{
+    config::Config,
+    issue::{self, Issue},
+    processor,
+    team::State,
+    verbosity,
+};
+
+/// Points in the life of an issue that can run a shell command
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    Create,
+    Start,
+    Close,
+}
+
+impl Event {
+    fn key(&self) -> &str {
+        match self {
+            Event::Create => "on_issue_create",
+            Event::Start => "on_issue_start",
+            Event::Close => "on_issue_close",
+        }
+    }
+
+    fn command<'a>(&self, config: &'a Config) -> Option<&'a String> {
+        match self {
+            Event::Create => config.on_issue_create.as_ref(),
+            Event::Start => config.on_issue_start.as_ref(),
+            Event::Close => config.on_issue_close.as_ref(),
+        }
+    }
+
+    /// The event for moving an issue to a state, if there is one
+    pub fn for_state(state: &State) -> Option<Event> {
+        match state.state_type.as_str() {
+            "started" => Some(Event::Start),
+            "completed" | "canceled" => Some(Event::Close),
+            _ => None,
+        }
+    }
+}
+
+/// Run the hook for the event with the issue as JSON on stdin, and return the output of the command
+/// that triggered it. The change has already been made, so a failing hook is reported after the output.
+pub fn run(config: &Config, event: Event, issue: &Issue, output: String) -> Result<String, String> {
+    let Some(command) = event.command(config) else {
+        return Ok(output);
+    };
+    let key = event.key();
+    verbosity::progress(&format!("Running {key} hook"));
+    let json = issue.to_json().to_string();
+    match execute(command, &json) {
+        Ok(()) => Ok(output),
+        Err(e) => Err(format!("{output}\n{key} hook {command} failed:\n{e}")),
+    }
+}
+
+/// Run the hook for an issue that moved to a state, fetching the issue only when there is a hook to run
+pub fn state_changed(
+    config: &Config,
+    token: &str,
+    id: String,
+    state: &Option<State>,
+    output: String,
+) -> Result<String, String> {
+    let event = state.as_ref().and_then(Event::for_state);
+    match event {
+        Some(event) if event.command(config).is_some() => {
+            let issue = issue::get(config, token, id)?;
+            run(config, event, &issue, output)
+        }
+        _ => Ok(output),
+    }
+}
+
+/// Run a shell command with input on stdin. Its stdout is shown with --verbose, and its stderr when it fails.
+fn execute(command: &str, input: &str) -> Result<(), String> {
+    let output = processor::pipe(command, input)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.trim().is_empty() {
+        verbosity::detail(stdout.trim_end());
+    }
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn issue() -> Issue {
+        serde_json::from_value(json!({"id": "issue-1", "identifier": "BE-3354", "title": "Test"}))
+            .unwrap()
+    }
+
+    #[test]
+    fn run_should_pass_the_issue_on_stdin() {
+        let path = std::env::temp_dir().join(format!("lnr-hook-{}.json", std::process::id()));
+        let config = Config {
+            on_issue_create: Some(format!("cat > {}", path.display())),
+            ..test::fixtures::config()
+        };
+
+        let result = run(&config, Event::Create, &issue(), String::from("Created"));
+        assert_eq!(result, Ok(String::from("Created")));
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["identifier"], "BE-3354");
+        assert_eq!(json["title"], "Test");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn run_should_report_a_failing_hook_after_the_output() {
+        let config = Config {
+            on_issue_close: Some(String::from("echo nope >&2; exit 1")),
+            ..test::fixtures::config()
+        };
+
+        assert_eq!(
+            run(&config, Event::Close, &issue(), String::from("Updated")),
+            Err(String::from(
+                "Updated\non_issue_close hook echo nope >&2; exit 1 failed:\nnope\n"
+            ))
+        );
+        assert_eq!(
+            run(&config, Event::Start, &issue(), String::from("Updated")),
+            Ok(String::from("Updated"))
+        );
+    }
+
+    #[test]
+    fn for_state_should_match_state_types() {
+        let state = |state_type: &str| State {
+            state_type: state_type.to_string(),
+            ..State::default()
+        };
+        assert_eq!(Event::for_state(&state("started")), Some(Event::Start));
+        assert_eq!(Event::for_state(&state("canceled")), Some(Event::Close));
+        assert_eq!(Event::for_state(&state("backlog")), None);
+    }
+}
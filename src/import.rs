@@ -0,0 +1,451 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::{
+    config::Config,
+    github, hooks, issue, label,
+    priority::Priority,
+    request::{self, PageInfo},
+    team::{self, Project, State, Team},
+    user::User,
+};
+
+const IMPORTED_DOC: &str = "query (
+                    $teamId: ID!
+                    $source: String!
+                    $after: String
+                ) {
+                issues(
+                    first: 100
+                    after: $after
+                    includeArchived: true
+                    filter: {team: {id: {eq: $teamId}}, description: {contains: $source}}
+                ) {
+                    nodes {
+                        identifier
+                        description
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }";
+
+/// Prefix of the line linking an imported issue back to GitHub
+const IMPORTED_FROM: &str = "Imported from ";
+
+#[derive(Deserialize, Debug)]
+struct ImportedResponse {
+    data: ImportedData,
+}
+
+#[derive(Deserialize, Debug)]
+struct ImportedData {
+    issues: ImportedIssues,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ImportedIssues {
+    nodes: Vec<ImportedIssue>,
+    page_info: Option<PageInfo>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ImportedIssue {
+    identifier: String,
+    description: Option<String>,
+}
+
+/// One GitHub issue, for the mapping table
+struct Row {
+    /// Where the issue came from, i.e. #12
+    source: String,
+    title: String,
+    outcome: Outcome,
+    /// Anything that went wrong besides, i.e. an assignee that couldn't be looked up
+    notes: Vec<String>,
+}
+
+enum Outcome {
+    /// Identifier of the new Linear issue
+    Created(String),
+    /// Identifier of the Linear issue from an earlier import
+    Skipped(String),
+    /// Why the issue couldn't be created
+    Failed(String),
+}
+
+/// Create a Linear issue for every open issue in a GitHub repository, i.e. owner/name.
+/// Labels are matched by name and assignees by their public email, anything that doesn't
+/// match is left off. Issues imported before are skipped, so an import can be run again.
+/// Returns a table of GitHub numbers and Linear identifiers.
+pub fn github(
+    config: &Config,
+    token: &str,
+    github_token: &Option<String>,
+    repo: &str,
+    team: Team,
+    project: Option<Project>,
+    state: State,
+) -> Result<String, String> {
+    let issues = github::open_issues(config, repo, github_token)?;
+    if issues.is_empty() {
+        return Ok(format!("No open issues in {repo}"));
+    }
+    let imported = imported(config, token, &team, repo)?;
+    let members = team::get_members(config, token, &team)?;
+    let labels = label::for_team(config, token, &team)?;
+    let mut emails: HashMap<String, Result<Option<String>, String>> = HashMap::new();
+
+    let mut rows = Vec::new();
+    for gh_issue in issues {
+        let source = format!("#{}", gh_issue.number);
+        if let Some(identifier) = imported.get(&gh_issue.html_url) {
+            rows.push(Row {
+                source,
+                title: gh_issue.title,
+                outcome: Outcome::Skipped(identifier.clone()),
+                notes: Vec::new(),
+            });
+            continue;
+        }
+
+        let mut notes = Vec::new();
+        let assignee_id = match gh_issue.assignees.first() {
+            Some(account) => {
+                let email = emails
+                    .entry(account.login.clone())
+                    .or_insert_with(|| github::email(config, &account.login, github_token));
+                match email {
+                    Ok(email) => member_id(&members, email),
+                    Err(e) => {
+                        let login = &account.login;
+                        notes.push(format!("unassigned, could not look up {login}: {e}"));
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        let label_ids = gh_issue
+            .labels
+            .iter()
+            .filter_map(|gh_label| {
+                labels
+                    .iter()
+                    .find(|l| l.name.eq_ignore_ascii_case(&gh_label.name))
+                    .map(|l| l.id.clone())
+            })
+            .collect::<Vec<String>>();
+
+        let outcome = match issue::create(
+            config,
+            token,
+            gh_issue.title.clone(),
+            description(&gh_issue),
+            team.clone(),
+            project.clone(),
+            state.clone(),
+            assignee_id,
+            Priority::None,
+            None,
+            None,
+            None,
+            label_ids,
+            None,
+        ) {
+            Ok(created) => {
+                let hook = hooks::run(config, hooks::Event::Create, &created, String::new());
+                if let Err(e) = hook {
+                    notes.push(e.trim().replace('\n', " "));
+                }
+                Outcome::Created(created.identifier)
+            }
+            Err(e) => Outcome::Failed(e),
+        };
+        rows.push(Row {
+            source,
+            title: gh_issue.title,
+            outcome,
+            notes,
+        });
+    }
+
+    Ok(table(&rows, repo))
+}
+
+/// Identifiers of the team's issues that were imported from the repository before, by GitHub url
+fn imported(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    repo: &str,
+) -> Result<HashMap<String, String>, String> {
+    let mut imported = HashMap::new();
+    let mut after = None;
+    loop {
+        let ImportedResponse { data } = request::Gql::new(config, token, IMPORTED_DOC)
+            .put_string("teamId", team.id.clone())
+            .put_string(
+                "source",
+                format!("{IMPORTED_FROM}https://github.com/{repo}/"),
+            )
+            .maybe_put_string("after", after)
+            .execute()?;
+        for issue in data.issues.nodes {
+            let urls = issue
+                .description
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix(IMPORTED_FROM))
+                .map(String::from)
+                .collect::<Vec<String>>();
+            for url in urls {
+                imported.insert(url, issue.identifier.clone());
+            }
+        }
+
+        after = data.issues.page_info.and_then(|p| p.next_cursor());
+        if after.is_none() {
+            return Ok(imported);
+        }
+    }
+}
+
+/// The GitHub body with a link back to the original issue
+fn description(gh_issue: &github::Issue) -> String {
+    let link = format!("{IMPORTED_FROM}{}", gh_issue.html_url);
+    match gh_issue.body.as_deref().map(str::trim) {
+        Some(body) if !body.is_empty() => format!("{body}\n\n{link}"),
+        _ => link,
+    }
+}
+
+fn member_id(members: &[User], email: &Option<String>) -> Option<String> {
+    let email = email.as_ref()?;
+    members
+        .iter()
+        .find(|m| {
+            m.email
+                .as_ref()
+                .is_some_and(|e| e.eq_ignore_ascii_case(email))
+        })
+        .and_then(|m| m.id.clone())
+}
+
+fn table(rows: &[Row], source: &str) -> String {
+    let width = rows.iter().map(|r| r.source.len()).max().unwrap_or(0);
+    let count = |matches: fn(&Outcome) -> bool| rows.iter().filter(|r| matches(&r.outcome)).count();
+    let created = count(|outcome| matches!(outcome, Outcome::Created(_)));
+    let skipped = count(|outcome| matches!(outcome, Outcome::Skipped(_)));
+    let lines = rows
+        .iter()
+        .map(|row| {
+            let line = match &row.outcome {
+                Outcome::Created(identifier) => {
+                    format!("{: <width$}  {identifier}  {}", row.source, row.title)
+                }
+                Outcome::Skipped(identifier) => format!(
+                    "{: <width$}  {identifier}  {} (already imported)",
+                    row.source, row.title
+                ),
+                Outcome::Failed(e) => {
+                    format!("{: <width$}  FAILED  {}: {e}", row.source, row.title)
+                }
+            };
+            match row.notes.is_empty() {
+                true => line,
+                false => format!("{line} ({})", row.notes.join("; ")),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    let skipped = match skipped {
+        0 => String::new(),
+        skipped => format!(", skipped {skipped} imported before"),
+    };
+    format!(
+        "{lines}\n\nImported {created} of {} issues from {source}{skipped}",
+        rows.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn imported_mock(server: &mut mockito::Server, nodes: &str) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"source\":\"Imported from https://github.com/wayne/batcave/\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                "{{\"data\":{{\"issues\":{{\"nodes\":[{nodes}],\"pageInfo\":{{\"hasNextPage\":false,\"endCursor\":null}}}}}}}}"
+            ))
+            .create()
+    }
+
+    #[test]
+    fn test_github() {
+        let mut server = mockito::Server::new();
+        let imported_mock = imported_mock(&mut server, "");
+        let issues_mock = server
+            .mock("GET", "/repos/wayne/batcave/issues")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::github_issues())
+            .create();
+        let bruce_mock = server
+            .mock("GET", "/users/bruce")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{\"login\":\"bruce\",\"email\":\"BRUCE@wayne.com\"}")
+            .create();
+        let alfred_mock = server
+            .mock("GET", "/users/alfred")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{\"login\":\"alfred\",\"email\":null}")
+            .create();
+        let members_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("members")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::team_members())
+            .create();
+        let labels_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("issueLabels")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_labels())
+            .create();
+        let first_create = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"assigneeId\":\"1\".*\"labelIds\":\\[\"label-cats\"\\].*\"title\":\"Batmobile won't start\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_create())
+            .create();
+        let second_create = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"description\":\"Imported from https://github.com/wayne/batcave/issues/3\",\"labelIds\":\\[\\]",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_create())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = github(
+            &config,
+            "1234",
+            &None,
+            "wayne/batcave",
+            test::fixtures::team(),
+            None,
+            test::fixtures::state(),
+        );
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "#1  BE-3354  Batmobile won't start\n#3  BE-3354  Restock utility belt\n\nImported 2 of 2 issues from wayne/batcave"
+            ))
+        );
+        issues_mock.assert();
+        imported_mock.assert();
+        bruce_mock.assert();
+        alfred_mock.assert();
+        members_mock.assert();
+        labels_mock.assert();
+        first_create.assert();
+        second_create.assert();
+    }
+
+    #[test]
+    fn github_should_skip_imported_issues_and_keep_going_without_an_email() {
+        let mut server = mockito::Server::new();
+        let issues_mock = server
+            .mock("GET", "/repos/wayne/batcave/issues")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::github_issues())
+            .create();
+        let imported_mock = imported_mock(
+            &mut server,
+            "{\"identifier\":\"BE-7\",\"description\":\"Imported from https://github.com/wayne/batcave/issues/3\"}",
+        );
+        let bruce_mock = server
+            .mock("GET", "/users/bruce")
+            .with_status(500)
+            .with_body("Server error")
+            .create();
+        let members_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("members")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::team_members())
+            .create();
+        let labels_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("issueLabels")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_labels())
+            .create();
+        let create_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"title\":\"Batmobile won't start\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_create())
+            .expect(1)
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = github(
+            &config,
+            "1234",
+            &None,
+            "wayne/batcave",
+            test::fixtures::team(),
+            None,
+            test::fixtures::state(),
+        )
+        .unwrap();
+        let lines = result.lines().collect::<Vec<&str>>();
+        assert!(lines[0].starts_with(
+            "#1  BE-3354  Batmobile won't start (unassigned, could not look up bruce: "
+        ));
+        assert_eq!(
+            lines[1],
+            "#3  BE-7  Restock utility belt (already imported)"
+        );
+        assert_eq!(
+            lines.last(),
+            Some(&"Imported 1 of 2 issues from wayne/batcave, skipped 1 imported before")
+        );
+        issues_mock.assert();
+        imported_mock.assert();
+        bruce_mock.assert();
+        members_mock.assert();
+        labels_mock.assert();
+        create_mock.assert();
+    }
+}
@@ -0,0 +1,323 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    request::{self, PageInfo},
+};
+
+const INITIATIVES_DOC: &str = "
+        query ($after: String) {
+            initiatives (first: 100, after: $after) {
+                nodes {
+                    id
+                    name
+                    status
+                    targetDate
+                    owner {
+                        displayName
+                    }
+                    projects (first: 100) {
+                        nodes {
+                            id
+                        }
+                        pageInfo {
+                            hasNextPage
+                        }
+                    }
+                }
+                pageInfo {
+                    hasNextPage
+                    endCursor
+                }
+            }
+        }";
+
+const INITIATIVE_DOC: &str = "
+        query ($name: String!) {
+            initiatives (filter: { name: { eqIgnoreCase: $name } }) {
+                nodes {
+                    id
+                    name
+                    status
+                    targetDate
+                    owner {
+                        displayName
+                    }
+                }
+            }
+        }";
+
+const INITIATIVE_PROJECTS_DOC: &str = "
+        query ($id: String!, $after: String) {
+            initiative (id: $id) {
+                projects (first: 100, after: $after) {
+                    nodes {
+                        id
+                        name
+                        health
+                        progress
+                        targetDate
+                        status {
+                            name
+                        }
+                        lead {
+                            displayName
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }
+        }";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct InitiativesResponse {
+    data: InitiativesData,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct InitiativesData {
+    initiatives: Initiatives,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct Initiatives {
+    nodes: Vec<Initiative>,
+    page_info: Option<PageInfo>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Initiative {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    /// One of Planned, Active, or Completed
+    pub status: Option<String>,
+    pub target_date: Option<String>,
+    pub owner: Option<Person>,
+    /// Only fetched for the list of every initiative, to count them
+    projects: Option<ProjectIds>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct ProjectIds {
+    nodes: Vec<ProjectId>,
+    page_info: Option<PageInfo>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct ProjectId {
+    id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectsResponse {
+    data: ProjectsData,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectsData {
+    initiative: ProjectsInitiative,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectsInitiative {
+    projects: Projects,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct Projects {
+    nodes: Vec<InitiativeProject>,
+    page_info: Option<PageInfo>,
+}
+
+/// A project of an initiative, with how far along it is
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InitiativeProject {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    /// One of onTrack, atRisk, or offTrack, from the latest project update
+    pub health: Option<String>,
+    /// Share of completed issues, from 0 to 1
+    pub progress: Option<f32>,
+    pub target_date: Option<String>,
+    pub status: Option<ProjectStatus>,
+    pub lead: Option<Person>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+pub struct ProjectStatus {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Person {
+    pub display_name: String,
+}
+
+impl Initiative {
+    /// How many projects the initiative has, i.e. "2 projects", or "100+ projects" past the first page
+    pub fn project_count(&self) -> String {
+        let (count, more) = match &self.projects {
+            Some(projects) => (
+                projects.nodes.len(),
+                projects.page_info.as_ref().is_some_and(|p| p.has_next_page),
+            ),
+            None => (0, false),
+        };
+        match (count, more) {
+            (count, true) => format!("{count}+ projects"),
+            (1, false) => String::from("1 project"),
+            (count, false) => format!("{count} projects"),
+        }
+    }
+}
+
+/// Every initiative in the organization, in Linear's order
+pub fn all(config: &Config, token: &str) -> Result<Vec<Initiative>, String> {
+    let mut initiatives = Vec::new();
+    let mut after = None;
+    loop {
+        let response: InitiativesResponse = request::Gql::new(config, token, INITIATIVES_DOC)
+            .maybe_put_string("after", after)
+            .execute()?;
+        let Initiatives { nodes, page_info } = response.data.initiatives;
+        initiatives.extend(nodes);
+
+        after = page_info.and_then(|p| p.next_cursor());
+        if after.is_none() {
+            return Ok(initiatives);
+        }
+    }
+}
+
+/// Look up an initiative by name, ignoring case
+pub fn find(config: &Config, token: &str, name: &str) -> Result<Initiative, String> {
+    let response: InitiativesResponse = request::Gql::new(config, token, INITIATIVE_DOC)
+        .put_string("name", name.to_string())
+        .execute()?;
+    response
+        .data
+        .initiatives
+        .nodes
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Initiative {name} not found"))
+}
+
+/// Every project of an initiative, in Linear's order
+pub fn projects(
+    config: &Config,
+    token: &str,
+    initiative: &Initiative,
+) -> Result<Vec<InitiativeProject>, String> {
+    let mut projects = Vec::new();
+    let mut after = None;
+    loop {
+        let response: ProjectsResponse = request::Gql::new(config, token, INITIATIVE_PROJECTS_DOC)
+            .put_string("id", initiative.id.clone())
+            .maybe_put_string("after", after)
+            .execute()?;
+        let Projects { nodes, page_info } = response.data.initiative.projects;
+        projects.extend(nodes);
+
+        after = page_info.and_then(|p| p.next_cursor());
+        if after.is_none() {
+            return Ok(projects);
+        }
+    }
+}
+
+/// Fetch the ids of the projects that belong to an initiative
+pub fn project_ids(config: &Config, token: &str, name: &str) -> Result<Vec<String>, String> {
+    let initiative = find(config, token, name)?;
+    let ids = projects(config, token, &initiative)?
+        .into_iter()
+        .map(|p| p.id)
+        .collect::<Vec<String>>();
+
+    if ids.is_empty() {
+        Err(format!("Initiative {name} has no projects"))
+    } else {
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_project_ids() {
+        let mut server = mockito::Server::new();
+        let find_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("eqIgnoreCase")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::initiative())
+            .create();
+        let first_page = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"variables\":\\{\"id\":\"i1\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::initiative_projects(true))
+            .create();
+        let second_page = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"after\":\"projects-1\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::initiative_projects(false))
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = project_ids(&config, "1234", "Platform Hardening");
+        assert_eq!(
+            result,
+            Ok(vec![
+                String::from("p1"),
+                String::from("p2"),
+                String::from("p1"),
+                String::from("p2")
+            ])
+        );
+        find_mock.assert();
+        first_page.assert();
+        second_page.assert();
+    }
+
+    #[test]
+    fn project_count_should_show_more_pages() {
+        let initiative = |count: usize, has_next_page: bool| Initiative {
+            projects: Some(ProjectIds {
+                nodes: (0..count).map(|_| ProjectId::default()).collect(),
+                page_info: Some(PageInfo {
+                    has_next_page,
+                    end_cursor: None,
+                }),
+            }),
+            ..Initiative::default()
+        };
+        assert_eq!(Initiative::default().project_count(), "0 projects");
+        assert_eq!(initiative(1, false).project_count(), "1 project");
+        assert_eq!(initiative(100, true).project_count(), "100+ projects");
+    }
+}
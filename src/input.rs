@@ -1,62 +1,262 @@
+use std::cell::RefCell;
 use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-use inquire::{Editor, Select, Text};
+use inquire::{Editor, MultiSelect, Password, Select, Text};
 
-/// Get text input from user
-pub fn string(desc: &str, mock_string: Option<String>) -> Result<String, String> {
-    if cfg!(test) {
-        if let Some(string) = mock_string {
-            Ok(string)
-        } else {
-            panic!("Must set mock_string in config")
-        }
-    } else {
-        Text::new(desc).prompt().map_err(|e| e.to_string())
+/// Set by --no-input and --yes, makes prompts return an error instead
+static PROMPTS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Set by --yes, prompts that have a sensible default take it instead of erroring
+static USE_DEFAULTS: AtomicBool = AtomicBool::new(false);
+
+/// Set from select_page_size in the config, 0 uses the inquire default
+static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of options shown at once in select prompts, 0 is ignored
+pub fn set_page_size(page_size: usize) {
+    PAGE_SIZE.store(page_size, Ordering::Relaxed);
+}
+
+/// Return errors instead of prompting for the rest of the process
+pub fn disable_prompts() {
+    PROMPTS_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Take defaults instead of prompting for the rest of the process, erroring when there is none
+pub fn use_defaults() {
+    USE_DEFAULTS.store(true, Ordering::Relaxed);
+    disable_prompts();
+}
+
+/// True with --yes, callers check this before prompting for something that has a default
+pub fn defaults_enabled() -> bool {
+    USE_DEFAULTS.load(Ordering::Relaxed)
+}
+
+/// False with --no-input or --yes, for prompts that are skipped rather than required
+pub fn prompts_enabled() -> bool {
+    !PROMPTS_DISABLED.load(Ordering::Relaxed)
+}
+
+/// The flag that disabled prompts, if any
+fn disabled_by() -> Option<&'static str> {
+    match (
+        PROMPTS_DISABLED.load(Ordering::Relaxed),
+        USE_DEFAULTS.load(Ordering::Relaxed),
+    ) {
+        (false, _) => None,
+        (true, true) => Some("--yes"),
+        (true, false) => Some("--no-input"),
     }
 }
 
-/// Get large amount of text from user using editor
-pub fn editor(
-    desc: &str,
-    default_text: &str,
-    mock_string: Option<String>,
-) -> Result<String, String> {
-    if cfg!(test) {
-        if let Some(string) = mock_string {
-            Ok(string)
-        } else {
-            panic!("Must set mock_string in config")
-        }
-    } else {
+/// Error naming the flag to use when prompting is disabled
+pub fn require(flag: &str) -> Result<(), String> {
+    check(disabled_by(), flag)
+}
+
+fn check(disabled_by: Option<&str>, flag: &str) -> Result<(), String> {
+    match disabled_by {
+        Some(option) => Err(format!("Missing {flag}, which is required with {option}")),
+        None => Ok(()),
+    }
+}
+
+/// Fallback for prompts that have no flag
+fn prompt_allowed(desc: &str) -> Result<(), String> {
+    match disabled_by() {
+        Some(option) => Err(format!("{desc} needs a prompt, which {option} disables")),
+        None => Ok(()),
+    }
+}
+
+/// Where the answers to prompts come from
+pub trait Input {
+    /// Text, starting with initial text that can be edited
+    fn string(&self, desc: &str, initial: &str) -> Result<String, String>;
+    /// Text that isn't shown as it is typed, such as a token
+    fn password(&self, desc: &str) -> Result<String, String>;
+    /// A larger amount of text, the extension lets an editor highlight formats such as TOML
+    fn editor(&self, desc: &str, default_text: &str, extension: &str) -> Result<String, String>;
+    /// The index of one of the options
+    fn select(&self, desc: &str, options: Vec<String>) -> Result<usize, String>;
+    /// The indexes of any number of the options, which may be none of them
+    fn multi_select(&self, desc: &str, options: Vec<String>) -> Result<Vec<usize>, String>;
+}
+
+/// Prompts in the terminal, unless --no-input or --yes disabled them
+pub struct Terminal;
+
+impl Input for Terminal {
+    fn string(&self, desc: &str, initial: &str) -> Result<String, String> {
+        prompt_allowed(desc)?;
+        Text::new(desc)
+            .with_initial_value(initial)
+            .prompt()
+            .map_err(|e| e.to_string())
+    }
+
+    fn password(&self, desc: &str) -> Result<String, String> {
+        prompt_allowed(desc)?;
+        Password::new(desc)
+            .without_confirmation()
+            .prompt()
+            .map_err(|e| e.to_string())
+    }
+
+    fn editor(&self, desc: &str, default_text: &str, extension: &str) -> Result<String, String> {
+        prompt_allowed(desc)?;
         Editor::new(desc)
             .with_predefined_text(default_text)
-            .with_file_extension(".md")
+            .with_file_extension(extension)
             .prompt()
             .map_err(|e| e.to_string())
     }
-}
 
-/// Select an input from a list
-pub fn select<T: Display>(
-    desc: &str,
-    options: Vec<T>,
-    mock_select: Option<usize>,
-) -> Result<T, String> {
-    if cfg!(test) {
-        if let Some(index) = mock_select {
-            Ok(options
-                .into_iter()
-                .nth(index)
-                .expect("Must provide a vector of options"))
-        } else {
-            panic!("Must set mock_select in config")
-        }
-    } else {
+    fn select(&self, desc: &str, options: Vec<String>) -> Result<usize, String> {
+        prompt_allowed(desc)?;
         Select::new(desc, options)
-            .prompt()
+            .with_page_size(page_size())
+            .raw_prompt()
+            .map(|option| option.index)
+            .map_err(|e| e.to_string())
+    }
+
+    fn multi_select(&self, desc: &str, options: Vec<String>) -> Result<Vec<usize>, String> {
+        prompt_allowed(desc)?;
+        MultiSelect::new(desc, options)
+            .with_page_size(page_size())
+            .raw_prompt()
+            .map(|options| options.into_iter().map(|option| option.index).collect())
             .map_err(|e| e.to_string())
     }
 }
+
+/// Answers given up front by tests, a prompt without an answer is an error
+#[cfg(test)]
+#[derive(Default)]
+pub struct Answers {
+    pub string: Option<String>,
+    pub select: Option<usize>,
+    pub multi_select: Option<Vec<usize>>,
+}
+
+#[cfg(test)]
+impl Answers {
+    fn missing(desc: &str, kind: &str) -> String {
+        format!("No {kind} answer for {desc}")
+    }
+}
+
+#[cfg(test)]
+impl Input for Answers {
+    fn string(&self, desc: &str, _initial: &str) -> Result<String, String> {
+        self.string
+            .clone()
+            .ok_or_else(|| Answers::missing(desc, "string"))
+    }
+
+    fn password(&self, desc: &str) -> Result<String, String> {
+        self.string
+            .clone()
+            .ok_or_else(|| Answers::missing(desc, "string"))
+    }
+
+    fn editor(&self, desc: &str, _default_text: &str, _extension: &str) -> Result<String, String> {
+        self.string
+            .clone()
+            .ok_or_else(|| Answers::missing(desc, "string"))
+    }
+
+    fn select(&self, desc: &str, options: Vec<String>) -> Result<usize, String> {
+        match self.select {
+            Some(index) if index < options.len() => Ok(index),
+            Some(index) => Err(format!("No option {index} for {desc}")),
+            None => Err(Answers::missing(desc, "select")),
+        }
+    }
+
+    fn multi_select(&self, desc: &str, _options: Vec<String>) -> Result<Vec<usize>, String> {
+        self.multi_select
+            .clone()
+            .ok_or_else(|| Answers::missing(desc, "multi select"))
+    }
+}
+
+thread_local! {
+    /// Per thread so that tests running in parallel can each give their own answers
+    static BACKEND: RefCell<Box<dyn Input>> = RefCell::new(Box::new(Terminal));
+}
+
+/// Answer the prompts on this thread from another backend, the terminal is used until this is called
+#[cfg(test)]
+pub fn set_backend(backend: impl Input + 'static) {
+    BACKEND.with(|current| *current.borrow_mut() = Box::new(backend));
+}
+
+fn with_backend<T>(prompt: impl FnOnce(&dyn Input) -> T) -> T {
+    BACKEND.with(|backend| prompt(backend.borrow().as_ref()))
+}
+
+/// Get text input from user
+pub fn string(desc: &str) -> Result<String, String> {
+    string_with_initial(desc, "")
+}
+
+/// Get text input from user without echoing it, i.e. a token
+pub fn password(desc: &str) -> Result<String, String> {
+    with_backend(|backend| backend.password(desc))
+}
+
+/// Get text input from user, starting with initial text they can edit
+pub fn string_with_initial(desc: &str, initial: &str) -> Result<String, String> {
+    with_backend(|backend| backend.string(desc, initial))
+}
+
+/// Get large amount of text from user using editor
+pub fn editor(desc: &str, default_text: &str) -> Result<String, String> {
+    editor_with_extension(desc, default_text, ".md")
+}
+
+/// Edit text using editor, the extension lets it highlight formats such as TOML
+pub fn editor_with_extension(
+    desc: &str,
+    default_text: &str,
+    extension: &str,
+) -> Result<String, String> {
+    with_backend(|backend| backend.editor(desc, default_text, extension))
+}
+
+/// Select an input from a list, typing narrows the options with fuzzy matching
+pub fn select<T: Display>(desc: &str, options: Vec<T>) -> Result<T, String> {
+    let labels = options.iter().map(|option| option.to_string()).collect();
+    let index = with_backend(|backend| backend.select(desc, labels))?;
+    options
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| format!("No option {index} for {desc}"))
+}
+
+/// Select any number of inputs from a list, which may be none of them
+pub fn multi_select<T: Display>(desc: &str, options: Vec<T>) -> Result<Vec<T>, String> {
+    let labels = options.iter().map(|option| option.to_string()).collect();
+    let indexes = with_backend(|backend| backend.multi_select(desc, labels))?;
+    Ok(options
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| indexes.contains(index))
+        .map(|(_, option)| option)
+        .collect())
+}
+
+fn page_size() -> usize {
+    match PAGE_SIZE.load(Ordering::Relaxed) {
+        0 => Select::<String>::DEFAULT_PAGE_SIZE,
+        page_size => page_size,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,12 +264,55 @@ mod tests {
 
     #[test]
     fn can_select() {
-        let result = select("type", vec!["there", "are", "words"], Some(0));
-        let expected = Ok("there");
-        assert_eq!(result, expected);
+        set_backend(Answers {
+            select: Some(1),
+            ..Answers::default()
+        });
+        let result = select("type", vec!["there", "are", "words"]);
+        assert_eq!(result, Ok("are"));
+    }
+
+    #[test]
+    fn can_multi_select() {
+        set_backend(Answers {
+            multi_select: Some(vec![0, 2]),
+            ..Answers::default()
+        });
+        let result = multi_select("type", vec!["there", "are", "words"]);
+        assert_eq!(result, Ok(vec!["there", "words"]));
 
-        let result = select("type", vec!["there", "are", "words"], Some(1));
-        let expected = Ok("are");
-        assert_eq!(result, expected);
+        set_backend(Answers {
+            multi_select: Some(Vec::new()),
+            ..Answers::default()
+        });
+        let result = multi_select("type", vec!["there", "are", "words"]);
+        assert_eq!(result, Ok(Vec::new()));
+    }
+
+    #[test]
+    fn answers_should_error_without_an_answer() {
+        let answers = Answers {
+            select: Some(3),
+            ..Answers::default()
+        };
+        assert_eq!(
+            answers.string("Title", ""),
+            Err(String::from("No string answer for Title"))
+        );
+        assert_eq!(
+            answers.select("State", vec![String::from("Todo")]),
+            Err(String::from("No option 3 for State"))
+        );
+    }
+
+    #[test]
+    fn check_should_name_flag() {
+        assert_eq!(check(None, "--title"), Ok(()));
+        assert_eq!(
+            check(Some("--no-input"), "--title"),
+            Err(String::from(
+                "Missing --title, which is required with --no-input"
+            ))
+        );
     }
 }
@@ -1,14 +1,34 @@
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use crossterm::{
+    cursor, execute,
+    terminal::{self, ClearType},
+};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
 
 use crate::{
-    color,
+    attachment::{self, Attachments},
+    branch, color,
     config::Config,
-    input,
+    customer, date, format, git, history, input, label,
     priority::{self, Priority},
-    request,
+    processor,
+    relation::{self, Relations},
+    request::{self, PageInfo},
+    schema,
     team::{Project, State, Team},
+    time,
+    user::User,
     viewer,
 };
 
@@ -20,6 +40,11 @@ const ISSUE_CREATE_DOC: &str = "mutation (
                     $priority: Int
                     $description: String
                     $projectId: String
+                    $estimate: Int
+                    $dueDate: TimelessDate
+                    $parentId: String
+                    $labelIds: [String!]
+                    $cycleId: String
                 ) {
                 issueCreate(
                     input: {
@@ -30,6 +55,11 @@ const ISSUE_CREATE_DOC: &str = "mutation (
                         priority: $priority
                         description: $description
                         projectId: $projectId
+                        estimate: $estimate
+                        dueDate: $dueDate
+                        parentId: $parentId
+                        labelIds: $labelIds
+                        cycleId: $cycleId
                     }
                 ) {
                     issue {
@@ -39,6 +69,8 @@ const ISSUE_CREATE_DOC: &str = "mutation (
                         description
                         url
                         branchName
+                        estimate
+                        dueDate
                         state {
                             id
                             name
@@ -64,20 +96,40 @@ const ISSUE_UPDATE_DOC: &str = "mutation (
                         description
                         url
                         branchName
+                        estimate
+                        dueDate
                         state {
                             id
                             name
+                            position
                         }
                     }
                     }
                 }
                 ";
 
+const ISSUE_UNARCHIVE_DOC: &str = "mutation (
+                    $id: String!
+                ) {
+                issueUnarchive(
+                    id: $id
+                ) {
+                    success
+                    entity {
+                        identifier
+                        url
+                    }
+                }
+                }
+                ";
+
 const ISSUE_LIST_DOC: &str = "query (
                     $filter: IssueFilter,
+                    $includeArchived: Boolean,
                 ) {
                 issues (
                     filter: $filter
+                    includeArchived: $includeArchived
                 ) {
                         nodes {
                             id
@@ -85,7 +137,37 @@ const ISSUE_LIST_DOC: &str = "query (
                             title
                             description
                             url
+                            priority
+                            createdAt
+                            updatedAt
+                            estimate
+                            dueDate
+                            slaStartedAt
+                            slaBreachesAt
+                            archivedAt
                         branchName
+                        customerNeeds {
+                            nodes {
+                                id
+                            }
+                        }
+                        team {
+                            id
+                            name
+                        }
+                        project {
+                            id
+                            name
+                        }
+                        labels {
+                            nodes {
+                                id
+                                name
+                            }
+                        }
+                        parent {
+                            id
+                        }
                         children {
                             nodes {
                                 id
@@ -97,12 +179,14 @@ const ISSUE_LIST_DOC: &str = "query (
                                 state {
                                     id
                                     name
+                                    position
                                 }
                             }
                         }
                         state {
                             id
                             name
+                            position
                         }
                     }
                   }
@@ -121,28 +205,57 @@ const ISSUE_BRANCH_VIEW_DOC: &str = "query (
                         title
                         branchName
                         description
-                        comments {
+                        estimate
+                        dueDate
+                        slaStartedAt
+                        slaBreachesAt
+                        priority
+                        createdAt
+                        updatedAt
+                        assignee {
+                            displayName
+                        }
+                        project {
+                            id
+                            name
+                        }
+                        labels {
                             nodes {
-                                body
-                                createdAt
-                                editedAt
-                                url
-                                user {
-                                    displayName
+                                id
+                                name
+                            }
+                        }
+                        team {
+                            id
+                            name
+                        }
+                        relations {
+                            nodes {
+                                type
+                                relatedIssue {
+                                    identifier
+                                    title
                                 }
-                                children {
-                                    nodes {
-                                        body
-                                        createdAt
-                                        editedAt
-                                        url
-                                        user {
-                                            displayName
-                                        }
-                                    }
+                            }
+                        }
+                        inverseRelations {
+                            nodes {
+                                type
+                                issue {
+                                    identifier
+                                    title
                                 }
                             }
                         }
+                        attachments {
+                            nodes {
+                                title
+                                url
+                                subtitle
+                                metadata
+                                sourceType
+                            }
+                        }
                         state {
                             id
                             name
@@ -164,28 +277,57 @@ const ISSUE_ID_VIEW_DOC: &str = "query (
                         title
                         branchName
                         description
-                        comments {
+                        estimate
+                        dueDate
+                        slaStartedAt
+                        slaBreachesAt
+                        priority
+                        createdAt
+                        updatedAt
+                        assignee {
+                            displayName
+                        }
+                        project {
+                            id
+                            name
+                        }
+                        labels {
                             nodes {
-                                body
-                                createdAt
-                                editedAt
-                                url
-                                user {
-                                    displayName
+                                id
+                                name
+                            }
+                        }
+                        team {
+                            id
+                            name
+                        }
+                        relations {
+                            nodes {
+                                type
+                                relatedIssue {
+                                    identifier
+                                    title
                                 }
-                                children {
-                                    nodes {
-                                        body
-                                        createdAt
-                                        editedAt
-                                        url
-                                        user {
-                                            displayName
-                                        }
-                                    }
+                            }
+                        }
+                        inverseRelations {
+                            nodes {
+                                type
+                                issue {
+                                    identifier
+                                    title
                                 }
                             }
                         }
+                        attachments {
+                            nodes {
+                                title
+                                url
+                                subtitle
+                                metadata
+                                sourceType
+                            }
+                        }
                         state {
                             id
                             name
@@ -195,6 +337,80 @@ const ISSUE_ID_VIEW_DOC: &str = "query (
                 }
                 ";
 
+const ISSUE_COMMENTS_DOC: &str = "query (
+                    $id: String!
+                    $after: String
+                ) {
+                issue(
+                    id: $id
+                )   {
+                        comments (
+                            first: 100
+                            after: $after
+                        ) {
+                            nodes {
+                                id
+                                body
+                                createdAt
+                                editedAt
+                                url
+                                parent {
+                                    id
+                                }
+                                user {
+                                    displayName
+                                    statusEmoji
+                                    statusLabel
+                                    statusUntilAt
+                                }
+                            }
+                            pageInfo {
+                                hasNextPage
+                                endCursor
+                            }
+                        }
+                    }
+                }
+                ";
+
+const COMMENT_CREATE_DOC: &str = "mutation (
+                    $issueId: String!
+                    $body: String!
+                ) {
+                commentCreate(
+                    input: {
+                        issueId: $issueId
+                        body: $body
+                    }
+                ) {
+                    success
+                    comment {
+                        url
+                    }
+                }
+                }
+                ";
+
+/// Identifiers from commit messages to look up before giving up, words like UTF-8 also match
+const COMMIT_IDENTIFIER_ATTEMPTS: usize = 3;
+
+/// Comments posted at once by issue comment, the rest wait for a worker to be free
+const COMMENT_WORKERS: usize = 4;
+
+/// Columns of issue list --output csv, named after their --format placeholders
+const CSV_COLUMNS: [&str; 10] = [
+    "identifier",
+    "title",
+    "state",
+    "priority",
+    "estimate",
+    "project",
+    "url",
+    "due_date",
+    "created_at",
+    "updated_at",
+];
+
 // ISSUE LIST
 #[derive(Deserialize, Serialize, Debug)]
 struct IssueListResponse {
@@ -206,7 +422,7 @@ struct IssueListData {
     issues: IssueListIssues,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 struct IssueListIssues {
     nodes: Vec<Issue>,
 }
@@ -227,6 +443,22 @@ struct IssueData {
     issue: Option<Issue>,
 }
 
+// ISSUE COMMENTS
+#[derive(Deserialize, Serialize, Debug)]
+struct IssueCommentsResponse {
+    data: Option<IssueCommentsData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct IssueCommentsData {
+    issue: Option<IssueComments>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct IssueComments {
+    comments: CommentsConnection,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 struct IssueBranchViewData {
     #[serde(rename = "issueVcsBranchSearch")]
@@ -267,51 +499,295 @@ struct IssueUpdate {
     issue: Option<Issue>,
 }
 
+// COMMENT CREATE
+
 #[derive(Deserialize, Serialize, Debug)]
-struct Issue {
-    id: String,
-    state: State,
-    identifier: String,
+struct CommentCreateResponse {
+    data: Option<CommentCreateData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CommentCreateData {
+    #[serde(rename = "commentCreate")]
+    comment_create: CommentCreate,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CommentCreate {
+    success: bool,
+    comment: Option<CreatedComment>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct CreatedComment {
     url: String,
-    title: String,
+}
 
-    #[serde(rename = "branchName")]
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct Issue {
+    pub id: String,
+    #[serde(default)]
+    pub state: State,
+    pub identifier: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+
+    #[serde(rename = "branchName", default)]
     branch_name: String,
-    description: Option<String>,
+    pub description: Option<String>,
+    pub team: Option<Team>,
+    project: Option<Project>,
+    assignee: Option<User>,
+    labels: Option<label::Labels>,
+    priority: Option<f32>,
+    estimate: Option<f32>,
+    #[serde(rename = "dueDate")]
+    due_date: Option<String>,
+    #[serde(rename = "slaStartedAt")]
+    sla_started_at: Option<String>,
+    #[serde(rename = "slaBreachesAt")]
+    sla_breaches_at: Option<String>,
+    #[serde(rename = "customerNeeds")]
+    customer_needs: Option<customer::Needs>,
+    #[serde(rename = "archivedAt")]
+    archived_at: Option<String>,
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
+    #[serde(rename = "updatedAt")]
+    updated_at: Option<String>,
+    parent: Option<IssueParent>,
     children: Option<IssueListIssues>,
     comments: Option<CommentsConnection>,
+    relations: Option<Relations>,
+    #[serde(rename = "inverseRelations")]
+    inverse_relations: Option<Relations>,
+    attachments: Option<Attachments>,
+    #[serde(skip)]
+    history: Option<Vec<history::Entry>>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+/// File formats available for issue export
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
+pub enum ExportFormat {
+    Md,
+    Html,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &str {
+        match self {
+            ExportFormat::Md => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// Output formats available for issue list
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
+pub enum Output {
+    /// Human readable list
+    Text,
+    /// JSON array including the UUID of each issue, for scripts and integrations
+    Json,
+    /// CSV with a header row, for spreadsheets
+    Csv,
+    /// CSV in the columns Jira's importer expects, with sub-tasks linked to their parents
+    JiraCsv,
+}
+
+/// Orderings available for issue list
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
+pub enum Sort {
+    /// Highest priority first, no priority last
+    Priority,
+    /// In order of workflow state position
+    State,
+    /// Most recently updated first
+    Updated,
+    /// Most recently created first
+    Created,
+    /// Team key, then issue number
+    Identifier,
+    /// Closest to breaching its SLA first, no SLA last
+    Breach,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
 struct CommentsConnection {
     nodes: Vec<Comment>,
+    #[serde(rename = "pageInfo")]
+    page_info: Option<PageInfo>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 struct Comment {
+    id: String,
+    parent: Option<CommentParent>,
+    #[serde(default)]
     body: String,
-    #[serde(rename = "createdAt")]
+    #[serde(rename = "createdAt", default)]
     created_at: String,
     #[serde(rename = "editedAt")]
     edited_at: Option<String>,
+    #[serde(default)]
     url: String,
+    #[serde(default)]
     user: User,
-    children: Option<CommentsConnection>,
+    /// Replies, oldest first. Built from parent ids rather than fetched
+    #[serde(skip)]
+    replies: Vec<Comment>,
+    /// Posted since the last refresh of issue view --watch
+    #[serde(skip)]
+    new: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct IssueParent {
+    id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct CommentParent {
+    id: String,
 }
 
 impl Comment {
-    fn format(&self) -> String {
+    /// Render the comment followed by its replies, indented by depth
+    fn format(&self, date_format: &str, depth: usize) -> String {
         let divider = color::green_string("----------------");
         let body = &self.body;
-        let user = color::cyan_string(&self.user.display_name);
-        let created_at = &self.created_at;
-        format!("\n{body}\n\n- {user} {created_at}\n\n{divider}")
+        let user = color::cyan_string(&self.user.name_with_status());
+        let created_at = date::display(&self.created_at, date_format);
+        let new = match self.new {
+            true => format!(" {}", color::red_string("NEW")),
+            false => String::new(),
+        };
+        let indent = "    ".repeat(depth);
+        let text = format!("\n{body}\n\n- {user} {created_at}{new}\n\n{divider}")
+            .lines()
+            .map(|line| match line {
+                "" => String::new(),
+                line => format!("{indent}{line}"),
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        self.replies.iter().fold(text, |text, reply| {
+            format!("{text}\n{}", reply.format(date_format, depth + 1))
+        })
+    }
+
+    /// Flag the comments and replies that aren't in seen, returning how many there were
+    fn mark_new(&mut self, seen: &HashSet<String>) -> usize {
+        self.new = !seen.contains(&self.id);
+        self.replies
+            .iter_mut()
+            .fold(usize::from(self.new), |count, reply| {
+                count + reply.mark_new(seen)
+            })
+    }
+
+    fn ids(&self) -> Vec<String> {
+        self.replies
+            .iter()
+            .flat_map(|reply| reply.ids())
+            .chain([self.id.clone()])
+            .collect()
+    }
+
+    fn to_markdown(&self, heading_level: usize, date_format: &str) -> String {
+        let heading = "#".repeat(heading_level);
+        let body = &self.body;
+        let created_at = date::display(&self.created_at, date_format);
+        let user = &self.user.display_name;
+        let markdown = format!("\n{heading} {user} {created_at}\n\n{body}\n");
+
+        // Markdown has no headings past level 6
+        self.replies.iter().fold(markdown, |markdown, reply| {
+            markdown + &reply.to_markdown((heading_level + 1).min(6), date_format)
+        })
+    }
+
+    fn to_html(&self, date_format: &str) -> String {
+        let user = escape_html(&self.user.display_name);
+        let created_at = escape_html(&date::display(&self.created_at, date_format));
+        let body = escape_html(&self.body);
+        let replies = match self.replies.is_empty() {
+            true => String::new(),
+            false => {
+                let replies = self
+                    .replies
+                    .iter()
+                    .map(|r| r.to_html(date_format))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                format!("\n<div class=\"replies\">{replies}</div>")
+            }
+        };
+        format!(
+            "<div class=\"comment\"><p class=\"meta\">{user} {created_at}</p><div class=\"body\">{body}</div></div>{replies}"
+        )
     }
 }
-#[derive(Deserialize, Serialize, Debug)]
-struct User {
-    #[serde(rename = "displayName")]
-    display_name: String,
+
+/// Arrange comments into threads using their parent ids, oldest first at every level.
+/// Replies to comments that weren't fetched are treated as top level.
+fn thread(comments: Vec<Comment>) -> Vec<Comment> {
+    let ids = comments
+        .iter()
+        .map(|c| c.id.clone())
+        .collect::<HashSet<String>>();
+    let mut replies_by_parent: HashMap<String, Vec<Comment>> = HashMap::new();
+    let mut top_level = Vec::new();
+    for comment in comments {
+        match &comment.parent {
+            Some(CommentParent { id }) if ids.contains(id) => replies_by_parent
+                .entry(id.clone())
+                .or_default()
+                .push(comment),
+            _ => top_level.push(comment),
+        }
+    }
+
+    fn attach(
+        mut comment: Comment,
+        replies_by_parent: &mut HashMap<String, Vec<Comment>>,
+    ) -> Comment {
+        let mut replies = replies_by_parent.remove(&comment.id).unwrap_or_default();
+        replies.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        comment.replies = replies
+            .into_iter()
+            .map(|reply| attach(reply, replies_by_parent))
+            .collect();
+        comment
+    }
+
+    top_level.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    top_level
+        .into_iter()
+        .map(|comment| attach(comment, &mut replies_by_parent))
+        .collect()
+}
+
+/// Fetch every comment on an issue, following pagination, and arrange them into threads
+fn get_comments(config: &Config, token: &str, issue_id: &str) -> Result<Vec<Comment>, String> {
+    let mut comments = Vec::new();
+    let mut after = None;
+    loop {
+        let response = request::Gql::new(config, token, ISSUE_COMMENTS_DOC)
+            .put_string("id", issue_id.to_string())
+            .maybe_put_string("after", after)
+            .run()?;
+        let CommentsConnection { nodes, page_info } = issue_comments_response(response)?;
+        comments.extend(nodes);
+
+        after = page_info.and_then(|p| p.next_cursor());
+        if after.is_none() {
+            return Ok(thread(comments));
+        }
+    }
 }
 
 enum Format {
@@ -320,7 +796,7 @@ enum Format {
 }
 
 impl Issue {
-    fn format(&self, format: Format) -> String {
+    fn format(&self, format: Format, date_format: &str) -> String {
         let title = color::green_string(&self.title);
         let id = color::blue_string(&self.identifier);
         let description = self
@@ -337,37 +813,263 @@ impl Issue {
         } else {
             String::new()
         };
-        let comments = &self.render_comments();
+        let estimate = match self.estimate {
+            Some(estimate) => format!(" | {estimate} points"),
+            None => String::new(),
+        };
+        let due_date = match &self.due_date {
+            Some(due_date) if date::is_overdue(due_date, date::today()) => {
+                let due_date = date::display(due_date, date_format);
+                format!(" | {}", color::red_string(&format!("Overdue {due_date}")))
+            }
+            Some(due_date) => format!(" | Due {}", date::display(due_date, date_format)),
+            None => String::new(),
+        };
+        let archived = match &self.archived_at {
+            Some(archived_at) => format!(" | Archived {}", date::display(archived_at, date_format)),
+            None => String::new(),
+        };
+        let sla = match self.sla_breaches_at.as_deref() {
+            Some(breaches_at) => sla_countdown(breaches_at, Utc::now())
+                .map(|countdown| format!(" | {countdown}"))
+                .unwrap_or_default(),
+            None => String::new(),
+        };
 
         match format {
             Format::View => {
-                let divider = color::green_string("--- COMMENTS ---");
+                let comments = match &self.comments {
+                    Some(CommentsConnection { nodes, .. }) if nodes.is_empty() => {
+                        let divider = color::green_string("--- COMMENTS ---");
+                        format!("\n\n{divider}\n\n<No Comments>")
+                    }
+                    Some(CommentsConnection { nodes, .. }) => {
+                        let divider = color::green_string("--- COMMENTS ---");
+                        let comments = nodes
+                            .iter()
+                            .map(|c| c.format(date_format, 0))
+                            .collect::<Vec<String>>()
+                            .join("\n");
+                        format!("\n\n{divider}\n{comments}")
+                    }
+                    None => String::new(),
+                };
+                let relations = [
+                    relation::describe(&self.relations, &self.inverse_relations),
+                    attachment::describe(&self.attachments),
+                ]
+                .into_iter()
+                .filter(|lines| !lines.is_empty())
+                .map(|lines| format!("\n{lines}"))
+                .collect::<String>();
+                let history = match &self.history {
+                    Some(entries) => {
+                        let divider = color::green_string("--- HISTORY ---");
+                        format!(
+                            "\n\n{divider}\n\n{}",
+                            history::describe(entries, date_format)
+                        )
+                    }
+                    None => String::new(),
+                };
+                let metadata = self.metadata(date_format);
                 format!(
-                    "{title}\n{id} | {state}{child_tickets}\n{url}\n{branch_name}\n\n{description}\n\n{divider}\n{comments}"
+                    "{title}\n{id} | {state}{estimate}{due_date}{sla}{child_tickets}\n{metadata}{url}\n{branch_name}{relations}\n\n{description}{history}{comments}"
                 )
             }
 
             Format::List => {
                 let id = format!("{: >10}", id);
-                format!("- {id} | {title}\n             | {state}{child_tickets}\n")
+                let customer_requests = match self.customer_needs.as_ref().map(|n| n.count()) {
+                    Some(1) => String::from(" | 1 customer request"),
+                    Some(count) if count > 1 => format!(" | {count} customer requests"),
+                    _ => String::new(),
+                };
+                format!(
+                    "- {id} | {title}\n             | {state}{estimate}{due_date}{sla}{child_tickets}{customer_requests}{archived}\n"
+                )
             }
         }
     }
 
-    fn render_comments(&self) -> String {
-        match &self.comments {
-            Some(CommentsConnection { nodes }) => {
-                let mut comment_text = nodes.iter().map(|c| c.format()).collect::<Vec<String>>();
+    /// Lines for the assignee, priority, project, labels, and timestamps that are set
+    fn metadata(&self, date_format: &str) -> String {
+        let labels = self.labels.as_ref().map(|labels| {
+            labels
+                .nodes
+                .iter()
+                .map(|label| label.name.clone())
+                .collect::<Vec<String>>()
+                .join(", ")
+        });
+        let date = |value: &Option<String>| value.as_ref().map(|v| date::display(v, date_format));
+
+        [
+            (
+                "Assignee",
+                self.assignee.as_ref().map(|a| a.display_name.clone()),
+            ),
+            (
+                "Priority",
+                self.priority
+                    .filter(|p| *p > 0.0)
+                    .map(|p| priority::from_int(p as u8).to_string()),
+            ),
+            ("Project", self.project.as_ref().map(|p| p.name.clone())),
+            ("Labels", labels),
+            ("Created", date(&self.created_at)),
+            ("Updated", date(&self.updated_at)),
+            ("SLA started", date(&self.sla_started_at)),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| {
+            value
+                .filter(|v| !v.is_empty())
+                .map(|v| format!("{name}: {v}\n"))
+        })
+        .collect()
+    }
 
-                if comment_text.is_empty() {
-                    String::from("\n<No Comments>")
-                } else {
-                    comment_text.reverse();
-                    comment_text.join("\n")
-                }
+    /// The url and branch name printed once an issue is created
+    pub fn created(&self) -> String {
+        format!("{}\n{}", self.url, self.branch_name)
+    }
+
+    /// YAML front matter, leaving out fields that aren't set. Dates are kept as ISO 8601
+    fn front_matter(&self) -> String {
+        let labels = self.labels.as_ref().map(|labels| {
+            let names = labels
+                .nodes
+                .iter()
+                .map(|label| escape_yaml(&label.name))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("[{names}]")
+        });
+        let quoted = |value: &Option<String>| value.as_ref().map(|v| escape_yaml(v));
+
+        let fields = [
+            ("identifier", Some(escape_yaml(&self.identifier))),
+            ("title", Some(escape_yaml(&self.title))),
+            ("state", Some(escape_yaml(&self.state.name))),
+            ("team", self.team.as_ref().map(|t| escape_yaml(&t.name))),
+            (
+                "project",
+                self.project.as_ref().map(|p| escape_yaml(&p.name)),
+            ),
+            (
+                "assignee",
+                self.assignee.as_ref().map(|a| escape_yaml(&a.display_name)),
+            ),
+            (
+                "priority",
+                self.priority
+                    .filter(|p| *p > 0.0)
+                    .map(|p| escape_yaml(&priority::from_int(p as u8).to_string())),
+            ),
+            ("estimate", self.estimate.map(|e| e.to_string())),
+            ("labels", labels),
+            ("due_date", quoted(&self.due_date)),
+            ("created_at", quoted(&self.created_at)),
+            ("updated_at", quoted(&self.updated_at)),
+            ("url", Some(escape_yaml(&self.url))),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|v| format!("{name}: {v}\n")))
+        .collect::<String>();
+        format!("---\n{fields}---\n")
+    }
+
+    fn to_markdown(&self, include_comments: bool, date_format: &str) -> String {
+        let Issue {
+            identifier,
+            title,
+            url,
+            state,
+            ..
+        } = self;
+        let description = self.description.clone().unwrap_or_default();
+        let mut markdown = format!(
+            "# {identifier} {title}\n\n- State: {}\n- URL: {url}\n\n{description}\n",
+            state.name
+        );
+
+        if include_comments {
+            markdown.push_str("\n## Comments\n");
+            for comment in self.comments_oldest_first() {
+                markdown.push_str(&comment.to_markdown(3, date_format));
             }
-            None => String::new(),
         }
+        markdown
+    }
+
+    fn to_html(&self, include_comments: bool, date_format: &str) -> String {
+        let identifier = escape_html(&self.identifier);
+        let title = escape_html(&self.title);
+        let url = escape_html(&self.url);
+        let state = escape_html(&self.state.name);
+        let description = escape_html(&self.description.clone().unwrap_or_default());
+
+        let comments = if include_comments {
+            let comments = self
+                .comments_oldest_first()
+                .iter()
+                .map(|comment| comment.to_html(date_format))
+                .collect::<Vec<String>>()
+                .join("\n");
+            format!("<h2>Comments</h2>\n{comments}")
+        } else {
+            String::new()
+        };
+
+        format!(
+            "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{identifier} {title}</title>
+<style>
+body {{ font-family: sans-serif; max-width: 50em; margin: 2em auto; color: #222; }}
+.meta {{ color: #666; }}
+.body {{ white-space: pre-wrap; }}
+.comment {{ border-top: 1px solid #ddd; padding: 0.5em 0; }}
+.replies {{ margin-left: 2em; }}
+</style>
+</head>
+<body>
+<h1>{identifier} {title}</h1>
+<p class=\"meta\">{state} | <a href=\"{url}\">{url}</a></p>
+<div class=\"body\">{description}</div>
+{comments}
+</body>
+</html>
+"
+        )
+    }
+
+    fn comments_oldest_first(&self) -> Vec<&Comment> {
+        match &self.comments {
+            Some(CommentsConnection { nodes, .. }) => nodes.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Fetch the comment threads, keeping only the most recent when there is a limit
+    fn load_comments(
+        &mut self,
+        config: &Config,
+        token: &str,
+        limit: Option<usize>,
+    ) -> Result<(), String> {
+        let mut threads = get_comments(config, token, &self.id)?;
+        if let Some(limit) = limit {
+            threads.drain(..threads.len().saturating_sub(limit));
+        }
+        self.comments = Some(CommentsConnection {
+            nodes: threads,
+            page_info: None,
+        });
+        Ok(())
     }
 
     pub fn is_parent(&self) -> bool {
@@ -392,31 +1094,160 @@ impl Issue {
         let name = self.state.name.clone();
         format!("{parent}{name}")
     }
-}
 
-impl Display for Issue {
-    /// This is for rendering in a select
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let title = color::green_string(&self.title);
-        let id = self.identifier.clone();
-        let state = self.state.name.clone();
+    /// Machine readable representation, keyed by the UUID as well as the identifier
+    pub fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "identifier": self.identifier,
+            "title": self.title,
+            "state": self.state.name,
+            "priority": self.priority,
+            "estimate": self.estimate,
+            "dueDate": self.due_date,
+            "slaBreachesAt": self.sla_breaches_at,
+            "url": self.url,
+            "branchName": self.branch_name,
+            "createdAt": self.created_at,
+            "updatedAt": self.updated_at,
+        })
+    }
 
-        let child_tickets = if self.is_parent() {
-            let child_count = self.child_count();
-            format!(" | {child_count} child tickets")
-        } else {
-            String::new()
+    /// Render the issue with a --format string such as "{identifier}\t{state}\t{title}"
+    fn to_format(&self, format: &str, date_format: &str) -> Result<String, String> {
+        format::render(format, |name| self.placeholder(name, date_format))
+    }
+
+    /// Value of a --format placeholder, fields that aren't set are empty
+    fn placeholder(&self, name: &str, date_format: &str) -> Option<String> {
+        let date = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|value| date::display(value, date_format))
+                .unwrap_or_default()
         };
-        let id = format!("{: >10}", id);
+        let number = |value: Option<f32>| value.map(|v| v.to_string()).unwrap_or_default();
+
+        let value = match name {
+            "id" => self.id.clone(),
+            "identifier" => self.identifier.clone(),
+            "title" => self.title.clone(),
+            "state" => self.state.name.clone(),
+            "team" => self
+                .team
+                .as_ref()
+                .map(|t| t.name.clone())
+                .unwrap_or_default(),
+            "project" => self
+                .project
+                .as_ref()
+                .map(|p| p.name.clone())
+                .unwrap_or_default(),
+            "priority" => number(self.priority),
+            "estimate" => number(self.estimate),
+            "due_date" => date(&self.due_date),
+            "created_at" => date(&self.created_at),
+            "updated_at" => date(&self.updated_at),
+            "url" => self.url.clone(),
+            "branch_name" => self.branch_name.clone(),
+            "description" => self.description.clone().unwrap_or_default(),
+            _ => return None,
+        };
+        Some(value)
+    }
 
-        if self.is_parent() {
-            write!(
-                f,
-                "- {id} | {title}\n               | {state}{child_tickets}\n"
-            )
-        } else {
-            write!(f, "- {id} | {title}\n               | {state}\n")
-        }
+    /// A row of issue list --output csv, in the order of CSV_COLUMNS
+    fn to_csv_row(&self, date_format: &str) -> String {
+        CSV_COLUMNS
+            .iter()
+            .map(|name| escape_csv(&self.placeholder(name, date_format).unwrap_or_default()))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    /// Priority for sorting, where 0 (No priority) goes last
+    fn priority_rank(&self) -> f32 {
+        match self.priority {
+            Some(priority) if priority > 0.0 => priority,
+            _ => 5.0,
+        }
+    }
+
+    /// Split ENG-123 into ("ENG", 123) so that ENG-9 sorts before ENG-10
+    fn identifier_parts(&self) -> (String, u32) {
+        match self.identifier.rsplit_once('-') {
+            Some((key, number)) => (key.to_string(), number.parse().unwrap_or_default()),
+            None => (self.identifier.clone(), 0),
+        }
+    }
+}
+
+/// Time left before an SLA is breached, i.e. SLA 2d 4h left, in red when it is less than a day away
+fn sla_countdown(breaches_at: &str, now: DateTime<Utc>) -> Option<String> {
+    let breaches_at = DateTime::parse_from_rfc3339(breaches_at).ok()?;
+    let remaining = breaches_at.with_timezone(&Utc) - now;
+    let countdown = if remaining < TimeDelta::zero() {
+        format!("SLA breached {} ago", sla_duration(-remaining))
+    } else {
+        format!("SLA {} left", sla_duration(remaining))
+    };
+    match remaining < TimeDelta::days(1) {
+        true => Some(color::red_string(&countdown)),
+        false => Some(color::blue_string(&countdown)),
+    }
+}
+
+/// Days and hours, or hours and minutes when under a day
+fn sla_duration(delta: TimeDelta) -> String {
+    match delta.num_days() {
+        0 => time::format(delta.num_minutes() as u64),
+        days => format!("{days}d {}h", delta.num_hours() % 24),
+    }
+}
+
+pub fn sort_issues(issues: &mut [Issue], sort: &Option<Sort>, reverse: bool) {
+    match sort {
+        None => (),
+        Some(Sort::Priority) => {
+            issues.sort_by(|a, b| a.priority_rank().total_cmp(&b.priority_rank()))
+        }
+        Some(Sort::State) => issues.sort_by(|a, b| a.state.position.total_cmp(&b.state.position)),
+        Some(Sort::Updated) => issues.sort_by(|a, b| b.updated_at.cmp(&a.updated_at)),
+        Some(Sort::Created) => issues.sort_by(|a, b| b.created_at.cmp(&a.created_at)),
+        Some(Sort::Identifier) => issues.sort_by_key(|i| i.identifier_parts()),
+        Some(Sort::Breach) => {
+            issues.sort_by_key(|i| (i.sla_breaches_at.is_none(), i.sla_breaches_at.clone()))
+        }
+    }
+
+    if reverse {
+        issues.reverse();
+    }
+}
+
+impl Display for Issue {
+    /// This is for rendering in a select
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = color::green_string(&self.title);
+        let id = self.identifier.clone();
+        let state = self.state.name.clone();
+
+        let child_tickets = if self.is_parent() {
+            let child_count = self.child_count();
+            format!(" | {child_count} child tickets")
+        } else {
+            String::new()
+        };
+        let id = format!("{: >10}", id);
+
+        if self.is_parent() {
+            write!(
+                f,
+                "- {id} | {title}\n               | {state}{child_tickets}\n"
+            )
+        } else {
+            write!(f, "- {id} | {title}\n               | {state}\n")
+        }
     }
 }
 #[allow(clippy::too_many_arguments)]
@@ -428,123 +1259,855 @@ pub fn create(
     team: Team,
     project: Option<Project>,
     state: State,
-    assignee_id: String,
+    assignee_id: Option<String>,
     priority: Priority,
-) -> Result<String, String> {
+    estimate: Option<u8>,
+    due_date: Option<NaiveDate>,
+    parent_id: Option<String>,
+    label_ids: Vec<String>,
+    cycle_id: Option<String>,
+) -> Result<Issue, String> {
     let priority = priority::priority_to_int(&priority);
     let response = request::Gql::new(config, token, ISSUE_CREATE_DOC)
         .put_string("title", title)
-        .put_string("assigneeId", assignee_id)
+        .maybe_put_string("assigneeId", assignee_id)
         .put_string("teamId", team.id)
         .put_integer("priority", priority)
         .put_string("stateId", state.id)
         .maybe_put_string("projectId", project.map(|p| p.id))
+        .maybe_put_integer("estimate", estimate)
+        .maybe_put_string("dueDate", due_date.as_ref().map(date::to_string))
+        .maybe_put_string("parentId", parent_id)
+        .put_strings("labelIds", label_ids)
+        .maybe_put_string("cycleId", cycle_id)
         .put_string("description", description)
         .run()?;
 
-    let Issue {
-        url, branch_name, ..
-    } = issue_create_response(response)?;
+    issue_create_response(response)
+}
+
+/// Create a copy of an issue with its title, description, labels, priority, project, and estimate.
+/// On another team, labels are matched by name and the project is kept if the team has it.
+pub fn duplicate(
+    config: &Config,
+    token: &str,
+    original: &Issue,
+    team: Team,
+    state: State,
+) -> Result<Issue, String> {
+    let label_names = original
+        .labels
+        .as_ref()
+        .map(|labels| labels.nodes.iter().map(|l| l.name.clone()).collect())
+        .unwrap_or_default();
+    let label_ids = label::existing_ids(config, token, &team, label_names)?;
+    let same_team = original.team.as_ref().is_some_and(|t| t.id == team.id);
+    let project = original.project.clone().filter(|project| {
+        same_team
+            || team
+                .projects
+                .as_ref()
+                .is_some_and(|p| p.nodes.iter().any(|p| p.id == project.id))
+    });
+
+    create(
+        config,
+        token,
+        original.title.clone(),
+        original.description.clone().unwrap_or_default(),
+        team,
+        project,
+        state,
+        None,
+        priority::from_int(original.priority.unwrap_or_default() as u8),
+        original.estimate.map(|estimate| estimate as u8),
+        None,
+        None,
+        label_ids,
+        None,
+    )
+}
+
+/// Whose issues are fetched
+#[derive(Default, Debug, Clone, PartialEq)]
+pub enum Assignee {
+    /// Assigned to anyone, or to no one
+    #[default]
+    Any,
+    /// Assigned to the user with this id
+    User(String),
+    Unassigned,
+}
+
+/// Restrictions on which issues are fetched
+#[derive(Default, Debug)]
+pub struct Filter {
+    pub assignee: Assignee,
+    /// Only issues created by the user with this id
+    pub creator_id: Option<String>,
+    pub team: Option<Team>,
+    pub project: Option<Project>,
+    /// Only issues in one of these projects, i.e. those of an initiative
+    pub project_ids: Option<Vec<String>>,
+    pub excluded_states: Vec<String>,
+    /// Only issues with an SLA. IssueFilter can't match on SLA fields, so this is applied after fetching.
+    pub breaching: bool,
+    /// Only archived issues, which are otherwise left out
+    pub archived: bool,
+}
+
+/// The state names to exclude from a list, taking --all-states and --include-state into account
+pub fn excluded_states(
+    configured: Vec<String>,
+    all_states: bool,
+    include_states: &[String],
+) -> Vec<String> {
+    if all_states {
+        return Vec::new();
+    }
 
-    Ok(format!("{url}\n{branch_name}"))
+    configured
+        .into_iter()
+        .filter(|s| !include_states.contains(s))
+        .collect()
 }
 
 pub fn list(
     config: &Config,
     token: &str,
-    assignee_id: Option<String>,
-    team: Option<Team>,
-    project: Option<Project>,
+    filter: Filter,
+    sort: Option<Sort>,
+    reverse: bool,
+    output: Output,
+    format: Option<String>,
 ) -> Result<String, String> {
-    let issues_text = get_issues(config, token, assignee_id, team, project).map(|i| {
-        i.into_iter()
-            .map(|j| j.format(Format::List))
-            .collect::<Vec<String>>()
-            .join("\n")
-    })?;
-    let title = color::green_string("Issues");
-    Ok(format!("\n{title}\n\n{issues_text}"))
+    let mut issues = get_issues(config, token, filter)?;
+    sort_issues(&mut issues, &sort, reverse);
+
+    if let Some(format) = format {
+        let date_format = date::pattern(&config.date_format);
+        return issues
+            .iter()
+            .map(|issue| issue.to_format(&format, &date_format))
+            .collect::<Result<Vec<String>, String>>()
+            .map(|lines| lines.join("\n"));
+    }
+
+    match output {
+        Output::Text => {
+            let date_format = date::pattern(&config.date_format);
+            let issues_text = issues
+                .into_iter()
+                .map(|j| j.format(Format::List, &date_format))
+                .collect::<Vec<String>>()
+                .join("\n");
+            let title = color::green_string("Issues");
+            Ok(format!("\n{title}\n\n{issues_text}"))
+        }
+        Output::Json => {
+            let issues = issues.iter().map(|i| i.to_json()).collect::<Vec<Value>>();
+            serde_json::to_string_pretty(&issues).map_err(|e| e.to_string())
+        }
+        Output::Csv => {
+            let date_format = date::pattern(&config.date_format);
+            let rows = issues.iter().map(|i| i.to_csv_row(&date_format));
+            Ok(std::iter::once(CSV_COLUMNS.join(","))
+                .chain(rows)
+                .collect::<Vec<String>>()
+                .join("\n"))
+        }
+        Output::JiraCsv => Ok(jira_csv(&issues)),
+    }
 }
 
-fn get_issues(
-    config: &Config,
-    token: &str,
-    assignee_id: Option<String>,
-    team: Option<Team>,
-    project: Option<Project>,
-) -> Result<Vec<Issue>, String> {
+/// Issues as CSV for Jira's importer. Issue Id is the row number, which Parent Id refers to,
+/// so sub-tasks whose parent isn't in the list become tasks. Labels take one column each.
+fn jira_csv(issues: &[Issue]) -> String {
+    let row_ids = issues
+        .iter()
+        .enumerate()
+        .map(|(index, issue)| (issue.id.as_str(), index + 1))
+        .collect::<HashMap<&str, usize>>();
+    let label_names = |issue: &Issue| -> Vec<String> {
+        issue
+            .labels
+            .as_ref()
+            .map(|labels| {
+                labels
+                    .nodes
+                    .iter()
+                    .map(|l| l.name.replace(' ', "_"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let label_columns = issues
+        .iter()
+        .map(|issue| label_names(issue).len())
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let header = [
+        "Issue Id",
+        "Parent Id",
+        "Summary",
+        "Description",
+        "Issue Type",
+        "Priority",
+    ]
+    .into_iter()
+    .chain(std::iter::repeat_n("Labels", label_columns))
+    .collect::<Vec<&str>>()
+    .join(",");
+    let rows = issues.iter().enumerate().map(|(index, issue)| {
+        let parent_id = issue
+            .parent
+            .as_ref()
+            .and_then(|parent| row_ids.get(parent.id.as_str()));
+        let issue_type = match parent_id {
+            Some(_) => "Sub-task",
+            None => "Task",
+        };
+        let mut labels = label_names(issue);
+        labels.resize(label_columns, String::new());
+
+        [
+            (index + 1).to_string(),
+            parent_id.map(|id| id.to_string()).unwrap_or_default(),
+            issue.title.clone(),
+            issue.description.clone().unwrap_or_default(),
+            issue_type.to_string(),
+            jira_priority(issue.priority).to_string(),
+        ]
+        .into_iter()
+        .chain(labels)
+        .map(|value| escape_csv(&value))
+        .collect::<Vec<String>>()
+        .join(",")
+    });
+
+    std::iter::once(header)
+        .chain(rows)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Jira's default priority names, left empty for No priority
+fn jira_priority(priority: Option<f32>) -> &'static str {
+    match priority.map(|p| priority::from_int(p as u8)) {
+        Some(Priority::Urgent) => "Highest",
+        Some(Priority::High) => "High",
+        Some(Priority::Normal) => "Medium",
+        Some(Priority::Low) => "Low",
+        Some(Priority::None) | None => "",
+    }
+}
+
+pub fn get_issues(config: &Config, token: &str, filter: Filter) -> Result<Vec<Issue>, String> {
+    let breaching = filter.breaching;
+    let mut gql_variables = HashMap::new();
+    if filter.archived {
+        gql_variables.insert(String::from("includeArchived"), json!(true));
+    }
+    gql_variables.insert("filter".to_string(), issue_filter(filter));
+
+    let response = request::Gql::new(config, token, ISSUE_LIST_DOC)
+        .put_variables(gql_variables)
+        .run()?;
+    let mut issues = issue_list_response(response)?;
+    if breaching {
+        issues.retain(|issue| issue.sla_breaches_at.is_some());
+    }
+    Ok(issues)
+}
+
+/// Build the IssueFilter GraphQL variable
+pub fn issue_filter(filter: Filter) -> Value {
+    let Filter {
+        assignee,
+        creator_id,
+        team,
+        project,
+        project_ids,
+        excluded_states,
+        breaching: _,
+        archived,
+    } = filter;
     let mut and_filters = Vec::new();
+    if archived {
+        and_filters.push(json!({"archivedAt": {"null": false}}));
+    }
     if let Some(Project { id, .. }) = project {
         and_filters.push(json!({"project": {"id": {"eq": id}}}));
     }
 
-    if let Some(assignee_id) = assignee_id {
-        and_filters.push(json!({"assignee": {"id": {"eq": assignee_id}}}));
+    if let Some(project_ids) = project_ids {
+        and_filters.push(json!({"project": {"id": {"in": project_ids}}}));
+    }
+
+    match assignee {
+        Assignee::Any => (),
+        Assignee::User(id) => and_filters.push(json!({"assignee": {"id": {"eq": id}}})),
+        Assignee::Unassigned => and_filters.push(json!({"assignee": {"null": true}})),
+    }
+
+    if let Some(creator_id) = creator_id {
+        and_filters.push(json!({"creator": {"id": {"eq": creator_id}}}));
     }
 
     if let Some(Team { id, .. }) = team {
         and_filters.push(json!({"team": {"id": {"eq": id}}}));
     }
 
-    and_filters.push(json!({"state": {"name": {"neq": "Done"}}}));
-    and_filters.push(json!({"state": {"name": {"neq": "Backlog"}}}));
-    and_filters.push(json!({"state": {"name": {"neq": "Triage"}}}));
-    and_filters.push(json!({"state": {"name": {"neq": "Canceled"}}}));
-    and_filters.push(json!({"state": {"name": {"neq": "Closed"}}}));
-    and_filters.push(json!({"state": {"name": {"neq": "Merged to Dev"}}}));
+    for state in excluded_states {
+        and_filters.push(json!({"state": {"name": {"neq": state}}}));
+    }
+
+    json!({ "and": and_filters })
+}
 
-    let filter = json!({ "and": and_filters });
-    let mut gql_variables = HashMap::new();
-    gql_variables.insert("filter".to_string(), filter);
+/// View an issue with its comment threads. comment_limit is the number of most recent
+/// threads to show, None shows all of them and Some(0) skips fetching comments.
+/// A --format string renders only the issue fields, without comments.
+pub fn view(
+    config: &Config,
+    token: &str,
+    target: Target,
+    comment_limit: Option<usize>,
+    history: bool,
+    format: Option<String>,
+) -> Result<String, String> {
+    let mut issue = find(config, token, target)?;
 
-    let response = request::Gql::new(config, token, ISSUE_LIST_DOC)
-        .put_variables(gql_variables)
+    let date_format = date::pattern(&config.date_format);
+    if let Some(format) = format {
+        return issue.to_format(&format, &date_format);
+    }
+    issue.load_extras(config, token, comment_limit, history)?;
+    Ok(issue.format(Format::View, &date_format))
+}
+
+/// Number of the most recent changes listed above the issue by issue view --watch
+const WATCH_CHANGES_SHOWN: usize = 5;
+
+/// What issue view --watch remembers between refreshes
+struct Snapshot {
+    state: String,
+    comment_ids: HashSet<String>,
+}
+
+impl Issue {
+    /// Fetch the comments and history that issue view shows below the description
+    fn load_extras(
+        &mut self,
+        config: &Config,
+        token: &str,
+        comment_limit: Option<usize>,
+        history: bool,
+    ) -> Result<(), String> {
+        if history {
+            self.history = Some(history::get(config, token, &self.id)?);
+        }
+        if comment_limit != Some(0) {
+            self.load_comments(config, token, comment_limit)?;
+        }
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            state: self.state.name.clone(),
+            comment_ids: self
+                .comments_oldest_first()
+                .into_iter()
+                .flat_map(|comment| comment.ids())
+                .collect(),
+        }
+    }
+
+    /// Flag comments posted since the previous snapshot and describe what changed
+    fn changes_since(&mut self, previous: &Snapshot) -> Vec<String> {
+        let mut changes = Vec::new();
+        if self.state.name != previous.state {
+            changes.push(format!(
+                "State changed from {} to {}",
+                previous.state, self.state.name
+            ));
+        }
+        let new_comments = match &mut self.comments {
+            Some(CommentsConnection { nodes, .. }) => nodes
+                .iter_mut()
+                .map(|comment| comment.mark_new(&previous.comment_ids))
+                .sum(),
+            None => 0,
+        };
+        match new_comments {
+            0 => (),
+            1 => changes.push(String::from("1 new comment")),
+            n => changes.push(format!("{n} new comments")),
+        }
+        changes
+    }
+}
+
+/// Redraw issue view every interval until interrupted, highlighting new comments and state changes
+pub fn watch(
+    config: &Config,
+    token: &str,
+    target: Target,
+    comment_limit: Option<usize>,
+    history: bool,
+    interval: Duration,
+) -> Result<String, String> {
+    let id = find(config, token, target)?.id;
+    let date_format = date::pattern(&config.date_format);
+    let mut previous: Option<Snapshot> = None;
+    let mut changes: Vec<String> = Vec::new();
+    // The last issue fetched, kept on screen while Linear can't be reached
+    let mut shown = String::new();
+    loop {
+        let fetched = get(config, token, id.clone()).and_then(|mut issue| {
+            issue.load_extras(config, token, comment_limit, history)?;
+            Ok(issue)
+        });
+        let now = chrono::Local::now().format("%H:%M:%S");
+        let mut header = format!(
+            "Refreshing every {}s, press Ctrl-C to stop",
+            interval.as_secs()
+        );
+        match fetched {
+            Ok(mut issue) => {
+                if let Some(previous) = &previous {
+                    changes.extend(
+                        issue
+                            .changes_since(previous)
+                            .into_iter()
+                            .map(|change| format!("{now} {change}")),
+                    );
+                }
+                previous = Some(issue.snapshot());
+                shown = issue.format(Format::View, &date_format);
+            }
+            // A network error shouldn't end the watch, the next refresh may work
+            Err(e) => {
+                let error = e.trim().lines().next().unwrap_or_default().to_string();
+                let error = color::red_string(&format!("{now} Could not refresh: {error}"));
+                header = format!("{header}\n{error}");
+            }
+        }
+
+        let changes = changes[changes.len().saturating_sub(WATCH_CHANGES_SHOWN)..]
+            .iter()
+            .map(|change| format!("{}\n", color::red_string(change)))
+            .collect::<String>();
+        let mut stdout = std::io::stdout();
+        execute!(
+            stdout,
+            terminal::Clear(ClearType::All),
+            cursor::MoveTo(0, 0)
+        )
+        .map_err(|e| e.to_string())?;
+        println!("{}\n{changes}\n{shown}", color::normal_string(&header));
+        std::thread::sleep(interval);
+    }
+}
+
+/// Fetch an issue by identifier, UUID, or Linear URL
+pub fn get(config: &Config, token: &str, id: String) -> Result<Issue, String> {
+    let response = request::Gql::new(config, token, ISSUE_ID_VIEW_DOC)
+        .put_string("id", url_identifier(&id).to_string())
         .run()?;
-    issue_list_response(response)
+    issue_id_view_response(response)
 }
 
-pub fn view(config: &Config, token: &str, branch: Option<String>) -> Result<String, String> {
-    if let Some(branch) = branch {
-        let response = request::Gql::new(config, token, ISSUE_BRANCH_VIEW_DOC)
-            .put_string("branchName", branch.clone())
-            .run()?;
-        let issue = issue_branch_view_response(response, &branch)?;
+/// The identifier in an issue URL such as https://linear.app/acme/issue/ENG-123/fix-login,
+/// anything else is returned as it is
+fn url_identifier(id: &str) -> &str {
+    match id.trim().split_once("/issue/") {
+        Some((_, rest)) => rest.split(['/', '?', '#']).next().unwrap_or_default(),
+        None => id.trim(),
+    }
+}
+
+/// How a command finds the issue it works on
+pub enum Target {
+    /// The issue linked to a git branch
+    Branch(String),
+    /// An identifier, UUID, or Linear URL given on the command line
+    Id(String),
+    /// Prompt to select from your issues
+    Select,
+}
+
+/// Fetch the issue a command works on, prompting for it with Target::Select
+pub fn find(config: &Config, token: &str, target: Target) -> Result<Issue, String> {
+    match target {
+        Target::Branch(branch) => viewed_branch_issue(config, token, branch),
+        Target::Id(id) => get(config, token, id),
+        // Refetched because the list doesn't have relations
+        Target::Select => get(config, token, select_issue(config, token)?.id),
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct IssueUnarchiveResponse {
+    data: Option<IssueUnarchiveData>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IssueUnarchiveData {
+    issue_unarchive: IssueUnarchive,
+}
+
+#[derive(Deserialize, Debug)]
+struct IssueUnarchive {
+    success: bool,
+    entity: Option<UnarchivedIssue>,
+}
+
+#[derive(Deserialize, Debug)]
+struct UnarchivedIssue {
+    identifier: String,
+    url: String,
+}
+
+/// Bring an archived issue back, so that it shows up in lists and searches again
+pub fn restore(config: &Config, token: &str, id: String) -> Result<String, String> {
+    let response: IssueUnarchiveResponse = request::Gql::new(config, token, ISSUE_UNARCHIVE_DOC)
+        .put_string("id", id.clone())
+        .execute()?;
+
+    match response.data.map(|data| data.issue_unarchive) {
+        Some(IssueUnarchive {
+            success: true,
+            entity: Some(UnarchivedIssue { identifier, url }),
+        }) => Ok(format!("Restored {identifier}\n{url}")),
+        _ => Err(format!("Could not restore {id}")),
+    }
+}
+
+/// Select from the archived issues assigned to the viewer
+pub fn select_archived_issue(config: &Config, token: &str) -> Result<Issue, String> {
+    let assignee_id = viewer::get_viewer(config, token)?.id;
+    let filter = Filter {
+        assignee: Assignee::User(assignee_id),
+        archived: true,
+        ..Default::default()
+    };
+    let issues = get_issues(config, token, filter)?;
+    if issues.is_empty() {
+        return Err(String::from("You have no archived issues"));
+    }
+    input::require("an issue identifier")?;
+    input::select("Select an issue to restore", issues)
+}
+
+/// Select from the issues assigned to the viewer
+pub fn select_issue(config: &Config, token: &str) -> Result<Issue, String> {
+    let assignee_id = viewer::get_viewer(config, token)?.id;
+    let filter = Filter {
+        assignee: Assignee::User(assignee_id),
+        excluded_states: config.excluded_states(None, None),
+        ..Default::default()
+    };
+    let mut issues = get_issues(config, token, filter)?;
+    issues.reverse();
+    input::require("an issue identifier")?;
+    input::select("Select an issue", issues)
+}
+
+/// The branch name for an issue, using branch_format from config when it is set
+pub fn branch_name(
+    config: &Config,
+    token: &str,
+    identifier: Option<String>,
+) -> Result<String, String> {
+    let issue = match identifier {
+        Some(identifier) => {
+            let response = request::Gql::new(config, token, ISSUE_ID_VIEW_DOC)
+                .put_string("id", identifier)
+                .run()?;
+            issue_id_view_response(response)?
+        }
+        None => select_issue(config, token)?,
+    };
+
+    match &config.branch_format {
+        None => Ok(issue.branch_name),
+        Some(format) => {
+            let variables = branch::Variables {
+                identifier: issue.identifier,
+                title: issue.title,
+                branch_name: issue.branch_name,
+            };
+            branch::render(format, &variables)
+        }
+    }
+}
+
+/// Write a single issue to a self-contained file, returns the path written to
+pub fn export(
+    config: &Config,
+    token: &str,
+    identifier: String,
+    format: ExportFormat,
+    include_comments: bool,
+    path: Option<String>,
+) -> Result<String, String> {
+    let response = request::Gql::new(config, token, ISSUE_ID_VIEW_DOC)
+        .put_string("id", identifier)
+        .run()?;
+    let mut issue = issue_id_view_response(response)?;
+    if include_comments {
+        issue.load_comments(config, token, None)?;
+    }
+    let date_format = date::pattern(&config.date_format);
+
+    let contents = match format {
+        ExportFormat::Md => issue.to_markdown(include_comments, &date_format),
+        ExportFormat::Html => issue.to_html(include_comments, &date_format),
+    };
+    let path = path.unwrap_or_else(|| format!("{}.{}", issue.identifier, format.extension()));
+
+    std::fs::write(&path, contents).map_err(|e| format!("Could not write to {path}: {e}"))?;
+    Ok(path)
+}
+
+/// Write every issue matching the filter to <identifier>.md in dir, with front matter for
+/// static site generators. Returns the paths written.
+pub fn export_dir(
+    config: &Config,
+    token: &str,
+    filter: Filter,
+    include_comments: bool,
+    dir: &str,
+) -> Result<Vec<String>, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Could not create {dir}: {e}"))?;
+    let date_format = date::pattern(&config.date_format);
+
+    get_issues(config, token, filter)?
+        .into_iter()
+        .map(|listed| {
+            // The list leaves out assignees, labels, and teams
+            let mut issue = get(config, token, listed.id)?;
+            if include_comments {
+                issue.load_comments(config, token, None)?;
+            }
+            let contents = format!(
+                "{}\n{}",
+                issue.front_matter(),
+                issue.to_markdown(include_comments, &date_format)
+            );
+            let path = std::path::Path::new(dir).join(format!("{}.md", issue.identifier));
+            let path = path.display().to_string();
+            std::fs::write(&path, contents)
+                .map_err(|e| format!("Could not write to {path}: {e}"))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Quote a YAML string, escaping backslashes and double quotes
+fn escape_yaml(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
 
-        Ok(issue.format(Format::View))
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Quote a CSV field when it contains a comma, quote, or line break
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
     } else {
-        let assignee_id = viewer::get_viewer(config, token)?.id;
-        let mut issues = get_issues(config, token, Some(assignee_id), None, None)?;
-        issues.reverse();
-        let issue = input::select("Select an issue", issues, None)?;
-        // Need to refetch to get comments
+        text.to_string()
+    }
+}
 
-        let response = request::Gql::new(config, token, ISSUE_ID_VIEW_DOC)
-            .put_string("id", issue.id)
-            .run()?;
+/// Fields that can be changed with issue edit
+pub enum Field {
+    Description,
+    Title,
+    State,
+    Priority,
+    Project,
+    Assignee,
+    Estimate,
+    Labels,
+    Save,
+}
+
+impl Display for Field {
+    /// This is for rendering in a select
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Field::Description => write!(f, "Description"),
+            Field::Title => write!(f, "Title"),
+            Field::State => write!(f, "State"),
+            Field::Priority => write!(f, "Priority"),
+            Field::Project => write!(f, "Project"),
+            Field::Assignee => write!(f, "Assignee"),
+            Field::Estimate => write!(f, "Estimate"),
+            Field::Labels => write!(f, "Labels"),
+            Field::Save => write!(f, "Save changes"),
+        }
+    }
+}
+
+pub fn all_fields() -> Vec<Field> {
+    vec![
+        Field::Description,
+        Field::Title,
+        Field::State,
+        Field::Priority,
+        Field::Project,
+        Field::Assignee,
+        Field::Estimate,
+        Field::Labels,
+        Field::Save,
+    ]
+}
+
+/// Changes to make in a single issueUpdate, fields that are None are left alone
+#[derive(Default)]
+pub struct Changes {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub state: Option<State>,
+    /// Moves the issue to another team, the state has to be one of the new team's
+    pub team: Option<Team>,
+    pub priority: Option<Priority>,
+    /// Some(None) removes the issue from its project
+    pub project: Option<Option<Project>>,
+    /// Some(None) removes the issue from its milestone, which has to be in the issue's project
+    pub milestone_id: Option<Option<String>>,
+    /// Some(None) unassigns the issue
+    pub assignee_id: Option<Option<String>>,
+    /// Some(None) removes the estimate
+    pub estimate: Option<Option<u8>>,
+    /// Labels to add, existing labels are kept
+    pub added_label_ids: Vec<String>,
+}
 
-        let issue = issue_id_view_response(response)?;
-        Ok(issue.format(Format::View))
+impl Changes {
+    /// The IssueUpdateInput for these changes
+    fn input(self) -> Value {
+        let mut input = serde_json::Map::new();
+        if let Some(title) = self.title {
+            input.insert(String::from("title"), json!(title));
+        }
+        if let Some(description) = self.description {
+            input.insert(String::from("description"), json!(description));
+        }
+        if let Some(state) = self.state {
+            input.insert(String::from("stateId"), json!(state.id));
+        }
+        if let Some(team) = self.team {
+            input.insert(String::from("teamId"), json!(team.id));
+        }
+        if let Some(priority) = self.priority {
+            let priority = priority::priority_to_int(&priority);
+            input.insert(String::from("priority"), json!(priority));
+        }
+        if let Some(project) = self.project {
+            input.insert(String::from("projectId"), json!(project.map(|p| p.id)));
+        }
+        if let Some(milestone_id) = self.milestone_id {
+            input.insert(String::from("projectMilestoneId"), json!(milestone_id));
+        }
+        if let Some(assignee_id) = self.assignee_id {
+            input.insert(String::from("assigneeId"), json!(assignee_id));
+        }
+        if let Some(estimate) = self.estimate {
+            input.insert(String::from("estimate"), json!(estimate));
+        }
+        if !self.added_label_ids.is_empty() {
+            input.insert(String::from("addedLabelIds"), json!(self.added_label_ids));
+        }
+        Value::Object(input)
     }
 }
 
-pub fn edit(config: &Config, token: &str, branch: String) -> Result<String, String> {
+/// Fetch the issue linked to a branch, for commands that change it
+pub fn branch_issue(config: &Config, token: &str, branch: String) -> Result<Issue, String> {
+    if branch.is_empty() {
+        return Err(String::from(
+            "Not on a branch (detached HEAD), pass the issue identifier instead",
+        ));
+    }
     let response = request::Gql::new(config, token, ISSUE_BRANCH_VIEW_DOC)
         .put_string("branchName", branch.clone())
         .run()?;
-    let issue = issue_branch_view_response(response, &branch)?;
+    issue_branch_view_response(response, &branch)
+}
+
+/// Like branch_issue, for commands that only read the issue. On a detached HEAD, i.e. during a
+/// rebase or in CI, falls back to the most recent issue identifier in the commit messages
+pub fn viewed_branch_issue(config: &Config, token: &str, branch: String) -> Result<Issue, String> {
+    if !branch.is_empty() {
+        return branch_issue(config, token, branch);
+    }
+    let messages = git::recent_commit_messages().unwrap_or_default();
+    let issue = commit_identifiers(&messages)
+        .into_iter()
+        .take(COMMIT_IDENTIFIER_ATTEMPTS)
+        .find_map(|identifier| get(config, token, identifier).ok())
+        .ok_or_else(|| {
+            String::from(
+                "Not on a branch (detached HEAD) and no issue found in recent commit messages",
+            )
+        })?;
+    // On stderr so that it is seen even when the output is piped
+    eprintln!(
+        "Detached HEAD, using {} from the commit messages",
+        issue.identifier
+    );
+    Ok(issue)
+}
+
+/// Issue identifiers such as ENG-123 in commit messages, most recent first and without duplicates
+pub fn commit_identifiers(messages: &str) -> Vec<String> {
+    let pattern = Regex::new(r"\b[A-Z][A-Z0-9]*-\d+\b").unwrap();
+    let mut identifiers: Vec<String> = Vec::new();
+    for identifier in pattern.find_iter(messages).map(|m| m.as_str().to_string()) {
+        if !identifiers.contains(&identifier) {
+            identifiers.push(identifier);
+        }
+    }
+    identifiers
+}
+
+/// Open the description of an issue in the editor
+pub fn edit_description(config: &Config, issue: &Issue) -> Result<String, String> {
     // Stops wierd spinner output from rolling into the input text
     println!();
     let description = input::editor(
         "Enter updated description",
-        &issue.description.unwrap_or_default(),
-        config.mock_string.clone(),
+        &issue.description.clone().unwrap_or_default(),
     )?;
-    let mut input = HashMap::new();
-    input.insert("description".to_string(), description);
+    processor::description(config, description)
+}
 
+/// Apply changes to an issue in a single issueUpdate
+pub fn update(
+    config: &Config,
+    token: &str,
+    id: String,
+    changes: Changes,
+) -> Result<String, String> {
     let mut gql_variables = HashMap::new();
-    gql_variables.insert("id".to_string(), Value::String(issue.id));
-    gql_variables.insert("input".to_string(), json!(input));
+    gql_variables.insert("id".to_string(), Value::String(id));
+    gql_variables.insert("input".to_string(), changes.input());
     let response = request::Gql::new(config, token, ISSUE_UPDATE_DOC)
         .put_variables(gql_variables)
         .run()?;
@@ -552,30 +2115,152 @@ pub fn edit(config: &Config, token: &str, branch: String) -> Result<String, Stri
     Ok(issue.url)
 }
 
-/// Get the id from an issue response, needed for parent issues and terminal output
-fn issue_create_response(response: String) -> Result<Issue, String> {
-    let data: Result<IssueCreateResponse, _> = serde_json::from_str(&response);
-
-    match data {
-        Ok(IssueCreateResponse {
-            data:
-                Some(IssueCreateData {
-                    issue_create: IssueCreate { issue },
-                }),
-        }) => Ok(issue),
-        err => Err(format!(
-            "Could not parse response for issue:
-            ---
-            {err:?}
-            ---
-            {response:?}"
-        )),
-    }
+/// Set the estimate of the issue linked to a branch
+pub fn estimate(
+    config: &Config,
+    token: &str,
+    branch: String,
+    estimate: u8,
+) -> Result<String, String> {
+    let issue = update_branch_issue(config, token, branch, json!({ "estimate": estimate }))?;
+    Ok(issue.url)
 }
 
-fn issue_branch_view_response(response: String, branch: &String) -> Result<Issue, String> {
-    let data: Result<IssueBranchViewResponse, _> = serde_json::from_str(&response);
-    match data {
+/// Set or clear the due date of the issue linked to a branch
+pub fn due(
+    config: &Config,
+    token: &str,
+    branch: String,
+    due_date: Option<NaiveDate>,
+) -> Result<String, String> {
+    let due_date = due_date.as_ref().map(date::to_string);
+    let issue = update_branch_issue(config, token, branch, json!({ "dueDate": due_date }))?;
+    Ok(issue.url)
+}
+
+/// Run an issueUpdate with the given input on the issue linked to a branch
+fn update_branch_issue(
+    config: &Config,
+    token: &str,
+    branch: String,
+    input: Value,
+) -> Result<Issue, String> {
+    let issue = branch_issue(config, token, branch)?;
+
+    let mut gql_variables = HashMap::new();
+    gql_variables.insert("id".to_string(), Value::String(issue.id));
+    gql_variables.insert("input".to_string(), input);
+    let response = request::Gql::new(config, token, ISSUE_UPDATE_DOC)
+        .put_variables(gql_variables)
+        .run()?;
+    issue_update_response(response)
+}
+
+/// Get the identifier of the issue linked to a branch
+pub fn branch_identifier(config: &Config, token: &str, branch: String) -> Result<String, String> {
+    let issue = branch_issue(config, token, branch)?;
+    Ok(issue.identifier)
+}
+
+/// Post the same comment to several issues concurrently and summarize the results
+pub fn comment(
+    config: &Config,
+    token: &str,
+    identifiers: Vec<String>,
+    body: String,
+) -> Result<String, String> {
+    if identifiers.is_empty() {
+        return Err(String::from("No issues to comment on"));
+    }
+    // Spinners from several threads at once garble the terminal
+    let config = Config {
+        spinners: Some(false),
+        ..config.clone()
+    };
+
+    // Workers take the next issue until there are none left, so that a long list doesn't open
+    // a connection per issue at once
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(
+        identifiers
+            .iter()
+            .map(|_| Err(String::from("Comment thread panicked")))
+            .collect::<Vec<Result<String, String>>>(),
+    );
+    std::thread::scope(|scope| {
+        for _ in 0..identifiers.len().min(COMMENT_WORKERS) {
+            scope.spawn(|| {
+                let mut index = next.fetch_add(1, Ordering::Relaxed);
+                while let Some(identifier) = identifiers.get(index) {
+                    let result = post_comment(&config, token, identifier.clone(), body.clone());
+                    if let Ok(mut results) = results.lock() {
+                        results[index] = result;
+                    }
+                    index = next.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    let results = results.into_inner().map_err(|e| e.to_string())?;
+
+    let total = results.len();
+    let succeeded = results.iter().filter(|r| r.is_ok()).count();
+    let lines = identifiers
+        .iter()
+        .zip(results)
+        .map(|(identifier, result)| match result {
+            Ok(url) => format!("- {identifier} {} {url}", color::green_string("✓")),
+            Err(err) => format!("- {identifier} {} {err}", color::red_string("✗")),
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    let summary = format!("{lines}\n\n{succeeded} of {total} comments posted");
+
+    if succeeded == total {
+        Ok(summary)
+    } else {
+        Err(summary)
+    }
+}
+
+/// Post a comment on a single issue, returning the url of the comment
+pub fn post_comment(
+    config: &Config,
+    token: &str,
+    issue_id: String,
+    body: String,
+) -> Result<String, String> {
+    let response = request::Gql::new(config, token, COMMENT_CREATE_DOC)
+        .put_string("issueId", issue_id)
+        .put_string("body", body)
+        .run()?;
+    comment_create_response(response)
+}
+
+/// Get the id from an issue response, needed for parent issues and terminal output
+fn issue_create_response(response: String) -> Result<Issue, String> {
+    let data: Result<IssueCreateResponse, _> = schema::parse(&response, "issue");
+
+    match data {
+        Ok(IssueCreateResponse {
+            data:
+                Some(IssueCreateData {
+                    issue_create: IssueCreate { issue },
+                }),
+        }) => Ok(issue),
+        err => Err(format!(
+            "Could not parse response for issue:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+fn issue_branch_view_response(response: String, branch: &String) -> Result<Issue, String> {
+    let data: Result<IssueBranchViewResponse, _> = schema::parse(&response, "issue");
+    match data {
         Ok(IssueBranchViewResponse {
             data:
                 Some(IssueBranchViewData {
@@ -599,7 +2284,7 @@ fn issue_branch_view_response(response: String, branch: &String) -> Result<Issue
 }
 
 fn issue_id_view_response(response: String) -> Result<Issue, String> {
-    let data: Result<IssueIdViewResponse, _> = serde_json::from_str(&response);
+    let data: Result<IssueIdViewResponse, _> = schema::parse(&response, "issue");
 
     match data {
         Ok(IssueIdViewResponse {
@@ -619,7 +2304,7 @@ fn issue_id_view_response(response: String) -> Result<Issue, String> {
 }
 
 fn issue_list_response(response: String) -> Result<Vec<Issue>, String> {
-    let data: Result<IssueListResponse, _> = serde_json::from_str(&response);
+    let data: Result<IssueListResponse, _> = schema::parse(&response, "issue");
 
     match data {
         Ok(IssueListResponse {
@@ -642,8 +2327,52 @@ fn issue_list_response(response: String) -> Result<Vec<Issue>, String> {
     }
 }
 
+fn issue_comments_response(response: String) -> Result<CommentsConnection, String> {
+    let data: Result<IssueCommentsResponse, _> = schema::parse(&response, "comments");
+
+    match data {
+        Ok(IssueCommentsResponse {
+            data:
+                Some(IssueCommentsData {
+                    issue: Some(IssueComments { comments }),
+                }),
+        }) => Ok(comments),
+        err => Err(format!(
+            "Could not parse response for comments:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+fn comment_create_response(response: String) -> Result<String, String> {
+    let data: Result<CommentCreateResponse, _> = schema::parse(&response, "issue");
+
+    match data {
+        Ok(CommentCreateResponse {
+            data:
+                Some(CommentCreateData {
+                    comment_create:
+                        CommentCreate {
+                            success: true,
+                            comment: Some(CreatedComment { url }),
+                        },
+                }),
+        }) => Ok(url),
+        err => Err(format!(
+            "Could not parse response for comment:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
 fn issue_update_response(response: String) -> Result<Issue, String> {
-    let data: Result<IssueUpdateResponse, _> = serde_json::from_str(&response);
+    let data: Result<IssueUpdateResponse, _> = schema::parse(&response, "issue");
 
     match data {
         Ok(IssueUpdateResponse {
@@ -672,6 +2401,9 @@ mod tests {
         let mut server = mockito::Server::new();
         let mock = server
             .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"cycleId\":\"c12\".*\"labelIds\":\\[\"label-team\"\\],\"parentId\":\"BE-3000\"",
+            )))
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(test::responses::issue_create())
@@ -684,7 +2416,7 @@ mod tests {
         let team = test::fixtures::team();
         let state = test::fixtures::state();
         let project = None;
-        let assignee_id = "456".to_string();
+        let assignee_id = Some("456".to_string());
 
         let result = create(
             &config,
@@ -696,7 +2428,13 @@ mod tests {
             state,
             assignee_id,
             Priority::None,
-        );
+            Some(3),
+            NaiveDate::from_ymd_opt(2025, 10, 1),
+            Some(String::from("BE-3000")),
+            vec![String::from("label-team")],
+            Some(String::from("c12")),
+        )
+        .map(|issue| issue.created());
         assert_eq!(
             result,
             Ok("https://linear.app/vardy/issue/BE-3354/test\nbe-3354-test".to_string())
@@ -704,6 +2442,491 @@ mod tests {
         mock.assert();
     }
 
+    #[test]
+    fn test_comment() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::comment_create())
+            .expect(2)
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let identifiers = vec![String::from("BE-1"), String::from("BE-2")];
+        let result = comment(&config, "1234", identifiers, String::from("Deployed"));
+        assert_eq!(
+            result,
+            Ok("- BE-1 ✓ https://linear.app/vardy/issue/BE-3354/test#comment-1\n- BE-2 ✓ https://linear.app/vardy/issue/BE-3354/test#comment-1\n\n2 of 2 comments posted".to_string())
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn comment_should_keep_order_with_more_issues_than_workers() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::comment_create())
+            .expect(COMMENT_WORKERS * 2 + 1)
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let identifiers = (1..=COMMENT_WORKERS * 2 + 1)
+            .map(|n| format!("BE-{n}"))
+            .collect::<Vec<String>>();
+        let result = comment(
+            &config,
+            "1234",
+            identifiers.clone(),
+            String::from("Deployed"),
+        )
+        .unwrap();
+        let posted = result
+            .lines()
+            .filter_map(|line| line.strip_prefix("- "))
+            .filter_map(|line| line.split(' ').next())
+            .collect::<Vec<&str>>();
+        assert_eq!(posted, identifiers);
+        mock.assert();
+    }
+
+    /// Mocks for the two pages of test::responses::issue_comments_page_1 and _2
+    fn mock_comments(
+        server: &mut mockito::ServerGuard,
+        expect: usize,
+    ) -> (mockito::Mock, mockito::Mock) {
+        let first_page = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"variables\":\\{\"id\":\"[^\"]*\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_comments_page_1())
+            .expect(expect)
+            .create();
+        let second_page = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("\"after\":\"page2\"")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_comments_page_2())
+            .expect(expect)
+            .create();
+        (first_page, second_page)
+    }
+
+    #[test]
+    fn changes_since_should_flag_new_comments_and_state_changes() {
+        let comment = |id: &str, replies: Vec<Comment>| Comment {
+            id: id.to_string(),
+            replies,
+            ..Default::default()
+        };
+        let issue = |state: &str, comments: Vec<Comment>| Issue {
+            state: State {
+                name: state.to_string(),
+                ..Default::default()
+            },
+            comments: Some(CommentsConnection {
+                nodes: comments,
+                page_info: None,
+            }),
+            ..Default::default()
+        };
+
+        let previous = issue("Todo", vec![comment("c1", vec![])]).snapshot();
+        let mut latest = issue("Todo", vec![comment("c1", vec![])]);
+        assert_eq!(latest.changes_since(&previous), Vec::<String>::new());
+
+        let mut latest = issue(
+            "In Progress",
+            vec![
+                comment("c1", vec![comment("c2", vec![])]),
+                comment("c3", vec![]),
+            ],
+        );
+        assert_eq!(
+            latest.changes_since(&previous),
+            vec!["State changed from Todo to In Progress", "2 new comments"]
+        );
+        let flags = latest
+            .comments_oldest_first()
+            .iter()
+            .map(|c| {
+                (
+                    c.new,
+                    c.replies.iter().map(|r| r.new).collect::<Vec<bool>>(),
+                )
+            })
+            .collect::<Vec<(bool, Vec<bool>)>>();
+        assert_eq!(flags, vec![(false, vec![true]), (true, vec![])]);
+        assert!(latest.format(Format::View, "%Y-%m-%d").contains(" NEW"));
+    }
+
+    #[test]
+    fn test_url_identifier() {
+        assert_eq!(
+            url_identifier("https://linear.app/acme/issue/ENG-123/fix-the-login"),
+            "ENG-123"
+        );
+        assert_eq!(
+            url_identifier("https://linear.app/acme/issue/ENG-123#comment-1a2b"),
+            "ENG-123"
+        );
+        assert_eq!(url_identifier(" ENG-123 "), "ENG-123");
+        assert_eq!(
+            url_identifier("cbe16d8a-9999-9999-9999-9f2e79c3cb7e"),
+            "cbe16d8a-9999-9999-9999-9f2e79c3cb7e"
+        );
+    }
+
+    #[test]
+    fn view_should_fetch_by_url() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"variables\":\\{\"id\":\"BE-3354\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_view())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let target = Target::Id(String::from("https://linear.app/vardy/issue/BE-3354/test"));
+        assert_eq!(
+            view(
+                &config,
+                "1234",
+                target,
+                Some(0),
+                false,
+                Some(String::from("{identifier} {state}"))
+            ),
+            Ok(String::from("BE-3354 Todo"))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_view() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "issueVcsBranchSearch",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_branch_view())
+            .expect(3)
+            .create();
+        let (first_page, second_page) = mock_comments(&mut server, 2);
+        let config = test::fixtures::config().mock_url(server.url());
+        let branch = || Target::Branch(String::from("be-3354-test"));
+
+        let result = view(&config, "1234", branch(), None, false, None).unwrap();
+        assert!(result.ends_with(
+            "A description\n\n--- COMMENTS ---\n\nFirst\n\n- Bruce 2024-05-01 00:00\n\n----------------\n\n    A reply\n\n    - Alfred 2024-05-02 00:00\n\n    ----------------\n\n        A nested reply\n\n        - Bruce 2024-05-02 12:00\n\n        ----------------\n\nSecond\n\n- Bruce 2024-05-03 00:00\n\n----------------"
+        ));
+
+        let result = view(&config, "1234", branch(), Some(1), false, None).unwrap();
+        assert!(result.ends_with(
+            "--- COMMENTS ---\n\nSecond\n\n- Bruce 2024-05-03 00:00\n\n----------------"
+        ));
+
+        let result = view(&config, "1234", branch(), Some(0), false, None).unwrap();
+        assert!(result.ends_with("A description"));
+        assert!(result.contains(
+            "Assignee: bruce\nPriority: High\nProject: Lair\nLabels: bug, cli-created\nCreated: 2024-05-01 00:00\nhttps://linear.app/vardy/issue/BE-3354/test\n"
+        ));
+        mock.assert();
+        first_page.assert();
+        second_page.assert();
+    }
+
+    #[test]
+    fn test_export_dir() {
+        let mut server = mockito::Server::new();
+        let list_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("\"filter\"")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_list())
+            .create();
+        let view_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"id\":\"438bced3-9999-9999-9999-a51423f24fc6\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                test::responses::issue_branch_view().replace("issueVcsBranchSearch", "issue"),
+            )
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let dir = std::env::temp_dir().join(format!("lnr-export-{}", uuid::Uuid::new_v4()));
+        let dir = dir.to_str().unwrap().to_string();
+
+        let result = export_dir(&config, "1234", Filter::default(), false, &dir);
+        let path = format!("{dir}/BE-3354.md");
+        assert_eq!(result, Ok(vec![path.clone()]));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "---\nidentifier: \"BE-3354\"\ntitle: \"Test\"\nstate: \"Todo\"\nproject: \"Lair\"\nassignee: \"bruce\"\npriority: \"High\"\nlabels: [\"bug\", \"cli-created\"]\ncreated_at: \"2024-05-01T00:00:00.000Z\"\nurl: \"https://linear.app/vardy/issue/BE-3354/test\"\n---\n\n# BE-3354 Test\n\n- State: Todo\n- URL: https://linear.app/vardy/issue/BE-3354/test\n\nA description\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+        list_mock.assert();
+        view_mock.assert();
+    }
+
+    #[test]
+    fn escape_yaml_should_quote() {
+        assert_eq!(escape_yaml("Say \"hi\"\\"), "\"Say \\\"hi\\\"\\\\\"");
+    }
+
+    #[test]
+    fn test_export() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("branchName")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_view())
+            .expect(2)
+            .create();
+        let (first_page, second_page) = mock_comments(&mut server, 1);
+        let config = test::fixtures::config().mock_url(server.url());
+        let name = format!("lnr-export-{}", uuid::Uuid::new_v4());
+        let directory = std::env::temp_dir();
+
+        let path = directory.join(format!("{name}.md"));
+        let path = path.to_str().unwrap().to_string();
+        let result = export(
+            &config,
+            "1234",
+            String::from("BE-3354"),
+            ExportFormat::Md,
+            true,
+            Some(path.clone()),
+        );
+        assert_eq!(result, Ok(path.clone()));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# BE-3354 Test <issue>\n\n- State: Todo\n- URL: https://linear.app/vardy/issue/BE-3354/test\n\nA description\n\n## Comments\n\n### Bruce 2024-05-01 00:00\n\nFirst\n\n#### Alfred 2024-05-02 00:00\n\nA reply\n\n##### Bruce 2024-05-02 12:00\n\nA nested reply\n\n### Bruce 2024-05-03 00:00\n\nSecond\n"
+        );
+
+        std::fs::remove_file(&path).unwrap();
+
+        let path = directory.join(format!("{name}.html"));
+        let path = path.to_str().unwrap().to_string();
+        let result = export(
+            &config,
+            "1234",
+            String::from("BE-3354"),
+            ExportFormat::Html,
+            false,
+            Some(path.clone()),
+        );
+        assert_eq!(result, Ok(path.clone()));
+        let html = std::fs::read_to_string(&path).unwrap();
+        assert!(html.contains("<h1>BE-3354 Test &lt;issue&gt;</h1>"));
+        assert!(!html.contains("Comments"));
+        std::fs::remove_file(&path).unwrap();
+        mock.assert();
+        first_page.assert();
+        second_page.assert();
+    }
+
+    #[test]
+    fn test_branch_name() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_view())
+            .expect(2)
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let identifier = Some(String::from("BE-3354"));
+
+        let result = branch_name(&config, "1234", identifier.clone());
+        assert_eq!(result, Ok(String::from("be-3354-test")));
+
+        let config = Config {
+            branch_format: Some(String::from("{{team_key|lower}}/{{number}}-{{slug}}")),
+            ..config
+        };
+        let result = branch_name(&config, "1234", identifier);
+        assert_eq!(result, Ok(String::from("be/3354-test-issue")));
+        mock.assert();
+    }
+
+    #[test]
+    fn test_estimate() {
+        let mut server = mockito::Server::new();
+        let view_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "issueVcsBranchSearch",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_branch_view())
+            .create();
+        let update_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("issueUpdate")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_update())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = estimate(&config, "1234", String::from("be-3354-test"), 3);
+        assert_eq!(
+            result,
+            Ok(String::from("https://linear.app/vardy/issue/BE-3354/test"))
+        );
+        view_mock.assert();
+        update_mock.assert();
+    }
+
+    #[test]
+    fn test_due() {
+        let mut server = mockito::Server::new();
+        let view_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "issueVcsBranchSearch",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_branch_view())
+            .expect(2)
+            .create();
+        let update_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "dueDate\":\"2025-10-01",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_update())
+            .create();
+        let clear_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("dueDate\":null")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_update())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let branch = String::from("be-3354-test");
+
+        let result = due(
+            &config,
+            "1234",
+            branch.clone(),
+            NaiveDate::from_ymd_opt(2025, 10, 1),
+        );
+        assert_eq!(
+            result,
+            Ok(String::from("https://linear.app/vardy/issue/BE-3354/test"))
+        );
+        let result = due(&config, "1234", branch, None);
+        assert_eq!(
+            result,
+            Ok(String::from("https://linear.app/vardy/issue/BE-3354/test"))
+        );
+        view_mock.assert();
+        update_mock.assert();
+        clear_mock.assert();
+    }
+
+    #[test]
+    fn test_commit_identifiers() {
+        let messages =
+            "Fix login for UTF-8 names\n\nCloses ENG-123\n\nENG-120: Add login\nPart of ENG-123";
+        assert_eq!(
+            commit_identifiers(messages),
+            vec![
+                String::from("UTF-8"),
+                String::from("ENG-123"),
+                String::from("ENG-120")
+            ]
+        );
+        assert_eq!(commit_identifiers("lowercase eng-1"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_changes_input() {
+        assert_eq!(Changes::default().input(), json!({}));
+
+        let changes = Changes {
+            title: Some(String::from("New title")),
+            state: Some(test::fixtures::state()),
+            team: Some(test::fixtures::team()),
+            priority: Some(Priority::High),
+            project: Some(None),
+            milestone_id: Some(None),
+            assignee_id: Some(Some(String::from("456"))),
+            estimate: Some(Some(3)),
+            added_label_ids: vec![String::from("label-team")],
+            ..Default::default()
+        };
+        assert_eq!(
+            changes.input(),
+            json!({
+                "title": "New title",
+                "stateId": "123456",
+                "teamId": "123456",
+                "priority": 2,
+                "projectId": null,
+                "projectMilestoneId": null,
+                "assigneeId": "456",
+                "estimate": 3,
+                "addedLabelIds": ["label-team"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"input\":\\{\"title\":\"New title\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_update())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let changes = Changes {
+            title: Some(String::from("New title")),
+            ..Default::default()
+        };
+
+        let result = update(&config, "1234", String::from("1"), changes);
+        assert_eq!(
+            result,
+            Ok(String::from("https://linear.app/vardy/issue/BE-3354/test"))
+        );
+        mock.assert();
+    }
+
     #[test]
     fn test_list() {
         let mut server = mockito::Server::new();
@@ -716,15 +2939,372 @@ mod tests {
         let config = test::fixtures::config().mock_url(server.url());
 
         let token = "1234";
-        let team_id = None;
-        let project_id = None;
-        let assignee_id = None;
+        let filter = Filter::default();
+
+        let result = list(&config, token, filter, None, false, Output::Text, None);
+        assert_eq!(
+            result,
+            Ok("\nIssues\n\n-   SHO-2148 | Modify schema\n             | Todo | 2 customer requests\n".to_string())
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_list_format() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_list())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = list(
+            &config,
+            "1234",
+            Filter::default(),
+            None,
+            false,
+            Output::Text,
+            Some(String::from("{identifier}\\t{state}\\t{title}")),
+        );
+        assert_eq!(result, Ok(String::from("SHO-2148\tTodo\tModify schema")));
+        mock.assert();
+    }
+
+    #[test]
+    fn test_list_csv() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_list_unsorted())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = list(
+            &config,
+            "1234",
+            Filter::default(),
+            Some(Sort::Identifier),
+            false,
+            Output::Csv,
+            None,
+        )
+        .unwrap();
+        let lines = result.lines().collect::<Vec<&str>>();
+        assert_eq!(
+            lines[0],
+            "identifier,title,state,priority,estimate,project,url,due_date,created_at,updated_at"
+        );
+        assert_eq!(
+            lines[1],
+            "SHO-9,Nine,In Progress,3,,\"Batcave, Phase 2\",https://linear.app/vardy/issue/SHO-9/nine,,2024-05-01 00:00,2024-05-08 00:00"
+        );
+        assert_eq!(lines.len(), 4);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_list_jira_csv() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_list_unsorted())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = list(
+            &config,
+            "1234",
+            Filter::default(),
+            None,
+            false,
+            Output::JiraCsv,
+            None,
+        );
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "Issue Id,Parent Id,Summary,Description,Issue Type,Priority,Labels,Labels\n\
+                 1,,Nine,,Task,Medium,,\n\
+                 2,3,Eleven,,Sub-task,,bug,needs_review\n\
+                 3,,Ten,,Task,Highest,,"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn escape_csv_should_quote_special_characters() {
+        assert_eq!(escape_csv("Modify schema"), "Modify schema");
+        assert_eq!(
+            escape_csv("Fix \"login\", again"),
+            "\"Fix \"\"login\"\", again\""
+        );
+        assert_eq!(escape_csv("one\ntwo"), "\"one\ntwo\"");
+    }
+
+    #[test]
+    fn test_list_json() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_list())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = list(
+            &config,
+            "1234",
+            Filter::default(),
+            None,
+            false,
+            Output::Json,
+            None,
+        );
+        let issues: Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(issues[0]["id"], "438bced3-9999-9999-9999-a51423f24fc6");
+        assert_eq!(issues[0]["identifier"], "SHO-2148");
+        assert_eq!(issues[0]["state"], "Todo");
+        assert_eq!(issues[0]["estimate"], Value::Null);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_excluded_states() {
+        let configured = vec![
+            String::from("Done"),
+            String::from("Backlog"),
+            String::from("Triage"),
+        ];
+        assert_eq!(
+            excluded_states(configured.clone(), true, &[]),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            excluded_states(configured, false, &[String::from("Backlog")]),
+            vec!["Done", "Triage"]
+        );
+    }
 
-        let result = list(&config, token, assignee_id, team_id, project_id);
+    #[test]
+    fn test_issue_filter() {
+        let filter = Filter {
+            assignee: Assignee::User(String::from("456")),
+            project_ids: Some(vec![String::from("p1"), String::from("p2")]),
+            excluded_states: vec![String::from("Done")],
+            ..Default::default()
+        };
+        assert_eq!(
+            issue_filter(filter),
+            json!({"and": [
+                {"project": {"id": {"in": ["p1", "p2"]}}},
+                {"assignee": {"id": {"eq": "456"}}},
+                {"state": {"name": {"neq": "Done"}}}
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_duplicate() {
+        let mut server = mockito::Server::new();
+        let labels_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"names\":\\[\"cli-created\",\"cats\",\"missing\"\\]",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_labels())
+            .create();
+        let create_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"description\":\"Steps\",\"estimate\":3,\"labelIds\":\\[\"label-team\",\"label-cats\"\\],\"priority\":2,\"stateId\":\"123456\",\"teamId\":\"123456\",\"title\":\"Batmobile stalls\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_create())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let original: Issue = serde_json::from_value(json!({
+            "id": "original",
+            "identifier": "OPS-1",
+            "title": "Batmobile stalls",
+            "description": "Steps",
+            "url": "https://linear.app/vardy/issue/OPS-1",
+            "branchName": "ops-1-batmobile-stalls",
+            "state": {"id": "s1"},
+            "team": {"id": "654321", "name": "Ops"},
+            "project": {"id": "p-ops", "name": "Garage"},
+            "labels": {"nodes": [
+                {"id": "label-cli", "name": "cli-created"},
+                {"id": "label-cats", "name": "cats"},
+                {"id": "label-missing", "name": "missing"}
+            ]},
+            "priority": 2.0,
+            "estimate": 3.0,
+        }))
+        .unwrap();
+
+        let copy = duplicate(
+            &config,
+            "1234",
+            &original,
+            test::fixtures::team(),
+            test::fixtures::state(),
+        )
+        .unwrap();
+        assert_eq!(copy.identifier, "BE-3354");
+        labels_mock.assert();
+        create_mock.assert();
+    }
+
+    #[test]
+    fn test_list_archived() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"filter\":\\{\"and\":\\[\\{\"archivedAt\":\\{\"null\":false\\}\\}\\]\\},\"includeArchived\":true",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_list_archived())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let filter = Filter {
+            archived: true,
+            ..Default::default()
+        };
+
+        let result = list(&config, "1234", filter, None, false, Output::Text, None);
         assert_eq!(
             result,
-            Ok("\nIssues\n\n-   SHO-2148 | Modify schema\n             | Todo\n".to_string())
+            Ok("\nIssues\n\n-   SHO-2148 | Modify schema\n             | Done | Archived 2025-09-30 08:15\n".to_string())
         );
         mock.assert();
     }
+
+    #[test]
+    fn test_restore() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "issueUnarchive.*\"id\":\"SHO-2148\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_unarchive())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            restore(&config, "1234", String::from("SHO-2148")),
+            Ok(String::from(
+                "Restored SHO-2148\nhttps://linear.app/vardy/issue/SHO-2148/modify-schema"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn issue_filter_should_find_unassigned_and_creator() {
+        let filter = Filter {
+            assignee: Assignee::Unassigned,
+            creator_id: Some(String::from("456")),
+            ..Default::default()
+        };
+        assert_eq!(
+            issue_filter(filter),
+            json!({"and": [
+                {"assignee": {"null": true}},
+                {"creator": {"id": {"eq": "456"}}}
+            ]})
+        );
+        assert_eq!(issue_filter(Filter::default()), json!({"and": []}));
+    }
+
+    #[test]
+    fn test_list_sorted() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_list_unsorted())
+            .expect(5)
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let identifiers = |sort: Sort, reverse: bool| {
+            let breaching = sort == Sort::Breach;
+            list(
+                &config,
+                "1234",
+                Filter {
+                    breaching,
+                    ..Filter::default()
+                },
+                Some(sort),
+                reverse,
+                Output::Text,
+                None,
+            )
+            .unwrap()
+            .lines()
+            .filter(|l| l.starts_with("- "))
+            .map(|l| l.split_whitespace().nth(1).unwrap().to_string())
+            .collect::<Vec<String>>()
+        };
+
+        assert_eq!(
+            identifiers(Sort::Identifier, false),
+            vec!["SHO-9", "SHO-10", "SHO-11"]
+        );
+        assert_eq!(
+            identifiers(Sort::Priority, false),
+            vec!["SHO-10", "SHO-9", "SHO-11"]
+        );
+        assert_eq!(
+            identifiers(Sort::Updated, false),
+            vec!["SHO-11", "SHO-9", "SHO-10"]
+        );
+        assert_eq!(
+            identifiers(Sort::Created, true),
+            vec!["SHO-9", "SHO-11", "SHO-10"]
+        );
+        assert_eq!(identifiers(Sort::Breach, false), vec!["SHO-10", "SHO-11"]);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_sla_countdown() {
+        let now = DateTime::parse_from_rfc3339("2025-10-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let countdown = |breaches_at: &str| sla_countdown(breaches_at, now);
+
+        assert_eq!(
+            countdown("2025-10-03T16:30:00Z"),
+            Some(String::from("SLA 2d 4h left"))
+        );
+        assert_eq!(
+            countdown("2025-10-01T15:20:00Z"),
+            Some(String::from("SLA 3h 20m left"))
+        );
+        assert_eq!(
+            countdown("2025-10-01T10:00:00Z"),
+            Some(String::from("SLA breached 2h ago"))
+        );
+        assert_eq!(countdown("soon"), None);
+    }
 }
@@ -0,0 +1,493 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::{config::Config, input, request, schema, team::Team};
+
+const LABELS_DOC: &str = "query (
+                    $names: [String!]
+                ) {
+                issueLabels(
+                    filter: { name: { in: $names } }
+                ) {
+                    nodes {
+                        id
+                        name
+                        team {
+                            id
+                        }
+                    }
+                }
+                }
+                ";
+
+const TEAM_LABELS_DOC: &str = "query (
+                    $teamId: ID!
+                ) {
+                issueLabels(
+                    first: 250
+                    filter: { or: [{ team: { id: { eq: $teamId } } }, { team: { null: true } }] }
+                ) {
+                    nodes {
+                        id
+                        name
+                        team {
+                            id
+                        }
+                    }
+                }
+                }
+                ";
+
+const ISSUE_LABEL_CREATE_DOC: &str = "mutation (
+                    $input: IssueLabelCreateInput!
+                ) {
+                issueLabelCreate(
+                    input: $input
+                ) {
+                    success
+                    issueLabel {
+                        id
+                        name
+                    }
+                }
+                }
+                ";
+
+const ISSUE_LABEL_UPDATE_DOC: &str = "mutation (
+                    $id: String!
+                    $input: IssueLabelUpdateInput!
+                ) {
+                issueLabelUpdate(
+                    id: $id,
+                    input: $input
+                ) {
+                    success
+                }
+                }
+                ";
+
+const ISSUE_LABEL_ARCHIVE_DOC: &str = "mutation (
+                    $id: String!
+                ) {
+                issueLabelArchive(
+                    id: $id
+                ) {
+                    success
+                }
+                }
+                ";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct LabelsResponse {
+    data: Option<LabelsData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct LabelsData {
+    issue_labels: Labels,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct Labels {
+    pub nodes: Vec<Label>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct Label {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    /// None for workspace labels, which can be used by every team
+    team: Option<LabelTeam>,
+}
+
+impl Display for Label {
+    /// This is for rendering in a select
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct LabelTeam {
+    id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct LabelMutationResponse {
+    data: Option<HashMap<String, LabelPayload>>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct LabelPayload {
+    success: bool,
+}
+
+/// Create a label for a team, or for the whole workspace when team is None. A group holds other
+/// labels, which are added to it with parent, and only one label of a group can be on an issue.
+pub fn create(
+    config: &Config,
+    token: &str,
+    team: &Option<Team>,
+    name: String,
+    color: Option<String>,
+    parent: Option<String>,
+    group: bool,
+) -> Result<String, String> {
+    let mut input = json!({ "name": name });
+    if let Some(team) = team {
+        input["teamId"] = json!(team.id);
+    }
+    if let Some(color) = color {
+        input["color"] = json!(color);
+    }
+    if let Some(parent) = &parent {
+        input["parentId"] = json!(id_in(config, token, team, parent)?);
+    }
+    if group {
+        input["isGroup"] = json!(true);
+    }
+    mutate(
+        config,
+        token,
+        ISSUE_LABEL_CREATE_DOC,
+        json!({ "input": input }),
+    )?;
+
+    let kind = match group {
+        true => "label group",
+        false => "label",
+    };
+    let parent = parent.map(|p| format!(" under {p}")).unwrap_or_default();
+    Ok(format!("Created {kind} {name}{parent} in {}", scope(team)))
+}
+
+/// Rename a label, looked up like ids when there is a team
+pub fn rename(
+    config: &Config,
+    token: &str,
+    team: &Option<Team>,
+    name: &str,
+    new_name: String,
+) -> Result<String, String> {
+    let id = id_in(config, token, team, name)?;
+    let variables = json!({ "id": id, "input": { "name": new_name } });
+    mutate(config, token, ISSUE_LABEL_UPDATE_DOC, variables)?;
+    Ok(format!(
+        "Renamed label {name} to {new_name} in {}",
+        scope(team)
+    ))
+}
+
+/// Archive a label so it can no longer be added to issues, issues that have it keep it
+pub fn archive(
+    config: &Config,
+    token: &str,
+    team: &Option<Team>,
+    name: &str,
+) -> Result<String, String> {
+    let id = id_in(config, token, team, name)?;
+    mutate(config, token, ISSUE_LABEL_ARCHIVE_DOC, json!({ "id": id }))?;
+    Ok(format!("Archived label {name} in {}", scope(team)))
+}
+
+/// The id of a team's label, falling back to a workspace label, or of a workspace label when team is None.
+/// This isn't cached because the label may have just been created or renamed.
+fn id_in(config: &Config, token: &str, team: &Option<Team>, name: &str) -> Result<String, String> {
+    let response = request::Gql::new(config, token, LABELS_DOC)
+        .put_strings("names", vec![name.to_string()])
+        .run()?;
+    let labels = labels_response(response)?;
+
+    match team {
+        Some(team) => find(&labels, team, name),
+        None => labels
+            .into_iter()
+            .find(|l| l.name == name && l.team.is_none())
+            .map(|l| l.id)
+            .ok_or_else(|| format!("Workspace label {name} not found")),
+    }
+}
+
+fn scope(team: &Option<Team>) -> String {
+    match team {
+        Some(team) => team.name.clone(),
+        None => String::from("the workspace"),
+    }
+}
+
+fn mutate(config: &Config, token: &str, query: &str, variables: Value) -> Result<(), String> {
+    let Value::Object(variables) = variables else {
+        return Err(String::from("Variables should be an object"));
+    };
+    let response: LabelMutationResponse = request::Gql::new(config, token, query)
+        .put_variables(variables.into_iter().collect())
+        .execute()?;
+
+    let success = response
+        .data
+        .is_some_and(|data| data.values().all(|payload| payload.success));
+    match success {
+        true => Ok(()),
+        false => Err(String::from("Linear did not accept the label change")),
+    }
+}
+
+/// Look up label ids by name, preferring a team's own label over a workspace label of the same name
+pub fn ids(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    names: Vec<String>,
+) -> Result<Vec<String>, String> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let response = request::Gql::new(config, token, LABELS_DOC)
+        .cached()
+        .put_strings("names", names.clone())
+        .run()?;
+    let labels = labels_response(response)?;
+
+    names.iter().map(|name| find(&labels, team, name)).collect()
+}
+
+/// Like ids, but leaves out names the team has no label for, i.e. when copying an issue to another team
+pub fn existing_ids(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    names: Vec<String>,
+) -> Result<Vec<String>, String> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let response = request::Gql::new(config, token, LABELS_DOC)
+        .cached()
+        .put_strings("names", names.clone())
+        .run()?;
+    let labels = labels_response(response)?;
+
+    Ok(names
+        .iter()
+        .filter_map(|name| find(&labels, team, name).ok())
+        .collect())
+}
+
+/// Labels a team can use, its own and the workspace labels, sorted by name
+pub fn for_team(config: &Config, token: &str, team: &Team) -> Result<Vec<Label>, String> {
+    let response = request::Gql::new(config, token, TEAM_LABELS_DOC)
+        .cached()
+        .put_string("teamId", team.id.clone())
+        .run()?;
+    let mut labels = labels_response(response)?;
+    labels.sort_by_key(|label| label.name.to_lowercase());
+    Ok(labels)
+}
+
+/// Prompt for any number of the labels a team can use
+pub fn select_ids(config: &Config, token: &str, team: &Team) -> Result<Vec<String>, String> {
+    let labels = for_team(config, token, team)?;
+    if labels.is_empty() {
+        return Ok(Vec::new());
+    }
+    let selected = input::multi_select("Select labels", labels)?;
+    Ok(selected.into_iter().map(|label| label.id).collect())
+}
+
+fn find(labels: &[Label], team: &Team, name: &str) -> Result<String, String> {
+    let matching = labels.iter().filter(|l| l.name == name);
+    let team_label = matching
+        .clone()
+        .find(|l| l.team.as_ref().is_some_and(|t| t.id == team.id));
+    let workspace_label = matching.clone().find(|l| l.team.is_none());
+
+    match team_label.or(workspace_label) {
+        Some(label) => Ok(label.id.clone()),
+        None => Err(format!("Label {name} not found for team {}", team.name)),
+    }
+}
+
+fn labels_response(response: String) -> Result<Vec<Label>, String> {
+    let data: Result<LabelsResponse, _> = schema::parse(&response, "labels");
+
+    match data {
+        Ok(LabelsResponse {
+            data: Some(LabelsData {
+                issue_labels: Labels { nodes },
+            }),
+        }) => Ok(nodes),
+        err => Err(format!(
+            "Could not parse response for labels:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ids() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"names\":\\[\"cli-created\",\"cats\"\\]",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_labels())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let team = test::fixtures::team();
+
+        let result = ids(
+            &config,
+            "1234",
+            &team,
+            vec![String::from("cli-created"), String::from("cats")],
+        );
+        assert_eq!(
+            result,
+            Ok(vec![String::from("label-team"), String::from("label-cats")])
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_select_ids() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"teamId\":\"123456\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_labels())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        input::set_backend(input::Answers {
+            multi_select: Some(vec![0, 3]),
+            ..input::Answers::default()
+        });
+
+        let result = select_ids(&config, "1234", &test::fixtures::team());
+        assert_eq!(
+            result,
+            Ok(vec![String::from("label-cats"), String::from("label-team")])
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn find_should_error_on_missing_label() {
+        let labels = vec![Label {
+            id: String::from("label-other"),
+            name: String::from("dogs"),
+            team: Some(LabelTeam {
+                id: String::from("654321"),
+            }),
+        }];
+
+        assert_eq!(
+            find(&labels, &test::fixtures::team(), "dogs"),
+            Err(String::from("Label dogs not found for team Thundercats"))
+        );
+    }
+
+    #[test]
+    fn test_create() {
+        let mut server = mockito::Server::new();
+        let lookup = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"names\":\\[\"cats\"\\]",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_labels())
+            .create();
+        let mutation = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"input\":\\{\"color\":\"#eb5757\",\"name\":\"Hairball\",\"parentId\":\"label-cats\",\"teamId\":\"123456\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_label_create())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = create(
+            &config,
+            "1234",
+            &Some(test::fixtures::team()),
+            String::from("Hairball"),
+            Some(String::from("#eb5757")),
+            Some(String::from("cats")),
+            false,
+        );
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "Created label Hairball under cats in Thundercats"
+            ))
+        );
+        lookup.assert();
+        mutation.assert();
+    }
+
+    #[test]
+    fn test_archive() {
+        let mut server = mockito::Server::new();
+        let lookup = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("\"names\":")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_labels())
+            .expect(2)
+            .create();
+        let mutation = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "issueLabelArchive.*\"id\":\"label-workspace\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_label_archive())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            archive(&config, "1234", &None, "cli-created"),
+            Ok(String::from("Archived label cli-created in the workspace"))
+        );
+        assert_eq!(
+            archive(&config, "1234", &None, "dogs"),
+            Err(String::from("Workspace label dogs not found"))
+        );
+        lookup.assert();
+        mutation.assert();
+    }
+
+    #[test]
+    fn ids_should_skip_request_when_empty() {
+        let config = test::fixtures::config();
+        assert_eq!(
+            ids(&config, "1234", &test::fixtures::team(), Vec::new()),
+            Ok(Vec::new())
+        );
+    }
+}
@@ -2,28 +2,79 @@ extern crate clap;
 #[cfg(test)]
 extern crate matches;
 
+mod api;
+mod attachment;
+mod board;
+mod branch;
+mod bulk;
+mod cache;
+mod cassette;
 mod color;
 mod config;
+mod conflict;
+mod customer;
+mod cycle;
+mod date;
+mod defaults;
+mod doctor;
+mod document;
+mod estimate;
+mod format;
 mod git;
+mod github;
+mod history;
+mod hooks;
+mod import;
+mod initiative;
 mod input;
 mod issue;
+mod label;
+mod member;
+mod merge;
 mod priority;
+mod processor;
+mod project;
+mod queue;
+mod relation;
+mod report;
 mod request;
+mod roadmap;
+mod schema;
+mod state;
+mod stats;
 mod team;
 mod template;
 mod test;
+mod time;
+mod user;
+mod vcs;
+mod verbosity;
 mod viewer;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use colored::*;
 use config::Config;
+use issue::Field;
 use priority::Priority;
+use std::ffi::OsString;
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
 use team::{Project, State, Team};
+use verbosity::Verbosity;
 
 const NAME: &str = "lnr";
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const AUTHOR: &str = "Alan Vardy <alan@vardy.cc>";
 const ABOUT: &str = "A tiny unofficial Linear client";
+/// How long to wait for the version check after the command has finished
+const VERSION_CHECK_TIMEOUT: Duration = Duration::from_millis(500);
+/// How long the latest version from crates.io is cached for
+const VERSION_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const VERSION_CACHE_KEY: &str = "latest_version";
+/// How often issue view --watch refreshes when neither --interval nor config sets it
+const DEFAULT_WATCH_INTERVAL_SECONDS: u64 = 30;
 
 #[derive(Parser, Clone)]
 #[command(name = NAME)]
@@ -33,12 +84,56 @@ const ABOUT: &str = "A tiny unofficial Linear client";
 #[command(arg_required_else_help(true))]
 struct Cli {
     #[arg(short, long)]
-    /// Absolute path of configuration. Defaults to $XDG_CONFIG_HOME/lnr.cfg
+    /// Absolute path of configuration. Defaults to $XDG_CONFIG_HOME/lnr.toml
     config: Option<String>,
 
     #[arg(short, long)]
     /// You will be prompted at runtime if this isn't provided
     org: Option<String>,
+
+    #[arg(long)]
+    /// Profile from config to use, i.e. work. Defaults to the LNR_PROFILE environment variable
+    profile: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    /// Never prompt, return an error naming the missing flag instead. For scripts and CI
+    no_input: bool,
+
+    #[arg(
+        short,
+        long,
+        visible_alias = "defaults",
+        default_value_t = false,
+        conflicts_with = "no_input"
+    )]
+    /// Never prompt, take the default instead: the first team, no project, the team's default state, and Normal priority. Errors when there is no default, i.e. for a title
+    yes: bool,
+
+    #[arg(long, default_value_t = false)]
+    /// Fetch teams, states, and projects from the API instead of the cache
+    refresh: bool,
+
+    #[arg(long)]
+    /// Seconds to wait for a response from Linear, overrides request_timeout_seconds in config
+    timeout: Option<u64>,
+
+    #[arg(long, default_value_t = false)]
+    /// Don't show spinners while waiting for Linear. They are also left out when output isn't a terminal
+    no_spinner: bool,
+
+    #[arg(short, long, default_value_t = false, conflicts_with = "verbose")]
+    /// Only print the result, leaving out spinners, progress, and the new version notice
+    quiet: bool,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Print the organization, team, project, and state that are used before calling the API
+    verbose: bool,
+
+    #[arg(long, default_value_t = false, conflicts_with = "quiet")]
+    /// Like --verbose, and log each request to Linear with its variables, status, duration, and response.
+    /// Also turned on with LNR_LOG=debug
+    debug: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -59,25 +154,236 @@ enum Commands {
     #[clap(alias = "t")]
     /// (t) Commands for working with templates
     Template(TemplateCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "r")]
+    /// (r) Reports for teams
+    Report(ReportCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "m")]
+    /// (m) Commands for team members
+    Member(MemberCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "tm")]
+    /// (tm) Commands for team settings, such as workflow states
+    Team(TeamCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "l")]
+    /// (l) Commands for team and workspace labels
+    Label(LabelCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "pj")]
+    /// (pj) Commands for projects
+    Project(ProjectCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "p")]
+    /// (p) Commands for GitHub pull requests
+    Pr(PrCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "c")]
+    /// (c) Commands for the config file
+    Config(ConfigCommands),
+
+    #[clap(alias = "b")]
+    /// (b) Board of your issues with a column for each workflow state, for moving issues between states
+    Board(Board),
+
+    #[clap(alias = "d")]
+    /// (d) Check the config, tokens, git, editor, and connection to Linear for problems
+    Doctor(Doctor),
+
+    #[clap(alias = "s")]
+    /// (s) Send issues and comments that were queued with --queue while Linear couldn't be reached
+    Sync(Sync),
+
+    #[command(subcommand)]
+    #[clap(alias = "im")]
+    /// (im) Create Linear issues from another tracker
+    Import(ImportCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "rd")]
+    /// (rd) Initiatives and the health of their projects
+    Roadmap(RoadmapCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "dc")]
+    /// (dc) Read Linear documents, such as project specs
+    Doc(DocCommands),
+
+    #[command(subcommand)]
+    #[clap(alias = "cy")]
+    /// (cy) How the current cycle is going
+    Cycle(CycleCommands),
+
+    /// Run a GraphQL query or mutation against Linear and print the JSON response
+    Api(Api),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum CycleCommands {
+    #[clap(alias = "r")]
+    /// (r) Scope, completed and remaining points, issues added after the start, and the load on each person
+    Report(CycleReport),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum DocCommands {
+    #[clap(alias = "l")]
+    /// (l) List documents, most recently updated first
+    List(DocList),
+
+    #[clap(alias = "v")]
+    /// (v) View a document, rendering its markdown in the terminal
+    View(DocView),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum RoadmapCommands {
+    #[clap(alias = "l")]
+    /// (l) List initiatives with their status, target date, and number of projects
+    List(RoadmapList),
+
+    #[clap(alias = "v")]
+    /// (v) View an initiative with the status, progress, and health of each project
+    View(RoadmapView),
+
+    #[clap(alias = "a")]
+    /// (a) Add a project to an initiative
+    AddProject(RoadmapAddProject),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ImportCommands {
+    #[clap(alias = "g")]
+    /// (g) Create an issue for each open GitHub issue in a repository. Uses GITHUB_TOKEN, GH_TOKEN, or the GitHub CLI to authenticate
+    Github(ImportGithub),
 }
 
 #[derive(Subcommand, Debug, Clone)]
 enum IssueCommands {
     #[clap(alias = "c")]
     /// (c) Create a new issue
-    Create(IssueCreate),
+    Create(Box<IssueCreate>),
 
     #[clap(alias = "e")]
-    /// (e) Edit the issue for current branch
+    /// (e) Edit an issue, defaults to the issue for current branch. You will be prompted for which fields to change if no flags are provided
     Edit(IssueEdit),
 
     #[clap(alias = "v")]
-    /// (v) View the issue for current branch
+    /// (v) View an issue, defaults to the issue for current branch
     View(IssueView),
 
     #[clap(alias = "l")]
-    /// (l) List issues, maximum of 50. Returns issues assigned to user that are Todo or In Progress
+    /// (l) List issues, maximum of 50. Defaults to your issues, leaving out states such as Done and Backlog (see excluded_states). --assignee and --creator change whose issues, --all-states and --include-state add states
     List(IssueList),
+
+    #[clap(alias = "st")]
+    /// (st) Count your issues, or a team's, by state, priority, and project with estimate totals
+    Stats(IssueStats),
+
+    #[clap(alias = "m")]
+    /// (m) Comment on the issue for current branch, or on several issues at once
+    Comment(IssueComment),
+
+    #[clap(alias = "x")]
+    /// (x) Export an issue to a self-contained file for sharing
+    Export(IssueExport),
+
+    #[clap(alias = "s")]
+    /// (s) Set the estimate of the issue for current branch
+    Estimate(IssueEstimate),
+
+    #[clap(alias = "d")]
+    /// (d) Set or clear the due date of the issue for current branch
+    Due(IssueDue),
+
+    #[clap(alias = "k")]
+    /// (k) Switch to the git branch for an issue, creating it if needed. Uses branch_format from config if set
+    Checkout(IssueCheckout),
+
+    #[clap(alias = "r")]
+    /// (r) Link the issue for current branch to another issue as blocking, blocked by, related, or duplicate
+    Link(IssueLink),
+
+    #[clap(alias = "u")]
+    /// (u) Cross-reference an issue with an item in another tracker, such as a GitHub issue
+    Xref(IssueXref),
+
+    #[clap(alias = "g")]
+    /// (g) Merge a duplicate into another issue: relate them, copy labels and attachments, comment on both, and cancel the duplicate
+    Merge(IssueMerge),
+
+    #[clap(aliases = ["dp", "clone"])]
+    /// (dp) Copy the issue for current branch into a new issue, optionally on another team
+    Duplicate(IssueDuplicate),
+
+    /// Move the issue for current branch to the team's first unstarted state
+    Todo(IssueTransition),
+
+    /// Move the issue for current branch to the team's first started state
+    Progress(IssueTransition),
+
+    /// Move the issue for current branch to the team's first completed state
+    Done(IssueTransition),
+
+    /// Move the issue for current branch to the team's first canceled state
+    Cancel(IssueTransition),
+
+    #[clap(alias = "o")]
+    /// (o) Move the issue for current branch to another team or project. You will be prompted for both if no flags are provided
+    Move(IssueMove),
+
+    #[clap(alias = "b")]
+    /// (b) Apply the same state and labels to many issues, with identifiers from --ids or stdin
+    Bulk(IssueBulk),
+
+    #[clap(alias = "rs")]
+    /// (rs) Restore an archived issue. You will be prompted to select from your archived issues if no identifier is provided
+    Restore(IssueRestore),
+
+    #[command(subcommand)]
+    #[clap(alias = "p")]
+    /// (p) Commands for the project of an issue
+    Project(IssueProjectCommands),
+
+    #[clap(alias = "q")]
+    /// (q) List the customer requests attached to the issue for current branch, most important first
+    Requests(IssueRequests),
+
+    #[clap(alias = "a")]
+    /// (a) Attach a file or url to the issue for current branch, i.e. a log file for a bug
+    Attach(IssueAttach),
+
+    #[command(subcommand)]
+    #[clap(alias = "t")]
+    /// (t) Track time spent on issues, posting a comment when it is logged
+    Time(TimeCommands),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum TimeCommands {
+    #[clap(alias = "s")]
+    /// (s) Start tracking time on an issue
+    Start(TimeStart),
+
+    #[clap(alias = "p")]
+    /// (p) Stop tracking time and log it to the issue
+    Stop(TimeStop),
+
+    #[clap(alias = "l")]
+    /// (l) Log time spent on an issue without starting and stopping
+    Log(TimeLog),
+
+    #[clap(alias = "r")]
+    /// (r) Total time logged per issue this week
+    Report(TimeReport),
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -93,6 +399,145 @@ enum OrgCommands {
     #[clap(alias = "l")]
     /// (l) List organizations in config
     List(OrgList),
+
+    #[clap(alias = "n")]
+    /// (n) Rename an organization, keeping its token and the settings scoped to it
+    Rename(OrgRename),
+
+    #[clap(alias = "t")]
+    /// (t) Replace the token of an organization, i.e. when it expires. Reads the token from stdin if piped, otherwise prompts without showing it
+    SetToken(OrgSetToken),
+}
+
+#[derive(Parser, Debug, Clone)]
+struct Sync {}
+
+#[derive(Parser, Debug, Clone)]
+struct ImportGithub {
+    #[arg(short, long)]
+    /// Repository to import from, i.e. owner/name
+    repo: String,
+
+    #[arg(short = 'e', long)]
+    /// Team to create the issues in
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Don't prompt for project
+    noproject: bool,
+
+    #[arg(short, long)]
+    /// State to create the issues in, defaults to the team's default state with --yes
+    state: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct CycleReport {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct DocList {
+    #[arg(short, long)]
+    /// Only documents in this project
+    project: Option<String>,
+
+    #[arg(short = 'e', long)]
+    /// Only documents of this team and of its projects
+    team: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct DocView {
+    /// Id or url of the document, as shown by doc list
+    id: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct Api {
+    /// The query or mutation, i.e. "{ viewer { name } }"
+    #[arg(required_unless_present = "file", conflicts_with = "file")]
+    query: Option<String>,
+
+    #[arg(short, long)]
+    /// Read the query or mutation from a file, i.e. query.graphql
+    file: Option<String>,
+
+    #[arg(short = 'F', long = "var")]
+    /// A variable as key=value, can be repeated. Values that are valid JSON, such as 3 or ["a"], are passed as is
+    variables: Vec<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct RoadmapList {}
+
+#[derive(Parser, Debug, Clone)]
+struct RoadmapView {
+    /// Name of the initiative, ignoring case
+    initiative: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct RoadmapAddProject {
+    /// Name of the initiative, ignoring case
+    initiative: String,
+
+    #[arg(short, long)]
+    /// Name of the project to add, you will be prompted if not provided
+    project: Option<String>,
+
+    #[arg(short = 'e', long)]
+    /// Team the project belongs to
+    team: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct Doctor {}
+
+#[derive(Parser, Debug, Clone)]
+struct Board {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Show a column for every state, such as Backlog and Done
+    all_states: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ConfigCommands {
+    #[clap(alias = "e")]
+    /// (e) Open the config file in $EDITOR, checking that it is valid before saving
+    Edit(ConfigEdit),
+
+    #[clap(alias = "g")]
+    /// (g) Show the value of a config field, i.e. date_format
+    Get(ConfigGet),
+
+    #[clap(alias = "s")]
+    /// (s) Set a config field, i.e. spinners false. Use null to unset it
+    Set(ConfigSet),
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ConfigEdit {}
+
+#[derive(Parser, Debug, Clone)]
+struct ConfigGet {
+    /// Name of the field
+    key: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ConfigSet {
+    /// Name of the field
+    key: String,
+
+    /// New value, read as JSON when possible, i.e. false, 30, or {"default": ["Done"]}
+    value: String,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -104,6 +549,21 @@ struct OrgRemove {}
 #[derive(Parser, Debug, Clone)]
 struct OrgList {}
 
+#[derive(Parser, Debug, Clone)]
+struct OrgRename {
+    /// Current name of the organization. You will be prompted if this isn't provided
+    name: Option<String>,
+
+    /// New name for the organization. You will be prompted if this isn't provided
+    new_name: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct OrgSetToken {
+    /// Name of the organization, defaults to --org or the only organization. You will be prompted if there are several
+    name: Option<String>,
+}
+
 #[derive(Subcommand, Debug, Clone)]
 enum TemplateCommands {
     #[clap(alias = "e")]
@@ -111,270 +571,2703 @@ enum TemplateCommands {
     Evaluate(TemplateEvaluate),
 }
 
-#[derive(Parser, Debug, Clone)]
-struct TemplateEvaluate {
-    #[arg(short, long)]
-    /// Path to file or directory
-    path: Option<String>,
+#[derive(Subcommand, Debug, Clone)]
+enum MemberCommands {
+    #[clap(alias = "l")]
+    /// (l) List the members of a team with their names and emails
+    List(MemberList),
+}
 
+#[derive(Parser, Debug, Clone)]
+struct MemberList {
     #[arg(short = 'e', long)]
     /// Team name
     team: Option<String>,
+}
 
-    #[arg(short, long, default_value_t = false)]
-    /// Do not prompt for a project
-    noproject: bool,
+#[derive(Subcommand, Debug, Clone)]
+enum TeamCommands {
+    #[command(subcommand)]
+    #[clap(alias = "s")]
+    /// (s) Commands for the workflow states of a team
+    State(StateCommands),
+}
 
-    #[arg(short = 'r', long)]
-    /// 1 (Low), 2 (Normal), 3 (High), or 4 (Urgent)
-    priority: Option<u8>,
+#[derive(Subcommand, Debug, Clone)]
+enum StateCommands {
+    #[clap(alias = "l")]
+    /// (l) List the workflow states of a team with their types, in workflow order
+    List(StateList),
 
-    #[arg(short, long)]
-    /// i.e. Backlog or Todo
-    state: Option<String>,
+    #[clap(alias = "c")]
+    /// (c) Add a workflow state to a team
+    Create(StateCreate),
+
+    #[clap(alias = "r")]
+    /// (r) Rename a workflow state of a team
+    Rename(StateRename),
 }
 
 #[derive(Parser, Debug, Clone)]
-struct IssueCreate {
+struct StateList {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct StateCreate {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
     #[arg(short, long)]
-    /// Title for issue
-    title: Option<String>,
+    /// Name of the new state, i.e. "In Review"
+    name: String,
+
+    #[arg(short = 't', long = "type", value_enum)]
+    /// Type of the new state, which decides how Linear and commands such as issue done treat it
+    state_type: state::StateType,
 
     #[arg(short, long)]
-    /// Description for issue
-    description: Option<String>,
+    /// Hex color, i.e. "#f2c94c". Defaults to Linear's color for the type
+    color: Option<String>,
+}
 
-    #[arg(short = 'r', long)]
-    /// 1 (Low), 2 (Normal), 3 (High), or 4 (Urgent)
-    priority: Option<u8>,
+#[derive(Parser, Debug, Clone)]
+struct StateRename {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    /// Current name of the state
+    state: String,
+
+    /// New name for the state
+    name: String,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ProjectCommands {
+    #[clap(alias = "c")]
+    /// (c) Create a project for one or more teams
+    Create(ProjectCreate),
+
+    #[clap(alias = "l")]
+    /// (l) List the projects of a team, or of all your teams, with the teams each project belongs to
+    List(ProjectList),
+}
 
+#[derive(Parser, Debug, Clone)]
+struct ProjectList {
     #[arg(short = 'e', long)]
     /// Team name
     team: Option<String>,
 
-    #[arg(short, long, default_value_t = false)]
-    /// Do not prompt for a project
-    noproject: bool,
+    #[arg(short, long, default_value_t = false, conflicts_with = "team")]
+    /// List the projects of every team you are a member of, each shared project once
+    all_teams: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ProjectCreate {
+    #[arg(short, long)]
+    /// Name of the project
+    name: Option<String>,
+
+    #[arg(short, long)]
+    /// Description in markdown, use - to read it from stdin
+    description: Option<String>,
+
+    #[arg(short = 'e', long, visible_alias = "teams")]
+    /// Team name, can be repeated for projects shared between teams. Prompts for teams if none are given
+    team: Vec<String>,
+
+    #[arg(short, long)]
+    /// Display name, name, or email of the project lead, or me. Looked up in the first team
+    lead: Option<String>,
+
+    #[arg(short, long)]
+    /// When work starts, i.e. 2025-10-01, monday, or +1w
+    start_date: Option<String>,
+
+    #[arg(short, long)]
+    /// When the project should be done, i.e. 2025-12-19, or +6w
+    target_date: Option<String>,
+
+    #[arg(long)]
+    /// Project status name, i.e. Planned. Defaults to the workspace's default status
+    status: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Also prompt for the description, lead, dates, and status when they aren't given as flags
+    full: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum LabelCommands {
+    #[clap(alias = "c")]
+    /// (c) Create a label or a label group
+    Create(LabelCreate),
+
+    #[clap(alias = "r")]
+    /// (r) Rename a label
+    Rename(LabelRename),
+
+    #[clap(alias = "a")]
+    /// (a) Archive a label so it can no longer be added to issues
+    Archive(LabelArchive),
+}
+
+#[derive(Parser, Debug, Clone)]
+struct LabelCreate {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = false, conflicts_with = "team")]
+    /// Create a workspace label, which every team can use
+    workspace: bool,
+
+    #[arg(short, long)]
+    /// Name of the label
+    name: String,
+
+    #[arg(short, long)]
+    /// Hex color, i.e. "#eb5757". Linear picks one if this isn't provided
+    color: Option<String>,
+
+    #[arg(short, long)]
+    /// Name of the label group to add the label to
+    parent: Option<String>,
+
+    #[arg(short, long, default_value_t = false, conflicts_with = "parent")]
+    /// Create a label group, only one label of a group can be on an issue
+    group: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct LabelRename {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = false, conflicts_with = "team")]
+    /// Rename a workspace label
+    workspace: bool,
+
+    /// Current name of the label
+    label: String,
+
+    /// New name for the label
+    name: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct LabelArchive {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = false, conflicts_with = "team")]
+    /// Archive a workspace label
+    workspace: bool,
+
+    /// Name of the label
+    label: String,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum PrCommands {
+    #[clap(alias = "c")]
+    /// (c) Open a pull request for the current branch with gh, titled and described from its issue
+    Create(PrCreate),
+}
+
+#[derive(Parser, Debug, Clone)]
+struct PrCreate {
+    #[arg(short, long)]
+    /// Branch to merge into. Defaults to the repository's default branch
+    base: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Open the pull request as a draft
+    draft: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ReportCommands {
+    #[clap(alias = "c")]
+    /// (c) Chart issues created vs completed per week, to see whether the backlog is growing
+    CreatedVsCompleted(ReportCreatedVsCompleted),
+
+    #[clap(alias = "s")]
+    /// (s) Markdown for standup: what you completed, what is in progress, and what is blocked
+    Standup(ReportStandup),
+
+    #[clap(alias = "l")]
+    /// (l) Markdown changelog of the Linear issues mentioned in commit messages between two git revisions
+    Changelog(ReportChangelog),
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ReportChangelog {
+    #[arg(short, long)]
+    /// Start after this revision, usually the last release tag, i.e. v1.2.0
+    since: String,
+
+    #[arg(short, long, default_value = "HEAD")]
+    /// End at this revision
+    until: String,
+
+    #[arg(short, long, value_enum, default_value_t = report::GroupBy::Label)]
+    /// Group issues by their first label or by project
+    group_by: report::GroupBy,
+
+    #[arg(short, long)]
+    /// Heading of the changelog, defaults to the revision range
+    title: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ReportStandup {
+    #[arg(short, long, default_value = "yesterday", allow_hyphen_values = true)]
+    /// Include issues completed since this date, i.e. 2025-10-01, yesterday, friday, or -3d
+    since: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ReportCreatedVsCompleted {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = 8)]
+    /// Number of weeks to include, ending with the current week
+    weeks: u8,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TemplateEvaluate {
+    #[arg(short, long)]
+    /// Path to file or directory
+    path: Option<String>,
+
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Do not prompt for a project
+    noproject: bool,
+
+    #[arg(short = 'r', long)]
+    /// 1 (Low), 2 (Normal), 3 (High), or 4 (Urgent)
+    priority: Option<u8>,
+
+    #[arg(short, long)]
+    /// i.e. Backlog or Todo
+    state: Option<String>,
+
+    #[arg(short, long, default_value_t = 4)]
+    /// Number of TOML files in a directory to evaluate at once
+    concurrency: usize,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueCreate {
+    #[arg(short, long)]
+    /// Title for issue
+    title: Option<String>,
+
+    #[arg(short, long)]
+    /// Description for issue, use - to read from stdin
+    description: Option<String>,
+
+    #[arg(short = 'f', long, conflicts_with = "description")]
+    /// Path to a file containing the description
+    description_file: Option<String>,
+
+    #[arg(short = 'r', long)]
+    /// 1 (Low), 2 (Normal), 3 (High), or 4 (Urgent)
+    priority: Option<u8>,
+
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Do not prompt for a project
+    noproject: bool,
+
+    #[arg(short, long)]
+    /// i.e. Backlog or Todo
+    state: Option<String>,
+
+    #[arg(short = 'm', long)]
+    /// Estimate in points, or a size such as M for teams that estimate in t-shirt sizes
+    estimate: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    /// Do not prompt for an estimate
+    noestimate: bool,
+
+    #[arg(short, long)]
+    /// Label name, added to the default_labels. Can be repeated
+    label: Vec<String>,
+
+    #[arg(long, default_value_t = false, conflicts_with = "label")]
+    /// Do not prompt for labels
+    nolabels: bool,
+
+    #[arg(short, long)]
+    /// Display name, name, or email of a team member. Use none to leave unassigned, defaults to me
+    assignee: Option<String>,
+
+    #[arg(short, long)]
+    /// Number or name of a current or upcoming cycle
+    cycle: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    /// Also prompt for assignee, cycle, and due date when they aren't given as flags
+    full: bool,
+
+    #[arg(short = 'u', long)]
+    /// Due date, i.e. 2025-10-01, friday, or +3d
+    due: Option<String>,
+
+    #[arg(short, long)]
+    /// Identifier or UUID of the parent issue, creates a sub-issue
+    parent: Option<String>,
+
+    #[arg(long, default_value_t = false, conflicts_with = "parent")]
+    /// Create a sub-issue of the issue for current branch
+    parent_branch: bool,
+
+    #[arg(long, default_value_t = false)]
+    /// If Linear can't be reached, queue the issue to be created by lnr sync.
+    /// The description is saved as a draft when the lookups before it fail
+    queue: bool,
+
+    #[arg(long)]
+    /// Name of an issue template in config, which pre-fills the title, description, labels, and priority
+    template: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueEdit {
+    /// Issue identifier, UUID, or URL, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+
+    #[arg(short, long)]
+    /// New title
+    title: Option<String>,
+
+    #[arg(short, long)]
+    /// New description, use - to read from stdin
+    description: Option<String>,
+
+    #[arg(short, long)]
+    /// i.e. Backlog or Todo
+    state: Option<String>,
+
+    #[arg(short = 'r', long)]
+    /// 1 (Low), 2 (Normal), 3 (High), or 4 (Urgent)
+    priority: Option<u8>,
+
+    #[arg(short, long)]
+    /// Project name, or None to remove the issue from its project
+    project: Option<String>,
+
+    #[arg(short, long)]
+    /// Display name, name, or email of a team member. Use me to assign to yourself or none to unassign
+    assignee: Option<String>,
+
+    #[arg(short = 'm', long)]
+    /// Estimate in points, or a size such as M for teams that estimate in t-shirt sizes
+    estimate: Option<String>,
+
+    #[arg(short, long)]
+    /// Label name to add, existing labels are kept. Can be repeated
+    label: Vec<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueDue {
+    #[arg(conflicts_with = "clear")]
+    /// Due date, i.e. 2025-10-01, friday, or +3d. You will be prompted if this isn't provided
+    due: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Remove the due date
+    clear: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueEstimate {
+    /// Estimate in points, or a size such as M for teams that estimate in t-shirt sizes
+    estimate: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueView {
+    #[arg(conflicts_with = "select")]
+    /// Issue identifier, UUID, or URL, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Select ticket from list view
+    select: bool,
+
+    #[arg(short, long)]
+    /// Only show this many of the most recent comment threads
+    comments: Option<usize>,
+
+    #[arg(long, default_value_t = false, conflicts_with = "comments")]
+    /// Don't fetch or show comments
+    no_comments: bool,
+
+    #[arg(long, default_value_t = false)]
+    /// Show state and assignee changes, oldest first
+    history: bool,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Keep the issue on screen, refreshing it and highlighting new comments and state changes
+    watch: bool,
+
+    #[arg(long, requires = "watch")]
+    /// Seconds between refreshes with --watch, defaults to watch_interval_seconds in config or 30
+    interval: Option<u64>,
+
+    #[arg(short, long, conflicts_with_all = ["comments", "no_comments", "history", "watch"])]
+    /// Template for the issue without comments, i.e. "{identifier}\t{state}\t{title}". See the README for placeholders
+    format: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueList {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Don't prompt for project
+    noproject: bool,
+
+    #[arg(short = 't', long, default_value_t = false)]
+    /// Don't prompt for team
+    noteam: bool,
+
+    #[arg(short, long, value_enum)]
+    /// Order of issues, defaults to parent issues first and then by state name
+    sort: Option<issue::Sort>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Reverse the order of issues
+    reverse: bool,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Only issues with an SLA, closest to breach first unless --sort is given
+    breaching: bool,
+
+    #[arg(long, default_value_t = false)]
+    /// Only archived issues, in every state. Restore one with issue restore
+    archived: bool,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Include issues in every state, such as Backlog and Done
+    all_states: bool,
+
+    #[arg(short, long)]
+    /// Include issues in a state that is normally left out, i.e. Backlog. Can be repeated
+    include_state: Vec<String>,
+
+    #[arg(short = 'I', long)]
+    /// Only issues in the projects of this initiative, skips the project prompt
+    initiative: Option<String>,
+
+    #[arg(short = 'g', long)]
+    /// Display name, name, or email of a team member. Use none for unassigned issues and any for everyone's, defaults to me
+    assignee: Option<String>,
+
+    #[arg(short = 'c', long)]
+    /// Only issues created by me or by a team member, assigned to anyone unless --assignee is given
+    creator: Option<String>,
+
+    #[arg(short, long, value_enum, default_value_t = issue::Output::Text)]
+    /// Output format, json includes the UUID of each issue
+    output: issue::Output,
+
+    #[arg(short, long, conflicts_with = "output")]
+    /// Template for each issue, i.e. "{identifier}\t{state}\t{title}". See the README for placeholders
+    format: Option<String>,
+
+    #[arg(long, default_value_t = false, conflicts_with_all = ["output", "format"])]
+    /// Select issues from the list and change their state, add labels, or reassign them all at once
+    interactive: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueStats {
+    #[arg(short = 'e', long)]
+    /// Team name
+    team: Option<String>,
+
+    #[arg(
+        short = 't',
+        long,
+        default_value_t = false,
+        conflicts_with = "everyone"
+    )]
+    /// Don't prompt for team, counts your issues across every team
+    noteam: bool,
+
+    #[arg(short = 'w', long, default_value_t = false)]
+    /// Count everyone's issues in the team, not only yours
+    everyone: bool,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Include issues in every state, such as Backlog and Done
+    all_states: bool,
+
+    #[arg(short, long)]
+    /// Include issues in a state that is normally left out, i.e. Backlog. Can be repeated
+    include_state: Vec<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueComment {
+    #[arg(short, long)]
+    /// Comma separated issue identifiers or UUIDs, i.e. ENG-1,ENG-2. Defaults to issue for current branch
+    ids: Option<String>,
+
+    #[arg(short, long)]
+    /// Body of the comment, use - to read from stdin
+    body: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    /// If Linear can't be reached, queue the comment to be posted by lnr sync
+    queue: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueExport {
+    #[arg(required_unless_present = "dir")]
+    /// Issue identifier or UUID, i.e. ENG-12
+    identifier: Option<String>,
+
+    #[arg(short, long, value_enum, default_value_t = issue::ExportFormat::Md)]
+    /// File format to export to
+    format: issue::ExportFormat,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Include comments and replies
+    include_comments: bool,
+
+    #[arg(short, long)]
+    /// Path of file to write, defaults to <identifier>.<format> in current directory
+    output: Option<String>,
+
+    #[arg(short, long, conflicts_with_all = ["identifier", "output", "format"])]
+    /// Write a Markdown file with front matter for each issue in a team to this directory
+    dir: Option<String>,
+
+    #[arg(short = 'e', long, requires = "dir")]
+    /// Team name, with --dir
+    team: Option<String>,
+
+    #[arg(long, default_value_t = false, requires = "dir")]
+    /// Don't prompt for project, with --dir
+    noproject: bool,
+
+    #[arg(short, long, default_value_t = false, requires = "dir")]
+    /// Include issues in every state, such as Backlog and Done, with --dir
+    all_states: bool,
+
+    #[arg(long, requires = "dir")]
+    /// Include issues in a state that is normally left out, i.e. Backlog, with --dir. Can be repeated
+    include_state: Vec<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueTransition {
+    /// Issue identifier or UUID, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueBulk {
+    #[arg(short, long)]
+    /// Comma separated issue identifiers or UUIDs, i.e. ENG-1,ENG-2. Read from stdin if not provided
+    ids: Option<String>,
+
+    #[arg(short, long, required_unless_present = "label")]
+    /// State name to move each issue to, matched in the issue's team
+    state: Option<String>,
+
+    #[arg(short, long)]
+    /// Label name to add, existing labels are kept. Can be repeated
+    label: Vec<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueRestore {
+    /// Issue identifier or UUID, i.e. ENG-12
+    identifier: Option<String>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum IssueProjectCommands {
+    #[clap(alias = "s")]
+    /// (s) Add the issue for current branch to a project, and optionally a milestone of the project
+    Set(IssueProjectSet),
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueProjectSet {
+    /// Issue identifier or UUID, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+
+    #[arg(short, long)]
+    /// Project name, or None to remove the issue from its project. You will be prompted if this isn't provided
+    project: Option<String>,
+
+    #[arg(short, long)]
+    /// Milestone name, or None. You will be prompted when the project has milestones and this isn't provided
+    milestone: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueRequests {
+    /// Issue identifier or UUID, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueCheckout {
+    /// Issue identifier or UUID, i.e. ENG-12. You will be prompted to select an issue if this isn't provided
+    identifier: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Check the branch out in its own git worktree instead of switching branches
+    worktree: bool,
+
+    #[arg(short, long, requires = "worktree")]
+    /// Where to create the worktree, defaults to beside the repository
+    path: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueXref {
+    /// Issue identifier or UUID, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+
+    #[arg(short, long)]
+    /// URL of the item in the other tracker
+    url: String,
+
+    #[arg(short, long)]
+    /// Title to show for the cross-reference, i.e. GH#99. Defaults to the URL
+    title: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TimeStart {
+    /// Issue identifier or UUID, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TimeStop {
+    #[arg(short, long)]
+    /// What the time was spent on, added to the comment
+    note: Option<String>,
+
+    #[arg(short, long)]
+    /// Label to add to the issue, i.e. billable
+    label: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TimeLog {
+    /// Time spent, i.e. 1h30m, 45m, or 1.5h
+    duration: String,
+
+    /// Issue identifier or UUID, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+
+    #[arg(short, long)]
+    /// What the time was spent on, added to the comment
+    note: Option<String>,
+
+    #[arg(short, long)]
+    /// Label to add to the issue, i.e. billable
+    label: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TimeReport {}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueAttach {
+    /// Issue identifier or UUID, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+
+    #[arg(
+        short,
+        long,
+        required_unless_present_any = ["url", "pr"],
+        conflicts_with_all = ["url", "pr"]
+    )]
+    /// Path of a local file to upload
+    file: Option<PathBuf>,
+
+    #[arg(short, long, conflicts_with = "pr")]
+    /// URL to attach
+    url: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    /// Attach the pull request for current branch, found using the GitHub CLI (gh)
+    pr: bool,
+
+    #[arg(short, long)]
+    /// Title to show for the attachment. Defaults to the file name or URL
+    title: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueMerge {
+    /// Identifier or UUID of the duplicate issue, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+
+    #[arg(short, long)]
+    /// Identifier or UUID of the issue to keep. You will be prompted to select an issue if this isn't provided
+    into: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueMove {
+    #[arg(short, long)]
+    /// Name of the team to move the issue to
+    team: Option<String>,
+
+    #[arg(short, long)]
+    /// Project name, or None to remove the issue from its project
+    project: Option<String>,
+
+    #[arg(short, long, requires = "team")]
+    /// State in the new team, or in the current one when the team doesn't change. Defaults to the state with the same name as the current one
+    state: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueDuplicate {
+    /// Identifier or UUID of the issue to copy, i.e. ENG-12. Defaults to issue for current branch
+    identifier: Option<String>,
+
+    #[arg(
+        short = 'i',
+        long,
+        default_value_t = false,
+        conflicts_with = "identifier"
+    )]
+    /// Select the issue to copy from your issues
+    select: bool,
+
+    #[arg(short = 'e', long)]
+    /// Team to create the copy in, defaults to the team of the original
+    team: Option<String>,
+
+    #[arg(short, long)]
+    /// State of the copy, defaults to the team's default state with --yes
+    state: Option<String>,
+
+    #[arg(short, long, default_value_t = false)]
+    /// Relate the copy to the original
+    link: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct IssueLink {
+    #[arg(value_enum)]
+    /// How the issue for current branch relates to the other issue
+    kind: relation::Kind,
+
+    /// Identifier or UUID of the other issue, i.e. ENG-12. You will be prompted to select an issue if this isn't provided
+    identifier: Option<String>,
+}
+
+fn main() {
+    let cli = parse_cli();
+    if cli.no_input {
+        input::disable_prompts();
+    }
+    if cli.yes {
+        input::use_defaults();
+    }
+    if cli.quiet {
+        verbosity::set(Verbosity::Quiet);
+    } else if cli.debug || std::env::var("LNR_LOG").is_ok_and(|level| level == "debug") {
+        verbosity::set(Verbosity::Debug);
+    } else if cli.verbose {
+        verbosity::set(Verbosity::Verbose);
+    }
+    if cli.refresh {
+        if let Some(Err(e)) = cache::dir().map(|dir| cache::clear(&dir)) {
+            println!("{}", e.red());
+            std::process::exit(1);
+        }
+    }
+
+    // Org and config commands don't talk to the API, so a version check would only slow them down
+    // The notice would end up in piped output
+    let version_check = match &cli.command {
+        _ if cli.quiet || !std::io::stdout().is_terminal() => None,
+        Commands::Org(_) | Commands::Config(_) => None,
+        _ => fetch_config(&cli)
+            .ok()
+            .filter(|config| config.version_check != Some(false))
+            .map(spawn_version_check),
+    };
+
+    let result = match &cli.command {
+        Commands::Issue(IssueCommands::Create(args)) => issue_create(cli.clone(), args),
+        Commands::Issue(IssueCommands::Edit(args)) => issue_edit(cli.clone(), args),
+        Commands::Issue(IssueCommands::View(args)) => issue_view(cli.clone(), args),
+        Commands::Issue(IssueCommands::List(args)) => issue_list(cli.clone(), args),
+        Commands::Issue(IssueCommands::Stats(args)) => issue_stats(cli.clone(), args),
+        Commands::Issue(IssueCommands::Comment(args)) => issue_comment(cli.clone(), args),
+        Commands::Issue(IssueCommands::Export(args)) => issue_export(cli.clone(), args),
+        Commands::Issue(IssueCommands::Estimate(args)) => issue_estimate(cli.clone(), args),
+        Commands::Issue(IssueCommands::Due(args)) => issue_due(cli.clone(), args),
+        Commands::Issue(IssueCommands::Checkout(args)) => issue_checkout(cli.clone(), args),
+        Commands::Issue(IssueCommands::Link(args)) => issue_link(cli.clone(), args),
+        Commands::Issue(IssueCommands::Xref(args)) => issue_xref(cli.clone(), args),
+        Commands::Issue(IssueCommands::Merge(args)) => issue_merge(cli.clone(), args),
+        Commands::Issue(IssueCommands::Duplicate(args)) => issue_duplicate(cli.clone(), args),
+        Commands::Issue(IssueCommands::Move(args)) => issue_move(cli.clone(), args),
+        Commands::Issue(IssueCommands::Todo(args)) => {
+            issue_transition(cli.clone(), args, "unstarted")
+        }
+        Commands::Issue(IssueCommands::Progress(args)) => {
+            issue_transition(cli.clone(), args, "started")
+        }
+        Commands::Issue(IssueCommands::Done(args)) => {
+            issue_transition(cli.clone(), args, "completed")
+        }
+        Commands::Issue(IssueCommands::Cancel(args)) => {
+            issue_transition(cli.clone(), args, "canceled")
+        }
+        Commands::Issue(IssueCommands::Bulk(args)) => issue_bulk(cli.clone(), args),
+        Commands::Issue(IssueCommands::Restore(args)) => issue_restore(cli.clone(), args),
+        Commands::Issue(IssueCommands::Project(IssueProjectCommands::Set(args))) => {
+            issue_project_set(cli.clone(), args)
+        }
+        Commands::Issue(IssueCommands::Requests(args)) => issue_requests(cli.clone(), args),
+        Commands::Issue(IssueCommands::Attach(args)) => issue_attach(cli.clone(), args),
+        Commands::Issue(IssueCommands::Time(TimeCommands::Start(args))) => {
+            time_start(cli.clone(), args)
+        }
+        Commands::Issue(IssueCommands::Time(TimeCommands::Stop(args))) => {
+            time_stop(cli.clone(), args)
+        }
+        Commands::Issue(IssueCommands::Time(TimeCommands::Log(args))) => {
+            time_log(cli.clone(), args)
+        }
+        Commands::Issue(IssueCommands::Time(TimeCommands::Report(args))) => {
+            time_report(cli.clone(), args)
+        }
+
+        Commands::Org(OrgCommands::Add(args)) => org_add(cli.clone(), args),
+        Commands::Org(OrgCommands::Remove(args)) => org_remove(cli.clone(), args),
+        Commands::Org(OrgCommands::List(args)) => org_list(cli.clone(), args),
+        Commands::Org(OrgCommands::Rename(args)) => org_rename(cli.clone(), args),
+        Commands::Org(OrgCommands::SetToken(args)) => org_set_token(cli.clone(), args),
+
+        Commands::Template(TemplateCommands::Evaluate(args)) => {
+            template_evaluate(cli.clone(), args)
+        }
+
+        Commands::Report(ReportCommands::CreatedVsCompleted(args)) => {
+            report_created_vs_completed(cli.clone(), args)
+        }
+        Commands::Report(ReportCommands::Standup(args)) => report_standup(cli.clone(), args),
+        Commands::Report(ReportCommands::Changelog(args)) => report_changelog(cli.clone(), args),
+
+        Commands::Member(MemberCommands::List(args)) => member_list(cli.clone(), args),
+        Commands::Project(ProjectCommands::Create(args)) => project_create(cli.clone(), args),
+        Commands::Project(ProjectCommands::List(args)) => project_list(cli.clone(), args),
+        Commands::Label(LabelCommands::Create(args)) => label_create(cli.clone(), args),
+        Commands::Label(LabelCommands::Rename(args)) => label_rename(cli.clone(), args),
+        Commands::Label(LabelCommands::Archive(args)) => label_archive(cli.clone(), args),
+        Commands::Team(TeamCommands::State(StateCommands::List(args))) => {
+            state_list(cli.clone(), args)
+        }
+        Commands::Team(TeamCommands::State(StateCommands::Create(args))) => {
+            state_create(cli.clone(), args)
+        }
+        Commands::Team(TeamCommands::State(StateCommands::Rename(args))) => {
+            state_rename(cli.clone(), args)
+        }
+
+        Commands::Pr(PrCommands::Create(args)) => pr_create(cli.clone(), args),
+        Commands::Config(ConfigCommands::Edit(args)) => config_edit(cli.clone(), args),
+        Commands::Config(ConfigCommands::Get(args)) => config_get(cli.clone(), args),
+        Commands::Config(ConfigCommands::Set(args)) => config_set(cli.clone(), args),
+        Commands::Board(args) => board(cli.clone(), args),
+        Commands::Doctor(args) => doctor(cli.clone(), args),
+        Commands::Sync(args) => sync(cli.clone(), args),
+        Commands::Import(ImportCommands::Github(args)) => import_github(cli.clone(), args),
+        Commands::Roadmap(RoadmapCommands::List(args)) => roadmap_list(cli.clone(), args),
+        Commands::Roadmap(RoadmapCommands::View(args)) => roadmap_view(cli.clone(), args),
+        Commands::Roadmap(RoadmapCommands::AddProject(args)) => {
+            roadmap_add_project(cli.clone(), args)
+        }
+        Commands::Doc(DocCommands::List(args)) => doc_list(cli.clone(), args),
+        Commands::Doc(DocCommands::View(args)) => doc_view(cli.clone(), args),
+        Commands::Cycle(CycleCommands::Report(args)) => cycle_report(cli.clone(), args),
+        Commands::Api(args) => api(cli.clone(), args),
+    };
+
+    if let Some(version_check) = version_check {
+        print_version_check(version_check);
+    }
+
+    match result {
+        Ok(text) => {
+            println!("{text}");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            println!("{}", e.red());
+            std::process::exit(1);
+        }
+    }
+}
+
+// --- ISSUES ---
+
+fn issue_create(cli: Cli, args: &IssueCreate) -> Result<String, String> {
+    let IssueCreate {
+        title,
+        description,
+        description_file,
+        team,
+        noproject,
+        priority,
+        state,
+        estimate,
+        noestimate,
+        label,
+        nolabels,
+        assignee,
+        cycle,
+        full,
+        due,
+        parent,
+        parent_branch,
+        queue,
+        template,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let org_name = fetch_org_name(&cli, &config)?;
+    let token = config.token(&org_name)?;
+    let config = Config {
+        offline_queue: queue.then(|| org_name.clone()),
+        ..config
+    };
+    let due_date = due
+        .as_ref()
+        .map(|due| date::parse(due, date::today()))
+        .transpose()?;
+    let template = match template {
+        Some(name) => config.issue_template(name)?,
+        None => config::IssueTemplate::default(),
+    };
+    let skeleton = template.description.clone().unwrap_or_default();
+
+    // Fetch from the API while the title and description are being written
+    let (prefetched, title, description) = std::thread::scope(|scope| {
+        let prefetch = scope.spawn(|| prefetch_for_create(&config, &token, team, *parent_branch));
+        let title = template_title(title, &template.title);
+        let description = match description_file {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| format!("Could not read description from {path}: {e}")),
+            None if description.is_none() && input::defaults_enabled() => Ok(skeleton),
+            None => fetch_editor(description, "Description", "--description", &skeleton),
+        };
+        let prefetched = prefetch
+            .join()
+            .unwrap_or_else(|_| Err(String::from("Prefetch thread panicked")));
+        (prefetched, title, description)
+    });
+    let title = title?;
+    let description = processor::description(&config, description?)?;
+
+    // Lookups that fail before the issue can be queued would lose the title and description
+    let mut submitting = false;
+    let submit = || -> Result<issue::Issue, String> {
+        let Prefetched {
+            viewer,
+            states,
+            parent_id: parent_branch_id,
+        } = prefetched?;
+        let parent_id = parent_branch_id.or(parent.clone());
+
+        let team = viewer::team(&viewer, team)?;
+        let states = match states {
+            Some((team_id, states)) if team_id == team.id => states,
+            _ => team::get_states(&config, &token, &team)?,
+        };
+        let state = select_state(&config, &token, &team, states, state)?;
+        let priority = get_priority(&priority.or(template.priority))?;
+        let estimate = get_estimate(&team, estimate, *noestimate)?;
+        let project = match noproject {
+            true => None,
+            false => get_project(&Some(team.clone()))?,
+        };
+        let mut label_names = config.default_labels(Some(&org_name), Some(&team.name));
+        for name in template.labels.unwrap_or_default() {
+            if !label_names.contains(&name) {
+                label_names.push(name);
+            }
+        }
+        let mut label_ids = label::ids(&config, &token, &team, label_names)?;
+        for id in get_labels(&config, &token, &team, label, *nolabels)? {
+            if !label_ids.contains(&id) {
+                label_ids.push(id);
+            }
+        }
+        let assignee_id = match (assignee, full) {
+            (None, false) => Some(viewer.id.clone()),
+            _ => get_assignee(&config, &token, &team, &viewer, assignee)?,
+        };
+        let cycle_id = match (cycle, full) {
+            (None, false) => None,
+            _ => get_cycle(&config, &token, &team, cycle)?,
+        };
+        let due_date = match (due_date, full) {
+            (None, true) => get_date("Due date", "--due")?,
+            (due_date, _) => due_date,
+        };
+
+        submitting = true;
+        issue::create(
+            &config,
+            &token,
+            title.clone(),
+            description.clone(),
+            team,
+            project,
+            state,
+            assignee_id,
+            priority,
+            estimate,
+            due_date,
+            parent_id,
+            label_ids,
+            cycle_id,
+        )
+    };
+    let issue = match submit() {
+        Err(e) if *queue && !submitting => return Err(save_draft(&title, &description, e)),
+        result => result?,
+    };
+    hooks::run(&config, hooks::Event::Create, &issue, issue.created())
+}
+
+/// The error for issue create --queue when a lookup fails before the issue could be queued,
+/// saving the description so that it isn't lost
+fn save_draft(title: &str, description: &str, error: String) -> String {
+    match queue::drafts_dir().and_then(|dir| queue::save_draft(&dir, description)) {
+        Ok(path) => {
+            let path = path.display().to_string();
+            format!(
+                "{error}\n\nSaved the description to {path}, create the issue once Linear can be reached with\n{}",
+                color::cyan_string(&format!(
+                    "lnr issue create --title {title:?} --description-file {path:?}"
+                ))
+            )
+        }
+        Err(e) => format!("{error}\n\nCould not save the description: {e}"),
+    }
+}
+
+/// What issue create needs from the API before the issue can be submitted
+struct Prefetched {
+    viewer: viewer::Viewer,
+    /// States of the team, when it is known without prompting
+    states: Option<(String, Vec<State>)>,
+    /// Id of the issue for the current branch, when it is the parent
+    parent_id: Option<String>,
+}
+
+/// Runs alongside the title and description prompts, so must not prompt itself
+fn prefetch_for_create(
+    config: &Config,
+    token: &str,
+    team_name: &Option<String>,
+    parent_branch: bool,
+) -> Result<Prefetched, String> {
+    // A spinner would draw over the prompt
+    let config = Config {
+        spinners: Some(false),
+        ..config.clone()
+    };
+    let parent_id = match parent_branch {
+        true => Some(issue::branch_issue(&config, token, vcs::get_branch()?)?.id),
+        false => None,
+    };
+    let viewer = viewer::get_viewer(&config, token)?;
+    let team = match team_name {
+        Some(name) => viewer::team_by_name(&viewer, name).ok(),
+        None => match viewer::team_names(&viewer)?.as_slice() {
+            [name] => viewer::team_by_name(&viewer, name).ok(),
+            _ => None,
+        },
+    };
+    let states = match team {
+        Some(team) => Some((team.id.clone(), team::get_states(&config, token, &team)?)),
+        None => None,
+    };
+
+    Ok(Prefetched {
+        viewer,
+        states,
+        parent_id,
+    })
+}
+
+fn issue_view(cli: Cli, args: &IssueView) -> Result<String, String> {
+    let IssueView {
+        identifier,
+        select,
+        comments,
+        no_comments,
+        history,
+        watch,
+        interval,
+        format,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let comment_limit = match no_comments {
+        true => Some(0),
+        false => *comments,
+    };
+    let target = match (identifier, select) {
+        (Some(identifier), _) => issue::Target::Id(identifier.clone()),
+        (None, true) => issue::Target::Select,
+        (None, false) => issue::Target::Branch(vcs::get_branch()?),
+    };
+    if *watch {
+        let seconds = interval
+            .or(config.watch_interval_seconds)
+            .unwrap_or(DEFAULT_WATCH_INTERVAL_SECONDS)
+            .max(1);
+        let interval = Duration::from_secs(seconds);
+        issue::watch(&config, &token, target, comment_limit, *history, interval)
+    } else {
+        issue::view(
+            &config,
+            &token,
+            target,
+            comment_limit,
+            *history,
+            format.clone(),
+        )
+    }
+}
+
+fn issue_link(cli: Cli, args: &IssueLink) -> Result<String, String> {
+    let IssueLink { kind, identifier } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let branch = vcs::get_branch()?;
+    let issue = issue::branch_issue(&config, &token, branch)?;
+    let other_id = match identifier {
+        Some(identifier) => identifier.clone(),
+        None => issue::select_issue(&config, &token)?.id,
+    };
+
+    relation::create(&config, &token, issue.id, other_id, kind.clone())
+}
+
+fn issue_xref(cli: Cli, args: &IssueXref) -> Result<String, String> {
+    let IssueXref {
+        identifier,
+        url,
+        title,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let issue_id = match identifier {
+        Some(identifier) => identifier.clone(),
+        None => issue::branch_issue(&config, &token, vcs::get_branch()?)?.id,
+    };
+
+    attachment::create_xref(&config, &token, issue_id, url.clone(), title.clone())
+}
+
+fn issue_restore(cli: Cli, args: &IssueRestore) -> Result<String, String> {
+    let IssueRestore { identifier } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let id = match identifier {
+        Some(identifier) => identifier.clone(),
+        None => issue::select_archived_issue(&config, &token)?.id,
+    };
+    issue::restore(&config, &token, id)
+}
+
+fn issue_project_set(cli: Cli, args: &IssueProjectSet) -> Result<String, String> {
+    let IssueProjectSet {
+        identifier,
+        project,
+        milestone,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+
+    let issue = match identifier {
+        Some(identifier) => issue::get(&config, &token, identifier.clone())?,
+        None => issue::branch_issue(&config, &token, vcs::get_branch()?)?,
+    };
+    let team = match &issue.team {
+        Some(team) => viewer::team_by_name(&viewer, &team.name)?,
+        None => viewer::team(&viewer, &None)?,
+    };
+    let team = Some(team);
+    let project = match project {
+        Some(name) => viewer::project(&team, name.clone())?,
+        None => get_project(&team)?,
+    };
+    let Some(project) = project else {
+        let changes = issue::Changes {
+            project: Some(None),
+            ..Default::default()
+        };
+        let url = issue::update(&config, &token, issue.id, changes)?;
+        return Ok(format!(
+            "Removed {} from its project\n{url}",
+            issue.identifier
+        ));
+    };
+
+    let milestone = get_milestone(&config, &token, &project, milestone)?;
+    let changes = issue::Changes {
+        project: Some(Some(project.clone())),
+        milestone_id: Some(milestone.as_ref().map(|m| m.id.clone())),
+        ..Default::default()
+    };
+    let url = issue::update(&config, &token, issue.id, changes)?;
+    let milestone = milestone
+        .map(|m| format!(", milestone {}", m.name))
+        .unwrap_or_default();
+    Ok(format!(
+        "Moved {} to project {}{milestone}\n{url}",
+        issue.identifier, project.name
+    ))
+}
+
+/// The named milestone of a project, or a prompt for one when the project has any
+fn get_milestone(
+    config: &Config,
+    token: &str,
+    project: &Project,
+    milestone: &Option<String>,
+) -> Result<Option<project::Milestone>, String> {
+    match milestone.as_deref() {
+        Some("None") => Ok(None),
+        Some(name) => {
+            let milestones = project::milestones(config, token, &project.id)?;
+            project::find_milestone(milestones, name).map(Some)
+        }
+        None if input::defaults_enabled() => Ok(None),
+        None => {
+            let milestones = project::milestones(config, token, &project.id)?;
+            if milestones.is_empty() {
+                return Ok(None);
+            }
+            let mut names = milestones
+                .iter()
+                .map(|m| m.name.clone())
+                .collect::<Vec<String>>();
+            names.insert(0, String::from("None"));
+            input::require("--milestone")?;
+            let name = input::select("Select milestone", names)?;
+            Ok(milestones.into_iter().find(|m| m.name == name))
+        }
+    }
+}
+
+fn issue_bulk(cli: Cli, args: &IssueBulk) -> Result<String, String> {
+    let IssueBulk { ids, state, label } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let identifiers = match ids {
+        Some(ids) => bulk::identifiers(ids),
+        None => {
+            if std::io::stdin().is_terminal() {
+                return Err(String::from(
+                    "Pipe issue identifiers to stdin or provide them with --ids",
+                ));
+            }
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("Could not read from stdin: {e}"))?;
+            bulk::identifiers(&text)
+        }
+    };
+    let update = bulk::Update {
+        state: state.clone(),
+        labels: label.clone(),
+        ..bulk::Update::default()
+    };
+
+    let result = bulk::update(&config, &token, identifiers, &update);
+    cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+    result
+}
+
+fn issue_requests(cli: Cli, args: &IssueRequests) -> Result<String, String> {
+    let IssueRequests { identifier } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let issue_id = match identifier {
+        Some(identifier) => identifier.clone(),
+        None => issue::viewed_branch_issue(&config, &token, vcs::get_branch()?)?.id,
+    };
+
+    customer::list(&config, &token, issue_id)
+}
+
+fn issue_attach(cli: Cli, args: &IssueAttach) -> Result<String, String> {
+    let IssueAttach {
+        identifier,
+        file,
+        url,
+        pr,
+        title,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let issue_id = match identifier {
+        Some(identifier) => identifier.clone(),
+        None => issue::branch_issue(&config, &token, vcs::get_branch()?)?.id,
+    };
+
+    match (file, url) {
+        (Some(file), _) => attachment::create_file(&config, &token, issue_id, file, title.clone()),
+        (None, Some(url)) => {
+            attachment::create_link(&config, &token, issue_id, url.clone(), title.clone())
+        }
+        (None, None) if *pr => {
+            let pull_request = github::pull_request(&vcs::get_branch()?)?;
+            let title = title
+                .clone()
+                .unwrap_or(format!("#{} {}", pull_request.number, pull_request.title));
+            attachment::create_link(&config, &token, issue_id, pull_request.url, Some(title))
+        }
+        (None, None) => Err(String::from("Provide --file, --url, or --pr")),
+    }
+}
+
+fn issue_merge(cli: Cli, args: &IssueMerge) -> Result<String, String> {
+    let IssueMerge { identifier, into } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let duplicate_id = match identifier {
+        Some(identifier) => identifier.clone(),
+        None => issue::branch_issue(&config, &token, vcs::get_branch()?)?.id,
+    };
+    let canonical_id = match into {
+        Some(into) => into.clone(),
+        None => {
+            input::require("--into")?;
+            issue::select_issue(&config, &token)?.id
+        }
+    };
+
+    merge::merge(&config, &token, duplicate_id, canonical_id)
+}
+
+fn issue_duplicate(cli: Cli, args: &IssueDuplicate) -> Result<String, String> {
+    let IssueDuplicate {
+        identifier,
+        select,
+        team,
+        state,
+        link,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+
+    let original = match (identifier, select) {
+        (Some(identifier), _) => issue::get(&config, &token, identifier.clone())?,
+        (None, true) => issue::select_issue(&config, &token)?,
+        (None, false) => issue::branch_issue(&config, &token, vcs::get_branch()?)?,
+    };
+    let team = match (team, &original.team) {
+        (None, Some(original_team)) => viewer::team_by_name(&viewer, &original_team.name)?,
+        _ => viewer::team(&viewer, team)?,
+    };
+    let states = team::get_states(&config, &token, &team)?;
+    let state = select_state(&config, &token, &team, states, state)?;
+
+    let copy = issue::duplicate(&config, &token, &original, team, state)?;
+    let mut output = format!(
+        "Copied {} to {}\n{}",
+        original.identifier,
+        copy.identifier,
+        copy.created()
+    );
+    if *link {
+        let related = relation::create(
+            &config,
+            &token,
+            copy.id.clone(),
+            original.id.clone(),
+            relation::Kind::Related,
+        )?;
+        output = format!("{output}\n{related}");
+    }
+    hooks::run(&config, hooks::Event::Create, &copy, output)
+}
+
+fn issue_move(cli: Cli, args: &IssueMove) -> Result<String, String> {
+    let IssueMove {
+        team,
+        project,
+        state,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+
+    let branch = vcs::get_branch()?;
+    let issue = issue::branch_issue(&config, &token, branch)?;
+    let current_team = match &issue.team {
+        Some(team) => viewer::team_by_name(&viewer, &team.name)?,
+        None => viewer::team(&viewer, &None)?,
+    };
+
+    let no_flags = team.is_none() && project.is_none();
+    let new_team = match team {
+        Some(_) => Some(viewer::team(&viewer, team)?),
+        None if no_flags => Some(viewer::team(&viewer, &None)?),
+        None => None,
+    };
+
+    let mut changes = issue::Changes::default();
+    let project_team = match new_team {
+        Some(new_team) if new_team.id != current_team.id => {
+            let states = team::get_states(&config, &token, &new_team)?;
+            let same_name = states.iter().find(|s| s.name == issue.state.name).cloned();
+            changes.state = match (state, same_name) {
+                (None, Some(same_name)) => Some(same_name),
+                _ => Some(select_state(&config, &token, &new_team, states, state)?),
+            };
+            changes.team = Some(new_team.clone());
+            new_team
+        }
+        _ => {
+            if state.is_some() {
+                let states = team::get_states(&config, &token, &current_team)?;
+                changes.state = Some(select_state(&config, &token, &current_team, states, state)?);
+            }
+            current_team
+        }
+    };
+
+    changes.project = match project {
+        Some(project_name) => Some(viewer::project(
+            &Some(project_team),
+            project_name.to_owned(),
+        )?),
+        None if no_flags => Some(get_project(&Some(project_team))?),
+        None => None,
+    };
+
+    let state = changes.state.clone();
+    let url = issue::update(&config, &token, issue.id.clone(), changes)?;
+    hooks::state_changed(&config, &token, issue.id, &state, url)
+}
+
+/// Move an issue to the first state of a type in its team's workflow, such as started
+fn issue_transition(cli: Cli, args: &IssueTransition, state_type: &str) -> Result<String, String> {
+    let IssueTransition { identifier } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+
+    let issue = match identifier {
+        Some(identifier) => issue::get(&config, &token, identifier.clone())?,
+        None => issue::branch_issue(&config, &token, vcs::get_branch()?)?,
+    };
+    let team = match &issue.team {
+        Some(team) => viewer::team_by_name(&viewer, &team.name)?,
+        None => viewer::team(&viewer, &None)?,
+    };
+    let states = team::get_states(&config, &token, &team)?;
+    let state = team::state_of_type(&team, states, state_type)?;
+    verbosity::detail(&format!("State: {}", state.name));
+
+    let changes = issue::Changes {
+        state: Some(state.clone()),
+        ..Default::default()
+    };
+    let url = issue::update(&config, &token, issue.id.clone(), changes)?;
+    let output = format!("Moved {} to {}\n{url}", issue.identifier, state.name);
+    hooks::state_changed(&config, &token, issue.id, &Some(state), output)
+}
+
+fn issue_edit(cli: Cli, args: &IssueEdit) -> Result<String, String> {
+    let IssueEdit {
+        identifier,
+        title,
+        description,
+        state,
+        priority,
+        project,
+        assignee,
+        estimate,
+        label,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+
+    let issue = match identifier {
+        Some(identifier) => issue::get(&config, &token, identifier.clone())?,
+        None => issue::branch_issue(&config, &token, vcs::get_branch()?)?,
+    };
+    let team = match &issue.team {
+        Some(team) => viewer::team_by_name(&viewer, &team.name)?,
+        None => viewer::team(&viewer, &None)?,
+    };
+
+    let no_flags = title.is_none()
+        && description.is_none()
+        && state.is_none()
+        && priority.is_none()
+        && project.is_none()
+        && assignee.is_none()
+        && estimate.is_none()
+        && label.is_empty();
+
+    let mut changes = issue::Changes::default();
+    let mut edited_description = false;
+    if no_flags {
+        input::require("a flag for the field to change, i.e. --title")?;
+        loop {
+            match input::select("Select a field to edit", issue::all_fields())? {
+                Field::Save => break,
+                Field::Description => {
+                    changes.description = Some(issue::edit_description(&config, &issue)?);
+                    edited_description = true;
+                }
+                Field::Title => changes.title = Some(input::string("Title")?),
+                Field::State => changes.state = Some(get_state(&config, &token, &team, &None)?),
+                Field::Priority => changes.priority = Some(get_priority(&None)?),
+                Field::Project => changes.project = Some(get_project(&Some(team.clone()))?),
+                Field::Assignee => {
+                    changes.assignee_id =
+                        Some(get_assignee(&config, &token, &team, &viewer, &None)?)
+                }
+                Field::Estimate => changes.estimate = Some(get_estimate(&team, &None, false)?),
+                Field::Labels => {
+                    changes.added_label_ids = get_labels(&config, &token, &team, &[], false)?
+                }
+            }
+        }
+    } else {
+        changes.title = title.clone();
+        if description.is_some() {
+            let description = fetch_editor(description, "Description", "--description", "")?;
+            changes.description = Some(processor::description(&config, description)?);
+        }
+        if state.is_some() {
+            changes.state = Some(get_state(&config, &token, &team, state)?);
+        }
+        if priority.is_some() {
+            changes.priority = Some(get_priority(priority)?);
+        }
+        if let Some(project_name) = project {
+            changes.project = Some(viewer::project(
+                &Some(team.clone()),
+                project_name.to_owned(),
+            )?);
+        }
+        if assignee.is_some() {
+            changes.assignee_id = Some(get_assignee(&config, &token, &team, &viewer, assignee)?);
+        }
+        if estimate.is_some() {
+            changes.estimate = Some(
+                estimate
+                    .as_ref()
+                    .map(|e| estimate::parse(&team, e))
+                    .transpose()?,
+            );
+        }
+        changes.added_label_ids = label::ids(&config, &token, &team, label.clone())?;
+    }
+
+    // Someone else may have changed the description while it was open in the editor
+    if let (true, Some(mine)) = (edited_description, changes.description.take()) {
+        let base = issue.description.clone().unwrap_or_default();
+        let latest = issue::get(&config, &token, issue.id.clone())?;
+        let theirs = latest.description.unwrap_or_default();
+        changes.description = Some(conflict::resolve(&base, &theirs, mine)?);
+    }
+
+    let state = changes.state.clone();
+    let url = issue::update(&config, &token, issue.id.clone(), changes)?;
+    hooks::state_changed(&config, &token, issue.id, &state, url)
+}
+
+fn issue_estimate(cli: Cli, args: &IssueEstimate) -> Result<String, String> {
+    let IssueEstimate { estimate } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+
+    let branch = vcs::get_branch()?;
+    let issue = issue::branch_issue(&config, &token, branch.clone())?;
+    let team = match &issue.team {
+        Some(team) => viewer::team_by_name(&viewer, &team.name)?,
+        None => viewer::team(&viewer, &None)?,
+    };
+    let estimate = estimate::parse(&team, estimate)?;
+    issue::estimate(&config, &token, branch, estimate)
+}
+
+fn issue_due(cli: Cli, args: &IssueDue) -> Result<String, String> {
+    let IssueDue { due, clear } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let due_date = if *clear {
+        None
+    } else {
+        let due = fetch_string(due, "Due date", "due date argument")?;
+        Some(date::parse(&due, date::today())?)
+    };
+    let branch = vcs::get_branch()?;
+    issue::due(&config, &token, branch, due_date)
+}
+
+fn issue_list(cli: Cli, args: &IssueList) -> Result<String, String> {
+    let IssueList {
+        team,
+        noteam,
+        noproject,
+        sort,
+        reverse,
+        breaching,
+        archived,
+        all_states,
+        include_state,
+        initiative,
+        assignee,
+        creator,
+        output,
+        format,
+        interactive,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let org_name = fetch_org_name(&cli, &config)?;
+    let token = config.token(&org_name)?;
+
+    let viewer = viewer::get_viewer(&config, &token)?;
+
+    let team = match *noteam {
+        true => None,
+        false => Some(viewer::team(&viewer, team)?),
+    };
+    let project = match *noproject || initiative.is_some() {
+        true => None,
+        false => get_project(&team)?,
+    };
+    let project_ids = match initiative {
+        Some(name) => Some(initiative::project_ids(&config, &token, name)?),
+        None => None,
+    };
+
+    let assignee = match (assignee, creator) {
+        (None, Some(_)) => issue::Assignee::Any,
+        _ => list_assignee(&config, &token, &team, &viewer, assignee)?,
+    };
+    let creator_id = match creator {
+        Some(creator) => Some(member_id(&config, &token, &team, &viewer, creator)?),
+        None => None,
+    };
+
+    let team_name = team.as_ref().map(|t| &t.name);
+    let configured_states = config.excluded_states(Some(&org_name), team_name);
+    let filter = issue::Filter {
+        assignee,
+        creator_id,
+        excluded_states: issue::excluded_states(
+            configured_states,
+            *all_states || *archived,
+            include_state,
+        ),
+        team,
+        project,
+        project_ids,
+        breaching: *breaching,
+        archived: *archived,
+    };
+    let sort = sort.clone().or(breaching.then_some(issue::Sort::Breach));
+
+    if *interactive {
+        let mut issues = issue::get_issues(&config, &token, filter)?;
+        issue::sort_issues(&mut issues, &sort, *reverse);
+        let result = bulk::interactive(&config, &token, issues);
+        cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+        return result;
+    }
+
+    issue::list(
+        &config,
+        &token,
+        filter,
+        sort,
+        *reverse,
+        output.clone(),
+        format.clone(),
+    )
+}
+
+fn issue_stats(cli: Cli, args: &IssueStats) -> Result<String, String> {
+    let IssueStats {
+        team,
+        noteam,
+        everyone,
+        all_states,
+        include_state,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let org_name = fetch_org_name(&cli, &config)?;
+    let token = config.token(&org_name)?;
+
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let team = match *noteam {
+        true => None,
+        false => Some(viewer::team(&viewer, team)?),
+    };
+
+    let team_name = team.as_ref().map(|t| &t.name);
+    let configured_states = config.excluded_states(Some(&org_name), team_name);
+    let filter = issue::Filter {
+        assignee: match everyone {
+            true => issue::Assignee::Any,
+            false => issue::Assignee::User(viewer.id),
+        },
+        excluded_states: issue::excluded_states(configured_states, *all_states, include_state),
+        team,
+        ..issue::Filter::default()
+    };
+
+    stats::summary(&config, &token, filter)
+}
+
+fn issue_comment(cli: Cli, args: &IssueComment) -> Result<String, String> {
+    let IssueComment { ids, body, queue } = args;
+    let config = fetch_config(&cli)?;
+    let org_name = fetch_org_name(&cli, &config)?;
+    let token = config.token(&org_name)?;
+    let config = Config {
+        offline_queue: queue.then_some(org_name),
+        ..config
+    };
+
+    let identifiers = match ids {
+        Some(ids) => ids
+            .split(',')
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect::<Vec<String>>(),
+        None => {
+            let branch = vcs::get_branch()?;
+            vec![issue::branch_identifier(&config, &token, branch)?]
+        }
+    };
+    let body = fetch_editor(body, "Comment", "--body", "")?;
+
+    issue::comment(&config, &token, identifiers, body)
+}
+
+fn issue_export(cli: Cli, args: &IssueExport) -> Result<String, String> {
+    let IssueExport {
+        identifier,
+        format,
+        include_comments,
+        output,
+        dir,
+        team,
+        noproject,
+        all_states,
+        include_state,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let org_name = fetch_org_name(&cli, &config)?;
+    let token = config.token(&org_name)?;
+
+    let Some(identifier) = identifier else {
+        let dir = dir.clone().unwrap_or_default();
+        let viewer = viewer::get_viewer(&config, &token)?;
+        let team = viewer::team(&viewer, team)?;
+        let project = match noproject {
+            true => None,
+            false => get_project(&Some(team.clone()))?,
+        };
+        let configured_states = config.excluded_states(Some(&org_name), Some(&team.name));
+        let filter = issue::Filter {
+            excluded_states: issue::excluded_states(configured_states, *all_states, include_state),
+            team: Some(team),
+            project,
+            ..Default::default()
+        };
+        let paths = issue::export_dir(&config, &token, filter, *include_comments, &dir)?;
+        return Ok(format!("Exported {} issues to {dir}", paths.len()));
+    };
+
+    let path = issue::export(
+        &config,
+        &token,
+        identifier.clone(),
+        format.clone(),
+        *include_comments,
+        output.clone(),
+    )?;
+    Ok(format!("Exported {identifier} to {path}"))
+}
+
+fn issue_checkout(cli: Cli, args: &IssueCheckout) -> Result<String, String> {
+    let IssueCheckout {
+        identifier,
+        worktree,
+        path,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let branch = issue::branch_name(&config, &token, identifier.clone())?;
+    match worktree {
+        true => vcs::checkout_worktree(&branch, path),
+        false => vcs::checkout_branch(&branch),
+    }
+}
+
+// --- TIME ---
+
+fn time_start(cli: Cli, args: &TimeStart) -> Result<String, String> {
+    let TimeStart { identifier } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let identifier = match identifier {
+        Some(identifier) => issue::get(&config, &token, identifier.clone())?.identifier,
+        None => issue::branch_identifier(&config, &token, vcs::get_branch()?)?,
+    };
+    let path = time::path()?;
+    let mut log = time::Log::load(&path)?;
+    log.start(&identifier, chrono::Utc::now())?;
+    log.save(&path)?;
+    Ok(format!("Tracking time on {identifier}"))
+}
+
+fn time_stop(cli: Cli, args: &TimeStop) -> Result<String, String> {
+    let TimeStop { note, label } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let path = time::path()?;
+    let mut log = time::Log::load(&path)?;
+    let entry = log.stop(chrono::Utc::now(), note.clone())?;
+    // The session keeps running if the comment can't be posted, so stop can be tried again
+    let result = post_time(&config, &token, &entry, label)?;
+    log.save(&path)?;
+    Ok(result)
+}
+
+fn time_log(cli: Cli, args: &TimeLog) -> Result<String, String> {
+    let TimeLog {
+        duration,
+        identifier,
+        note,
+        label,
+    } = args;
+    let minutes = time::parse(duration)?;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+
+    let identifier = match identifier {
+        Some(identifier) => issue::get(&config, &token, identifier.clone())?.identifier,
+        None => issue::branch_identifier(&config, &token, vcs::get_branch()?)?,
+    };
+    let path = time::path()?;
+    let mut log = time::Log::load(&path)?;
+    let entry = log.log(&identifier, minutes, chrono::Utc::now(), note.clone());
+    let result = post_time(&config, &token, &entry, label)?;
+    log.save(&path)?;
+    Ok(result)
+}
+
+fn time_report(_cli: Cli, _args: &TimeReport) -> Result<String, String> {
+    let log = time::Log::load(&time::path()?)?;
+    Ok(log.report(date::today()))
+}
+
+/// Comment on the issue with the time logged, and add the label when there is one
+fn post_time(
+    config: &Config,
+    token: &str,
+    entry: &time::Entry,
+    label: &Option<String>,
+) -> Result<String, String> {
+    let issue = issue::get(config, token, entry.identifier.clone())?;
+    // Looked up first, so a missing label doesn't leave a comment behind that stop would post again
+    let label_ids = match label {
+        Some(label) => {
+            let team = issue
+                .team
+                .clone()
+                .ok_or_else(|| format!("Could not find the team of {}", issue.identifier))?;
+            label::ids(config, token, &team, vec![label.clone()])?
+        }
+        None => Vec::new(),
+    };
+    let url = issue::post_comment(config, token, issue.id.clone(), time::comment_body(entry))?;
+    if !label_ids.is_empty() {
+        let changes = issue::Changes {
+            added_label_ids: label_ids,
+            ..Default::default()
+        };
+        issue::update(config, token, issue.id.clone(), changes)?;
+    }
+    Ok(format!(
+        "Logged {} on {}\n{url}",
+        time::format(entry.minutes),
+        issue.identifier
+    ))
+}
+
+// --- ORGANIZATIONS ---
+
+fn org_add(cli: Cli, _args: &OrgAdd) -> Result<String, String> {
+    // The profile is created by adding its first organization, so it isn't required to exist yet
+    let mut config = Config {
+        profile: fetch_profile(&cli),
+        ..config::get_or_create(cli.config.clone())?
+    };
+    let name = input::string("Input organization name")?;
+    let token = input::string("Input organization token")?;
+    config.update(|c| c.add_organization(name, token))
+}
+
+fn org_list(cli: Cli, _args: &OrgList) -> Result<String, String> {
+    let config = fetch_config(&cli)?;
+    let orgs = config
+        .organizations
+        .into_iter()
+        .map(|(k, v)| format!("- {k}: {v}"))
+        .collect::<Vec<String>>();
+
+    if orgs.is_empty() {
+        Ok("No organizations in config".to_string())
+    } else {
+        let title = color::green_string("Organizations");
+        let orgs = orgs.join("\n");
+
+        Ok(format!("{title}\n\n{orgs}"))
+    }
+}
+
+fn org_remove(cli: Cli, _args: &OrgRemove) -> Result<String, String> {
+    let mut config = fetch_config(&cli)?;
+    let org_names = config.organization_names();
+    if org_names.is_empty() {
+        let command = color::cyan_string("org add");
+        Err(format!("Add an organization with {}", command))
+    } else {
+        let org_name = input::select("Select an organization", org_names)?;
+        config.update(|c| c.remove_organization(&org_name))
+    }
+}
+
+fn org_rename(cli: Cli, args: &OrgRename) -> Result<String, String> {
+    let OrgRename { name, new_name } = args;
+    let mut config = fetch_config(&cli)?;
+    let mut org_names = config.organization_names();
+    org_names.sort();
+
+    let name = match name {
+        Some(name) => name.clone(),
+        None if org_names.is_empty() => {
+            let command = color::cyan_string("org add");
+            return Err(format!("Add an organization with {command}"));
+        }
+        None => {
+            input::require("the organization name")?;
+            input::select("Select an organization", org_names.clone())?
+        }
+    };
+    if !org_names.contains(&name) {
+        return Err(format!(
+            "Organization {name} not found, options are: {}",
+            org_names.join(", ")
+        ));
+    }
+    let new_name = match new_name {
+        Some(new_name) => new_name.clone(),
+        None => {
+            input::require("the new name")?;
+            input::string("Input new organization name")?
+        }
+    };
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err(String::from("The new name can't be empty"));
+    }
+    if org_names.contains(&new_name) {
+        return Err(format!("Organization {new_name} already exists"));
+    }
+
+    config.update(|c| c.rename_organization(&name, new_name.clone()))?;
+    let queued = queue::rename_org(&queue::path()?, &name, &new_name)?;
+    let queued = match queued {
+        0 => String::new(),
+        count => format!(", along with {count} queued changes"),
+    };
+    Ok(format!("Renamed {name} to {new_name}{queued}"))
+}
+
+fn org_set_token(cli: Cli, args: &OrgSetToken) -> Result<String, String> {
+    let OrgSetToken { name } = args;
+    let mut config = fetch_config(&cli)?;
+    let name = match name {
+        Some(name) => name.clone(),
+        None => fetch_org_name(&cli, &config)?,
+    };
+    // Fails for organizations that aren't in config, use org add for those
+    config.token(&name)?;
+
+    let token = if std::io::stdin().is_terminal() {
+        input::require("the token on stdin")?;
+        input::password(&format!("Input new token for {name}"))?
+    } else {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("Could not read from stdin: {e}"))?;
+        text
+    };
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        return Err(String::from("The token can't be empty"));
+    }
+
+    config.update(|c| c.add_organization(name.clone(), token))?;
+    // Cached responses are keyed by token, so the old ones would never be read again
+    cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+    Ok(format!("Updated the token for {name}"))
+}
+
+// --- TEMPLATES ---
+
+fn template_evaluate(cli: Cli, args: &TemplateEvaluate) -> Result<String, String> {
+    let TemplateEvaluate {
+        path,
+        team,
+        noproject,
+        priority,
+        state,
+        concurrency,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let org_name = fetch_org_name(&cli, &config)?;
+    let token = config.token(&org_name)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let team = viewer::team(&viewer, team)?;
+    let priority = get_priority(priority)?;
+    let state = get_state(&config, &token, &team, state)?;
+    let path = fetch_string(path, "Enter path to TOML file or directory", "--path")?;
+    let project = match *noproject {
+        true => None,
+        false => get_project(&Some(team.clone()))?,
+    };
+    let label_names = config.default_labels(Some(&org_name), Some(&team.name));
+    let label_ids = label::ids(&config, &token, &team, label_names)?;
+
+    template::evaluate(
+        &config,
+        &token,
+        &team,
+        &project,
+        &viewer,
+        &path,
+        &state,
+        &priority,
+        &label_ids,
+        *concurrency,
+    )
+}
+
+// --- STATES ---
+
+fn state_list(cli: Cli, args: &StateList) -> Result<String, String> {
+    let StateList { team } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let team = viewer::team(&viewer, team)?;
+
+    state::list(&config, &token, &team)
+}
+
+fn state_create(cli: Cli, args: &StateCreate) -> Result<String, String> {
+    let StateCreate {
+        team,
+        name,
+        state_type,
+        color,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let team = viewer::team(&viewer, team)?;
+
+    let output = state::create(
+        &config,
+        &token,
+        &team,
+        name.clone(),
+        state_type,
+        color.clone(),
+    )?;
+    // Cached state lists would leave out the change until they expire
+    cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+    Ok(output)
+}
+
+fn state_rename(cli: Cli, args: &StateRename) -> Result<String, String> {
+    let StateRename { team, state, name } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let team = viewer::team(&viewer, team)?;
+
+    let output = state::rename(&config, &token, &team, state, name.clone())?;
+    cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+    Ok(output)
+}
+
+// --- PROJECTS ---
+
+fn project_create(cli: Cli, args: &ProjectCreate) -> Result<String, String> {
+    let ProjectCreate {
+        name,
+        description,
+        team,
+        lead,
+        start_date,
+        target_date,
+        status,
+        full,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let parse_date = |date: &Option<String>| {
+        date.as_ref()
+            .map(|date| date::parse(date, date::today()))
+            .transpose()
+    };
+    let start_date = parse_date(start_date)?;
+    let target_date = parse_date(target_date)?;
+
+    let name = fetch_string(name, "Name", "--name")?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let teams = get_teams(&viewer, team)?;
+    let description = match (description, full) {
+        (None, false) => None,
+        (None, true) if input::defaults_enabled() => None,
+        (description, _) => {
+            let description = fetch_editor(description, "Description", "--description", "")?;
+            Some(processor::description(&config, description)?).filter(|d| !d.trim().is_empty())
+        }
+    };
+    let lead_id = match (lead, full) {
+        (Some(lead), _) => Some(member_id(
+            &config,
+            &token,
+            &teams.first().cloned(),
+            &viewer,
+            lead,
+        )?),
+        (None, true) if !input::defaults_enabled() => {
+            select_member(&config, &token, &teams[0], "Select lead", "--lead")?
+        }
+        (None, _) => None,
+    };
+    let start_date = match (start_date, full) {
+        (None, true) => get_date("Start date", "--start-date")?,
+        (start_date, _) => start_date,
+    };
+    let target_date = match (target_date, full) {
+        (None, true) => get_date("Target date", "--target-date")?,
+        (target_date, _) => target_date,
+    };
+    let status_id = match (status, full) {
+        (Some(status), _) => {
+            let statuses = project::statuses(&config, &token)?;
+            Some(project::find_status(statuses, status)?.id)
+        }
+        (None, true) if !input::defaults_enabled() => {
+            let statuses = project::statuses(&config, &token)?;
+            input::require("--status")?;
+            Some(input::select("Select status", statuses)?.id)
+        }
+        (None, _) => None,
+    };
+
+    let project = project::NewProject {
+        name,
+        description,
+        team_ids: teams.into_iter().map(|team| team.id).collect(),
+        lead_id,
+        start_date,
+        target_date,
+        status_id,
+    };
+    let output = project::create(&config, &token, project)?;
+    // Cached project lists would leave out the new project until they expire
+    cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+    Ok(output)
+}
+
+fn project_list(cli: Cli, args: &ProjectList) -> Result<String, String> {
+    let ProjectList { team, all_teams } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+
+    let projects = viewer::projects_with_teams(&viewer);
+    if *all_teams {
+        return Ok(project::list("your teams", projects));
+    }
+    let team = viewer::team(&viewer, team)?;
+    let projects = projects
+        .into_iter()
+        .filter(|(_, teams)| teams.contains(&team.name))
+        .collect();
+    Ok(project::list(&team.name, projects))
+}
+
+/// Teams by name, or prompt for any number of them when there are none
+fn get_teams(viewer: &viewer::Viewer, names: &[String]) -> Result<Vec<Team>, String> {
+    if !names.is_empty() {
+        return names
+            .iter()
+            .map(|name| viewer::team_by_name(viewer, name))
+            .collect();
+    }
+    let mut team_names = viewer::team_names(viewer)?;
+    if team_names.len() == 1 || input::defaults_enabled() {
+        return viewer::team(viewer, &None).map(|team| vec![team]);
+    }
+    team_names.sort();
+    input::require("--team")?;
+    let selected = input::multi_select("Select teams", team_names)?;
+    if selected.is_empty() {
+        return Err(String::from("Select at least one team"));
+    }
+    selected
+        .iter()
+        .map(|name| viewer::team_by_name(viewer, name))
+        .collect()
+}
+
+// --- LABELS ---
+
+fn label_create(cli: Cli, args: &LabelCreate) -> Result<String, String> {
+    let LabelCreate {
+        team,
+        workspace,
+        name,
+        color,
+        parent,
+        group,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let team = label_team(&config, &token, team, *workspace)?;
+
+    let output = label::create(
+        &config,
+        &token,
+        &team,
+        name.clone(),
+        color.clone(),
+        parent.clone(),
+        *group,
+    )?;
+    // Cached label lists would leave out the change until they expire
+    cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+    Ok(output)
+}
+
+fn label_rename(cli: Cli, args: &LabelRename) -> Result<String, String> {
+    let LabelRename {
+        team,
+        workspace,
+        label,
+        name,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let team = label_team(&config, &token, team, *workspace)?;
+
+    let output = label::rename(&config, &token, &team, label, name.clone())?;
+    cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+    Ok(output)
+}
+
+fn label_archive(cli: Cli, args: &LabelArchive) -> Result<String, String> {
+    let LabelArchive {
+        team,
+        workspace,
+        label,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let team = label_team(&config, &token, team, *workspace)?;
+
+    let output = label::archive(&config, &token, &team, label)?;
+    cache::dir().map(|dir| cache::clear(&dir)).transpose()?;
+    Ok(output)
+}
+
+/// The team whose labels to change, None for workspace labels
+fn label_team(
+    config: &Config,
+    token: &str,
+    team: &Option<String>,
+    workspace: bool,
+) -> Result<Option<team::Team>, String> {
+    if workspace {
+        return Ok(None);
+    }
+    let viewer = viewer::get_viewer(config, token)?;
+    viewer::team(&viewer, team).map(Some)
+}
+
+// --- MEMBERS ---
+
+fn member_list(cli: Cli, args: &MemberList) -> Result<String, String> {
+    let MemberList { team } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let team = viewer::team(&viewer, team)?;
+
+    member::list(&config, &token, &team)
+}
+
+// --- REPORTS ---
+
+fn report_created_vs_completed(
+    cli: Cli,
+    args: &ReportCreatedVsCompleted,
+) -> Result<String, String> {
+    let ReportCreatedVsCompleted { team, weeks } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let team = viewer::team(&viewer, team)?;
 
-    #[arg(short, long)]
-    /// i.e. Backlog or Todo
-    state: Option<String>,
+    report::created_vs_completed(&config, &token, &team, *weeks)
 }
 
-#[derive(Parser, Debug, Clone)]
-struct IssueEdit {}
+fn report_standup(cli: Cli, args: &ReportStandup) -> Result<String, String> {
+    let ReportStandup { since } = args;
+    let since = date::parse_since(since, date::today())?;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
 
-#[derive(Parser, Debug, Clone)]
-struct IssueView {
-    #[arg(short, long, default_value_t = false)]
-    /// Select ticket from list view
-    select: bool,
+    report::standup(&config, &token, &viewer.id, since)
 }
 
-#[derive(Parser, Debug, Clone)]
-struct IssueList {
-    #[arg(short = 'e', long)]
-    /// Team name
-    team: Option<String>,
+fn report_changelog(cli: Cli, args: &ReportChangelog) -> Result<String, String> {
+    let ReportChangelog {
+        since,
+        until,
+        group_by,
+        title,
+    } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let messages = git::commit_messages_between(since, until)?;
+    let title = title.clone().unwrap_or_else(|| format!("{since}..{until}"));
 
-    #[arg(short, long, default_value_t = false)]
-    /// Don't prompt for project
-    noproject: bool,
+    report::changelog(&config, &token, &messages, group_by, &title)
+}
 
-    #[arg(short = 't', long, default_value_t = false)]
-    /// Don't prompt for team
-    noteam: bool,
+fn cycle_report(cli: Cli, args: &CycleReport) -> Result<String, String> {
+    let CycleReport { team } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let viewer = viewer::get_viewer(&config, &token)?;
+    let team = viewer::team(&viewer, team)?;
+
+    cycle::report(&config, &token, &team, date::today())
 }
 
-fn main() {
-    let cli = Cli::parse();
+// --- PULL REQUESTS ---
 
-    let result = match &cli.command {
-        Commands::Issue(IssueCommands::Create(args)) => issue_create(cli.clone(), args),
-        Commands::Issue(IssueCommands::Edit(args)) => issue_edit(cli.clone(), args),
-        Commands::Issue(IssueCommands::View(args)) => issue_view(cli.clone(), args),
-        Commands::Issue(IssueCommands::List(args)) => issue_list(cli.clone(), args),
+fn pr_create(cli: Cli, args: &PrCreate) -> Result<String, String> {
+    let PrCreate { base, draft } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
 
-        Commands::Org(OrgCommands::Add(args)) => org_add(cli.clone(), args),
-        Commands::Org(OrgCommands::Remove(args)) => org_remove(cli.clone(), args),
-        Commands::Org(OrgCommands::List(args)) => org_list(cli.clone(), args),
+    let branch = vcs::get_branch()?;
+    let issue = issue::branch_issue(&config, &token, branch)?;
+    let title = github::title(&issue.identifier, &issue.title);
+    let body = github::body(&issue.identifier, &issue.description);
 
-        Commands::Template(TemplateCommands::Evaluate(args)) => {
-            template_evaluate(cli.clone(), args)
-        }
-    };
+    github::create_pull_request(&title, &body, base, *draft)
+}
 
-    match result {
-        Ok(text) => {
-            println!("{text}");
-            std::process::exit(0);
-        }
-        Err(e) => {
-            println!("{}", e.red());
-            std::process::exit(1);
-        }
-    }
+// --- SYNC ---
+
+fn sync(cli: Cli, _args: &Sync) -> Result<String, String> {
+    let config = fetch_config(&cli)?;
+    queue::sync(&config, &queue::path()?)
 }
 
-// --- ISSUES ---
+// --- IMPORT ---
 
-fn issue_create(cli: Cli, args: &IssueCreate) -> Result<String, String> {
-    let IssueCreate {
-        title,
-        description,
+fn import_github(cli: Cli, args: &ImportGithub) -> Result<String, String> {
+    let ImportGithub {
+        repo,
         team,
         noproject,
-        priority,
         state,
     } = args;
     let config = fetch_config(&cli)?;
     let token = fetch_token(&cli, &config)?;
+
     let viewer = viewer::get_viewer(&config, &token)?;
     let team = viewer::team(&viewer, team)?;
-    let state = get_state(&config, &token, &team, state)?;
-    let priority = get_priority(priority)?;
     let project = match noproject {
         true => None,
         false => get_project(&Some(team.clone()))?,
     };
-    let title = fetch_string(title, &config, "Title")?;
-    let description = fetch_editor(description, &config, "Description")?;
+    let states = team::get_states(&config, &token, &team)?;
+    let state = select_state(&config, &token, &team, states, state)?;
 
-    issue::create(
+    import::github(
         &config,
         &token,
-        title,
-        description,
+        &github::token(),
+        repo,
         team,
         project,
         state,
-        viewer.id,
-        priority,
     )
 }
 
-fn issue_view(cli: Cli, args: &IssueView) -> Result<String, String> {
-    let IssueView { select } = args;
+// --- ROADMAP ---
+
+fn roadmap_list(cli: Cli, _args: &RoadmapList) -> Result<String, String> {
     let config = fetch_config(&cli)?;
     let token = fetch_token(&cli, &config)?;
-    if *select {
-        issue::view(&config, &token, None)
-    } else {
-        let branch = git::get_branch()?;
-        issue::view(&config, &token, Some(branch))
-    }
+    roadmap::list(&config, &token)
 }
 
-fn issue_edit(cli: Cli, _args: &IssueEdit) -> Result<String, String> {
+fn roadmap_view(cli: Cli, args: &RoadmapView) -> Result<String, String> {
+    let RoadmapView { initiative } = args;
     let config = fetch_config(&cli)?;
     let token = fetch_token(&cli, &config)?;
-
-    let branch = git::get_branch()?;
-    issue::edit(&config, &token, branch)
+    roadmap::view(&config, &token, initiative)
 }
 
-fn issue_list(cli: Cli, args: &IssueList) -> Result<String, String> {
-    let IssueList {
+fn roadmap_add_project(cli: Cli, args: &RoadmapAddProject) -> Result<String, String> {
+    let RoadmapAddProject {
+        initiative,
+        project,
         team,
-        noteam,
-        noproject,
     } = args;
     let config = fetch_config(&cli)?;
     let token = fetch_token(&cli, &config)?;
 
     let viewer = viewer::get_viewer(&config, &token)?;
+    let team = Some(viewer::team(&viewer, team)?);
+    let project = match project {
+        Some(name) => viewer::project(&team, name.to_owned())?,
+        None => get_project(&team)?,
+    }
+    .ok_or_else(|| String::from("A project is required"))?;
 
-    let team = match *noteam {
-        true => None,
-        false => Some(viewer::team(&viewer, team)?),
-    };
-    let project = match *noproject {
-        true => None,
-        false => get_project(&team)?,
+    roadmap::add_project(&config, &token, initiative, project)
+}
+
+// --- DOC ---
+
+fn doc_list(cli: Cli, args: &DocList) -> Result<String, String> {
+    let DocList { project, team } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    let team = match team {
+        Some(name) => {
+            let viewer = viewer::get_viewer(&config, &token)?;
+            Some(viewer::team(&viewer, &Some(name.clone()))?)
+        }
+        None => None,
     };
+    document::list(&config, &token, project, &team)
+}
 
-    issue::list(&config, &token, Some(viewer.id), team, project)
+fn doc_view(cli: Cli, args: &DocView) -> Result<String, String> {
+    let DocView { id } = args;
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    document::view(&config, &token, id)
 }
 
-// --- ORGANIZATIONS ---
+// --- API ---
 
-fn org_add(cli: Cli, _args: &OrgAdd) -> Result<String, String> {
-    let mut config = fetch_config(&cli)?;
-    let name = input::string("Input organization name", None)?;
-    let token = input::string("Input organization token", None)?;
-    config.add_organization(name, token);
-    config.save()
+fn api(cli: Cli, args: &Api) -> Result<String, String> {
+    let Api {
+        query,
+        file,
+        variables,
+    } = args;
+    let query = match (query, file) {
+        (Some(query), _) => query.clone(),
+        (None, Some(file)) => std::fs::read_to_string(file)
+            .map_err(|e| format!("Could not read query from {file}: {e}"))?,
+        (None, None) => return Err(String::from("Pass a query or --file")),
+    };
+    let config = fetch_config(&cli)?;
+    let token = fetch_token(&cli, &config)?;
+    api::run(&config, &token, &query, variables)
 }
 
-fn org_list(cli: Cli, _args: &OrgList) -> Result<String, String> {
-    let config = fetch_config(&cli)?;
-    let orgs = config
-        .organizations
-        .into_iter()
-        .map(|(k, v)| format!("- {k}: {v}"))
-        .collect::<Vec<String>>();
+// --- CONFIG ---
 
-    if orgs.is_empty() {
-        Ok("No organizations in config".to_string())
-    } else {
-        let title = color::green_string("Organizations");
-        let orgs = orgs.join("\n");
+fn config_edit(cli: Cli, _args: &ConfigEdit) -> Result<String, String> {
+    let mut config = config::get_or_create(cli.config.clone())?;
+    config.edit()
+}
 
-        Ok(format!("{title}\n\n{orgs}"))
-    }
+fn config_get(cli: Cli, args: &ConfigGet) -> Result<String, String> {
+    let ConfigGet { key } = args;
+    let config = config::get_or_default(cli.config.clone())?;
+    config.get(key)
 }
 
-fn org_remove(cli: Cli, _args: &OrgRemove) -> Result<String, String> {
-    let mut config = fetch_config(&cli)?;
-    let org_names = config.organization_names();
-    if org_names.is_empty() {
-        let command = color::cyan_string("org add");
-        Err(format!("Add an organization with {}", command))
-    } else {
-        let org_name = input::select("Select an organization", org_names, None)?;
-        config.remove_organization(&org_name);
-        config.save()
-    }
+fn config_set(cli: Cli, args: &ConfigSet) -> Result<String, String> {
+    let ConfigSet { key, value } = args;
+    let mut config = config::get_or_create(cli.config.clone())?;
+    config.set(key, value)
 }
 
-// --- TEMPLATES ---
+// --- BOARD ---
 
-fn template_evaluate(cli: Cli, args: &TemplateEvaluate) -> Result<String, String> {
-    let TemplateEvaluate {
-        path,
-        team,
-        noproject,
-        priority,
-        state,
-    } = args;
+fn board(cli: Cli, args: &Board) -> Result<String, String> {
+    let Board { team, all_states } = args;
     let config = fetch_config(&cli)?;
-    let token = fetch_token(&cli, &config)?;
+    let org_name = fetch_org_name(&cli, &config)?;
+    let token = config.token(&org_name)?;
+
     let viewer = viewer::get_viewer(&config, &token)?;
     let team = viewer::team(&viewer, team)?;
-    let priority = get_priority(priority)?;
-    let state = get_state(&config, &token, &team, state)?;
-    let path = fetch_string(path, &config, "Enter path to TOML file or directory")?;
-    let project = match *noproject {
-        true => None,
-        false => get_project(&Some(team.clone()))?,
+    let configured_states = config.excluded_states(Some(&org_name), Some(&team.name));
+    let excluded_states = issue::excluded_states(configured_states, *all_states, &[]);
+    let states = team::get_states(&config, &token, &team)?
+        .into_iter()
+        .filter(|state| !excluded_states.contains(&state.name))
+        .collect::<Vec<State>>();
+
+    let filter = issue::Filter {
+        assignee: issue::Assignee::User(viewer.id),
+        excluded_states,
+        team: Some(team),
+        ..Default::default()
     };
+    let issues = issue::get_issues(&config, &token, filter)?;
+    board::run(&config, &token, states, issues)
+}
 
-    template::evaluate(
-        &config, &token, &team, &project, &viewer, &path, &state, &priority,
-    )
+// --- DOCTOR ---
+
+fn doctor(cli: Cli, _args: &Doctor) -> Result<String, String> {
+    doctor::run(cli.config.clone(), cli.timeout)
 }
 
 // --- VALUE HELPERS ---
 
+/// Doesn't create the config file, that happens the first time something is saved to it
 fn fetch_config(cli: &Cli) -> Result<Config, String> {
-    check_for_latest_version();
-    config::get_or_create(cli.config.clone())
+    let config = config::get_or_default(cli.config.clone())?.with_profile(fetch_profile(cli))?;
+    if let Some(page_size) = config.select_page_size {
+        input::set_page_size(page_size);
+    }
+    Ok(Config {
+        request_timeout_seconds: cli.timeout.or(config.request_timeout_seconds),
+        spinners: match cli.no_spinner || cli.quiet || verbosity::get() == Verbosity::Debug {
+            true => Some(false),
+            false => config.spinners,
+        },
+        ..config
+    })
+}
+
+/// Parses the command line, adding flags from the defaults in config for the subcommand being run
+fn parse_cli() -> Cli {
+    let args = std::env::args_os().collect::<Vec<OsString>>();
+    let cli = Cli::parse_from(&args);
+    let Some(defaults) = fetch_config(&cli).ok().and_then(|config| config.defaults) else {
+        return cli;
+    };
+    match defaults::apply(Cli::command(), args, &defaults) {
+        Ok(args) => Cli::parse_from(args),
+        Err(e) => {
+            println!("{}", e.red());
+            std::process::exit(1);
+        }
+    }
+}
+
+fn fetch_profile(cli: &Cli) -> Option<String> {
+    cli.profile
+        .clone()
+        .or_else(|| std::env::var("LNR_PROFILE").ok())
+        .filter(|profile| !profile.is_empty())
 }
 
 fn fetch_token(cli: &Cli, config: &Config) -> Result<String, String> {
-    let org_name = cli.org.clone();
-    match org_name {
-        Some(string) => config.token(&string),
+    let org_name = fetch_org_name(cli, config)?;
+    config.token(&org_name)
+}
+
+fn fetch_org_name(cli: &Cli, config: &Config) -> Result<String, String> {
+    let org_name = match &cli.org {
+        Some(string) => Ok(string.to_owned()),
         None => {
             let mut org_names = config.organization_names();
             org_names.sort();
@@ -383,13 +3276,22 @@ fn fetch_token(cli: &Cli, config: &Config) -> Result<String, String> {
                 let command = color::cyan_string("org add");
                 Err(format!("Add an organization with {}", command))
             } else if org_names.len() == 1 {
-                config.token(org_names.first().unwrap())
+                Ok(org_names.first().unwrap().to_owned())
+            } else if input::defaults_enabled() {
+                let org_name = org_names.first().unwrap().to_owned();
+                verbosity::detail(&format!(
+                    "Picked {org_name}, the first of {} organizations, because of --yes. Choose another with --org",
+                    org_names.len()
+                ));
+                Ok(org_name)
             } else {
-                let org_name = input::select("Select an organization", org_names, None)?;
-                config.token(&org_name)
+                input::require("--org")?;
+                input::select("Select an organization", org_names)
             }
         }
-    }
+    }?;
+    verbosity::detail(&format!("Organization: {org_name}"));
+    Ok(org_name)
 }
 
 fn get_project(team: &Option<Team>) -> Result<Option<Project>, String> {
@@ -397,9 +3299,13 @@ fn get_project(team: &Option<Team>) -> Result<Option<Project>, String> {
     if project_names.is_empty() {
         return Ok(None);
     }
+    if input::defaults_enabled() {
+        return Ok(None);
+    }
     project_names.sort();
     project_names.insert(0, String::from("None"));
-    let project_name = input::select("Select project", project_names, None)?;
+    input::require("--noproject")?;
+    let project_name = input::select("Select project", project_names)?;
     viewer::project(team, project_name)
 }
 
@@ -410,9 +3316,29 @@ fn get_state(
     state: &Option<String>,
 ) -> Result<State, String> {
     let states = team::get_states(config, token, team)?;
+    select_state(config, token, team, states, state)
+}
 
-    match state {
-        None => input::select("Select state", states, None),
+/// Find the named state, or prompt for one when there is no name
+fn select_state(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    states: Vec<State>,
+    state: &Option<String>,
+) -> Result<State, String> {
+    let state = match state {
+        None if input::defaults_enabled() => {
+            let default_id = team::get_default_state_id(config, token, team)?;
+            states
+                .into_iter()
+                .find(|s| Some(&s.id) == default_id.as_ref())
+                .ok_or_else(|| format!("{} has no default state, use --state", team.name))
+        }
+        None => {
+            input::require("--state")?;
+            input::select("Select state", states)
+        }
         Some(state_name) => {
             let matching_state = states
                 .into_iter()
@@ -424,14 +3350,80 @@ fn get_state(
                 Some(state) => Ok(state.to_owned()),
             }
         }
+    }?;
+    verbosity::detail(&format!("State: {}", state.name));
+    Ok(state)
+}
+
+/// Label ids from names, or prompt for them unless skipped. Labels are optional,
+/// so --no-input skips the prompt instead of requiring --label
+fn get_labels(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    names: &[String],
+    skip: bool,
+) -> Result<Vec<String>, String> {
+    if !names.is_empty() {
+        label::ids(config, token, team, names.to_vec())
+    } else if skip || !input::prompts_enabled() {
+        Ok(Vec::new())
+    } else {
+        label::select_ids(config, token, team)
+    }
+}
+
+/// The id of a cycle by number or name, or prompt for one. None leaves the issue out of cycles
+fn get_cycle(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    cycle: &Option<String>,
+) -> Result<Option<String>, String> {
+    let cycles = cycle::upcoming(config, token, team)?;
+    if let Some(query) = cycle {
+        return Ok(Some(cycle::find(cycles, query)?.id));
+    }
+    if cycles.is_empty() || input::defaults_enabled() {
+        return Ok(None);
+    }
+
+    let mut names = cycles
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<String>>();
+    names.insert(0, String::from("None"));
+    input::require("--cycle")?;
+    let name = input::select("Select cycle", names.clone())?;
+    let index = names.iter().position(|n| n == &name).unwrap_or_default();
+    Ok(index
+        .checked_sub(1)
+        .and_then(|i| cycles.get(i))
+        .map(|c| c.id.clone()))
+}
+
+/// Prompt for a date such as 2025-10-01, friday, or +3d, which may be left empty
+fn get_date(name: &str, flag: &str) -> Result<Option<chrono::NaiveDate>, String> {
+    if input::defaults_enabled() {
+        return Ok(None);
+    }
+    input::require(flag)?;
+    let date = input::string(&format!(
+        "{name}, i.e. 2025-10-01, friday, or +3d. Leave empty for none"
+    ))?;
+    match date.trim() {
+        "" => Ok(None),
+        date => date::parse(date, date::today()).map(Some),
     }
 }
 
 fn get_priority(priority: &Option<u8>) -> Result<Priority, String> {
     match priority {
+        None if input::defaults_enabled() => Ok(Priority::Normal),
         None => {
+            input::require("--priority")?;
             let priorities = priority::all_priorities();
-            input::select("Select priority", priorities, None)
+            input::select("Select priority", priorities)
         }
         Some(1) => Ok(Priority::Low),
         Some(2) => Ok(Priority::Normal),
@@ -443,22 +3435,189 @@ fn get_priority(priority: &Option<u8>) -> Result<Priority, String> {
     }
 }
 
-fn fetch_string(value: &Option<String>, config: &Config, prompt: &str) -> Result<String, String> {
+fn get_estimate(
+    team: &Team,
+    estimate: &Option<String>,
+    noestimate: bool,
+) -> Result<Option<u8>, String> {
+    if let Some(estimate) = estimate {
+        return estimate::parse(team, estimate).map(Some);
+    }
+    if noestimate {
+        return Ok(None);
+    }
+
+    let estimates = estimate::all_estimates(team);
+    if estimates.is_empty() || input::defaults_enabled() {
+        return Ok(None);
+    }
+    input::require("--estimate or --noestimate")?;
+    let estimate = input::select("Select estimate", estimates)?;
+    Ok(estimate.value)
+}
+
+/// The id of the user to assign, None to unassign
+fn get_assignee(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    viewer: &viewer::Viewer,
+    assignee: &Option<String>,
+) -> Result<Option<String>, String> {
+    match assignee.as_deref() {
+        Some("me") => Ok(Some(viewer.id.clone())),
+        Some("none") => Ok(None),
+        Some(query) => {
+            let members = team::get_members(config, token, team)?;
+            Ok(user::find(members, query)?.id)
+        }
+        None if input::defaults_enabled() => Ok(Some(viewer.id.clone())),
+        None => select_member(config, token, team, "Select assignee", "--assignee"),
+    }
+}
+
+/// Prompt for a member of the team, None when the None option is picked
+fn select_member(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    prompt: &str,
+    flag: &str,
+) -> Result<Option<String>, String> {
+    let members = team::get_members(config, token, team)?;
+    let mut names = members
+        .iter()
+        .map(|m| m.name_with_status())
+        .collect::<Vec<String>>();
+    names.insert(0, String::from("None"));
+    input::require(flag)?;
+    let name = input::select(prompt, names.clone())?;
+    let index = names.iter().position(|n| n == &name).unwrap_or_default();
+    Ok(index
+        .checked_sub(1)
+        .and_then(|i| members.get(i))
+        .and_then(|m| m.id.clone()))
+}
+
+/// Whose issues to list, names are looked up in the team
+fn list_assignee(
+    config: &Config,
+    token: &str,
+    team: &Option<Team>,
+    viewer: &viewer::Viewer,
+    assignee: &Option<String>,
+) -> Result<issue::Assignee, String> {
+    match assignee.as_deref() {
+        None => Ok(issue::Assignee::User(viewer.id.clone())),
+        Some("none") => Ok(issue::Assignee::Unassigned),
+        Some("any") => Ok(issue::Assignee::Any),
+        Some(query) => member_id(config, token, team, viewer, query).map(issue::Assignee::User),
+    }
+}
+
+/// The id of me or of a team member found by display name, name, or email
+fn member_id(
+    config: &Config,
+    token: &str,
+    team: &Option<Team>,
+    viewer: &viewer::Viewer,
+    query: &str,
+) -> Result<String, String> {
+    if query == "me" {
+        return Ok(viewer.id.clone());
+    }
+    let Some(team) = team else {
+        return Err(format!(
+            "A team is needed to look up {query}, use --team instead of --noteam"
+        ));
+    };
+    let members = team::get_members(config, token, team)?;
+    user::find(members, query)?
+        .id
+        .ok_or_else(|| format!("Could not find the id of {query}"))
+}
+
+/// The title for issue create, starting with the title of the template if there is one
+fn template_title(title: &Option<String>, prefix: &Option<String>) -> Result<String, String> {
+    match (title, prefix) {
+        (Some(title), Some(prefix)) if !title.starts_with(prefix.as_str()) => {
+            Ok(format!("{prefix}{title}"))
+        }
+        (Some(title), _) => Ok(title.to_owned()),
+        (None, Some(prefix)) => {
+            input::require("--title")?;
+            input::string_with_initial("Title", prefix)
+        }
+        (None, None) => fetch_string(title, "Title", "--title"),
+    }
+}
+
+fn fetch_string(value: &Option<String>, prompt: &str, flag: &str) -> Result<String, String> {
     match value {
         Some(string) => Ok(string.to_owned()),
-        None => input::string(prompt, config.mock_string.clone()),
+        None => {
+            input::require(flag)?;
+            input::string(prompt)
+        }
     }
 }
 
-fn fetch_editor(value: &Option<String>, config: &Config, prompt: &str) -> Result<String, String> {
+fn fetch_editor(
+    value: &Option<String>,
+    prompt: &str,
+    flag: &str,
+    default_text: &str,
+) -> Result<String, String> {
     match value {
+        Some(string) if string == "-" => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("Could not read from stdin: {e}"))?;
+            Ok(text)
+        }
         Some(string) => Ok(string.to_owned()),
-        None => input::editor(prompt, "", config.mock_string.clone()),
+        None => {
+            input::require(flag)?;
+            input::editor(prompt, default_text)
+        }
+    }
+}
+
+/// Check crates.io in the background while the command runs
+fn spawn_version_check(config: Config) -> mpsc::Receiver<Result<String, String>> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        _ = sender.send(latest_version(&config));
+    });
+    receiver
+}
+
+/// The latest version on crates.io, which is only fetched once a day
+fn latest_version(config: &Config) -> Result<String, String> {
+    let dir = cache::dir();
+    let cached = dir
+        .as_ref()
+        .and_then(|dir| cache::read(dir, VERSION_CACHE_KEY, VERSION_CACHE_TTL));
+    if let Some(version) = cached {
+        return Ok(version);
+    }
+
+    let version = request::get_latest_version(config)?;
+    if let Some(dir) = dir {
+        cache::write(&dir, VERSION_CACHE_KEY, &version);
     }
+    Ok(version)
 }
 
-fn check_for_latest_version() {
-    match request::get_latest_version() {
+/// Print the version check result if it arrives in time, it is not worth delaying the command for
+fn print_version_check(receiver: mpsc::Receiver<Result<String, String>>) {
+    let latest_version = match receiver.recv_timeout(VERSION_CHECK_TIMEOUT) {
+        Ok(latest_version) => latest_version,
+        Err(_) => return,
+    };
+
+    match latest_version {
         Ok(version) if version.as_str() != VERSION => {
             println!(
                 "Latest {} version is {}, found {}.\nRun {} to update if you installed with Cargo",
@@ -479,8 +3638,18 @@ fn check_for_latest_version() {
 
 #[test]
 fn verify_cmd() {
-    use clap::CommandFactory;
     // Mostly checks that it is not going to throw an exception because of conflicting short arguments
     Cli::try_parse().err();
     Cli::command().debug_assert();
 }
+
+#[test]
+fn standup_since_should_accept_relative_days() {
+    let cli = Cli::try_parse_from(["lnr", "report", "standup", "--since", "-3d"]).unwrap();
+    match cli.command {
+        Commands::Report(ReportCommands::Standup(ReportStandup { since })) => {
+            assert_eq!(since, "-3d")
+        }
+        command => panic!("Parsed as {command:?}"),
+    }
+}
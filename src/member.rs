@@ -0,0 +1,60 @@
+use crate::{color, config::Config, team, team::Team, user::User};
+
+/// List the members of a team with their names, email, and status
+pub fn list(config: &Config, token: &str, team: &Team) -> Result<String, String> {
+    let members = team::get_members(config, token, team)?;
+    if members.is_empty() {
+        return Ok(format!("No members found in {}", team.name));
+    }
+
+    let title = color::green_string(&format!("Members of {}", team.name));
+    let members = members
+        .iter()
+        .map(format)
+        .collect::<Vec<String>>()
+        .join("\n");
+    Ok(format!("\n{title}\n\n{members}"))
+}
+
+fn format(member: &User) -> String {
+    let display_name = color::cyan_string(&member.name_with_status());
+    let details = [&member.name, &member.email]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(" | ");
+
+    match details.is_empty() {
+        true => format!("- {display_name}"),
+        false => format!("- {display_name} | {details}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_list() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::team_members())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = list(&config, "1234", &test::fixtures::team());
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "\nMembers of Thundercats\n\n- alfred | Alfred Pennyworth | alfred@wayne.com\n- Bruce (🦇 On patrol) | Bruce Wayne | bruce@wayne.com"
+            ))
+        );
+        mock.assert();
+    }
+}
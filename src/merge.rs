@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    attachment::{self, Attachments},
+    color,
+    config::Config,
+    hooks,
+    issue::{self, Changes},
+    label::Labels,
+    relation::{self, Kind},
+    request, schema,
+    team::{State, Team},
+};
+
+const MERGE_ISSUE_DOC: &str = "query (
+                    $id: String!
+                ) {
+                issue(
+                    id: $id
+                ) {
+                    id
+                    identifier
+                    title
+                    url
+                    labels {
+                        nodes {
+                            id
+                            name
+                        }
+                    }
+                    attachments {
+                        nodes {
+                            title
+                            url
+                            subtitle
+                            metadata
+                            sourceType
+                        }
+                    }
+                    team {
+                        id
+                        name
+                        states {
+                            nodes {
+                                id
+                                name
+                                position
+                                type
+                            }
+                        }
+                    }
+                }
+                }
+                ";
+
+/// Preferred state for the duplicate, when the team has a canceled state with this name
+const DUPLICATE_STATE: &str = "Duplicate";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct MergeIssueResponse {
+    data: Option<MergeIssueData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct MergeIssueData {
+    issue: MergeIssue,
+}
+
+/// The parts of an issue that are carried over or needed to close it
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct MergeIssue {
+    id: String,
+    identifier: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    labels: Labels,
+    #[serde(default)]
+    attachments: Attachments,
+    #[serde(default)]
+    team: Team,
+}
+
+/// Fold a duplicate issue into the canonical one: relate them, copy over labels and
+/// attachments the canonical issue is missing, comment on both, and cancel the duplicate
+pub fn merge(
+    config: &Config,
+    token: &str,
+    duplicate_id: String,
+    canonical_id: String,
+) -> Result<String, String> {
+    let duplicate = get_issue(config, token, duplicate_id)?;
+    let canonical = get_issue(config, token, canonical_id)?;
+    if duplicate.id == canonical.id {
+        return Err(String::from("Cannot merge an issue into itself"));
+    }
+    let state = canceled_state(&duplicate.team)?;
+
+    let mut lines = vec![relation::create(
+        config,
+        token,
+        duplicate.id.clone(),
+        canonical.id.clone(),
+        Kind::Duplicate,
+    )?];
+
+    let added_label_ids = missing_label_ids(&duplicate.labels, &canonical.labels);
+    let label_count = added_label_ids.len();
+    if label_count > 0 {
+        let changes = Changes {
+            added_label_ids,
+            ..Default::default()
+        };
+        issue::update(config, token, canonical.id.clone(), changes)?;
+    }
+    let attachment_count = attachment::copy_missing(
+        config,
+        token,
+        &duplicate.attachments,
+        &canonical.attachments,
+        &canonical.id,
+    )?;
+    lines.push(format!(
+        "Copied {label_count} label(s) and {attachment_count} attachment(s) to {}",
+        canonical.identifier
+    ));
+
+    issue::post_comment(
+        config,
+        token,
+        canonical.id.clone(),
+        format!(
+            "Merged {} {} into this issue: {}",
+            duplicate.identifier, duplicate.title, duplicate.url
+        ),
+    )?;
+    issue::post_comment(
+        config,
+        token,
+        duplicate.id.clone(),
+        format!(
+            "Merged into {} {}: {}",
+            canonical.identifier, canonical.title, canonical.url
+        ),
+    )?;
+    lines.push(format!(
+        "Commented on {} and {}",
+        canonical.identifier, duplicate.identifier
+    ));
+
+    let state_name = color::cyan_string(&state.name);
+    let changes = Changes {
+        state: Some(state.clone()),
+        ..Default::default()
+    };
+    issue::update(config, token, duplicate.id.clone(), changes)?;
+    lines.push(format!("Moved {} to {state_name}", duplicate.identifier));
+
+    hooks::state_changed(config, token, duplicate.id, &Some(state), lines.join("\n"))
+}
+
+fn get_issue(config: &Config, token: &str, id: String) -> Result<MergeIssue, String> {
+    let response = request::Gql::new(config, token, MERGE_ISSUE_DOC)
+        .put_string("id", id)
+        .run()?;
+    merge_issue_response(response)
+}
+
+/// Ids of the labels on the duplicate that the canonical issue doesn't have
+fn missing_label_ids(duplicate: &Labels, canonical: &Labels) -> Vec<String> {
+    duplicate
+        .nodes
+        .iter()
+        .filter(|label| !canonical.nodes.iter().any(|l| l.id == label.id))
+        .map(|label| label.id.clone())
+        .collect()
+}
+
+/// The team's Duplicate state if it has one, otherwise its first canceled state
+fn canceled_state(team: &Team) -> Result<State, String> {
+    let mut canceled = team
+        .states
+        .clone()
+        .unwrap_or_default()
+        .nodes
+        .into_iter()
+        .filter(|s| s.state_type == "canceled")
+        .collect::<Vec<State>>();
+    canceled.sort_unstable_by_key(|s| s.position as i32);
+
+    match canceled.iter().position(|s| s.name == DUPLICATE_STATE) {
+        Some(index) => Ok(canceled.swap_remove(index)),
+        None if canceled.is_empty() => Err(format!(
+            "Team {} has no canceled state to move the duplicate to",
+            team.name
+        )),
+        None => Ok(canceled.remove(0)),
+    }
+}
+
+fn merge_issue_response(response: String) -> Result<MergeIssue, String> {
+    let data: Result<MergeIssueResponse, _> = schema::parse(&response, "issue");
+
+    match data {
+        Ok(MergeIssueResponse {
+            data: Some(MergeIssueData { issue }),
+        }) => Ok(issue),
+        err => Err(format!(
+            "Could not parse response for issue:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn mock(server: &mut mockito::ServerGuard, pattern: &str, body: String) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(pattern)))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create()
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut server = mockito::Server::new();
+        let duplicate = mock(
+            &mut server,
+            "\"variables\":\\{\"id\":\"ENG-2\"\\}",
+            test::responses::merge_duplicate(),
+        );
+        let canonical = mock(
+            &mut server,
+            "\"variables\":\\{\"id\":\"ENG-1\"\\}",
+            test::responses::merge_canonical(),
+        );
+        let relation = mock(
+            &mut server,
+            "issueRelationCreate",
+            test::responses::relation_create(),
+        );
+        let labels = mock(
+            &mut server,
+            "\"input\":\\{\"addedLabelIds\":\\[\"label-2\"\\]\\}",
+            test::responses::issue_update(),
+        );
+        let attachment = mock(
+            &mut server,
+            "\"url\":\"https://github.com/org/repo/issues/2\"",
+            test::responses::attachment_create(),
+        );
+        let comments = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("commentCreate")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::comment_create())
+            .expect(2)
+            .create();
+        let cancel = mock(
+            &mut server,
+            "\"input\":\\{\"stateId\":\"state-duplicate\"\\}",
+            test::responses::issue_update(),
+        );
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = merge(
+            &config,
+            "1234",
+            String::from("ENG-2"),
+            String::from("ENG-1"),
+        );
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "ENG-2 blocks ENG-1\nCopied 1 label(s) and 1 attachment(s) to ENG-1\nCommented on ENG-1 and ENG-2\nMoved ENG-2 to Duplicate"
+            ))
+        );
+        duplicate.assert();
+        canonical.assert();
+        relation.assert();
+        labels.assert();
+        attachment.assert();
+        comments.assert();
+        cancel.assert();
+    }
+
+    #[test]
+    fn canceled_state_should_prefer_duplicate() {
+        let state = |name: &str, position: f32, state_type: &str| State {
+            name: name.to_string(),
+            id: name.to_lowercase(),
+            position,
+            state_type: state_type.to_string(),
+        };
+        let mut team = test::fixtures::team();
+        team.states = Some(crate::team::StateNode {
+            nodes: vec![
+                state("Done", 1.0, "completed"),
+                state("Canceled", 2.0, "canceled"),
+                state("Duplicate", 3.0, "canceled"),
+            ],
+        });
+        assert_eq!(canceled_state(&team).unwrap().name, "Duplicate");
+
+        team.states = Some(crate::team::StateNode {
+            nodes: vec![state("Done", 1.0, "completed")],
+        });
+        assert_eq!(
+            canceled_state(&team).unwrap_err(),
+            "Team Thundercats has no canceled state to move the duplicate to"
+        );
+    }
+}
@@ -29,6 +29,16 @@ pub fn priority_to_int(priority: &Priority) -> u8 {
         Priority::Low => 4,
     }
 }
+/// Priority from the number Linear uses, where 0 is no priority
+pub fn from_int(value: u8) -> Priority {
+    match value {
+        1 => Priority::Urgent,
+        2 => Priority::High,
+        3 => Priority::Normal,
+        4 => Priority::Low,
+        _ => Priority::None,
+    }
+}
 pub fn all_priorities() -> Vec<Priority> {
     vec![
         Priority::Low,
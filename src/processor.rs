@@ -0,0 +1,110 @@
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Output, Stdio};
+
+use crate::config::Config;
+
+/// Pipe a description through the configured description_processor, if there is one
+pub fn description(config: &Config, description: String) -> Result<String, String> {
+    match &config.description_processor {
+        None => Ok(description),
+        Some(command) => run(command, &description),
+    }
+}
+
+/// Run a shell command with input on stdin, returning stdout
+fn run(command: &str, input: &str) -> Result<String, String> {
+    let output = pipe(command, input)
+        .map_err(|e| format!("Could not run description processor {command}: {e}"))?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|e| e.to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Description processor {command} failed:\n{stderr}"))
+    }
+}
+
+/// Run a shell command with input on stdin, collecting stdout and stderr. The input is written on
+/// another thread, a command that fills stdout before reading all of its input would otherwise
+/// block on its write while we block on ours. Commands are free to exit without reading it.
+pub fn pipe(command: &str, input: &str) -> Result<Output, String> {
+    let mut child = shell(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    let mut stdin = child.stdin.take().ok_or("Could not open stdin")?;
+    let input = input.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    match writer.join() {
+        Ok(Err(e)) if e.kind() != ErrorKind::BrokenPipe => Err(e.to_string()),
+        Err(_) => Err(String::from("Could not write to stdin")),
+        _ => Ok(output),
+    }
+}
+
+pub fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn description_without_processor_is_unchanged() {
+        let config = test::fixtures::config();
+        let result = description(&config, String::from("Some text"));
+        assert_eq!(result, Ok(String::from("Some text")));
+    }
+
+    #[test]
+    fn description_is_piped_through_processor() {
+        let config = Config {
+            description_processor: Some(String::from("tr a-z A-Z")),
+            ..test::fixtures::config()
+        };
+        let result = description(&config, String::from("Some text"));
+        assert_eq!(result, Ok(String::from("SOME TEXT")));
+    }
+
+    #[test]
+    fn failing_processor_returns_error() {
+        let config = Config {
+            description_processor: Some(String::from("echo nope >&2; exit 1")),
+            ..test::fixtures::config()
+        };
+        let result = description(&config, String::from("Some text"));
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Description processor echo nope >&2; exit 1 failed:\nnope\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn large_description_is_piped_through_processor() {
+        let config = Config {
+            description_processor: Some(String::from("cat")),
+            ..test::fixtures::config()
+        };
+        // Larger than a pipe buffer, so stdout fills up before stdin is fully written
+        let text = "a".repeat(1024 * 1024);
+        assert_eq!(description(&config, text.clone()), Ok(text));
+    }
+}
@@ -0,0 +1,424 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::{color, config::Config, date, request, team::Project};
+
+const PROJECT_STATUSES_DOC: &str = "
+        query {
+            projectStatuses {
+                nodes {
+                    id
+                    name
+                    type
+                    position
+                }
+            }
+        }";
+
+const PROJECT_CREATE_DOC: &str = "
+        mutation ($input: ProjectCreateInput!) {
+            projectCreate (input: $input) {
+                success
+                project {
+                    id
+                    name
+                    url
+                }
+            }
+        }";
+
+const PROJECT_MILESTONES_DOC: &str = "
+        query ($id: String!) {
+            project (id: $id) {
+                projectMilestones {
+                    nodes {
+                        id
+                        name
+                        sortOrder
+                    }
+                }
+            }
+        }";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectStatusesResponse {
+    data: Option<ProjectStatusesData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ProjectStatusesData {
+    project_statuses: Statuses,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct Statuses {
+    nodes: Vec<Status>,
+}
+
+/// A project status of the workspace, such as Backlog, Planned, or In Progress
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct Status {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    /// One of backlog, planned, started, paused, completed, or canceled
+    #[serde(rename = "type", default)]
+    status_type: String,
+    #[serde(default)]
+    position: f32,
+}
+
+impl Display for Status {
+    /// This is for rendering in a select
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectMilestonesResponse {
+    data: Option<ProjectMilestonesData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectMilestonesData {
+    project: ProjectMilestones,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ProjectMilestones {
+    project_milestones: Milestones,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct Milestones {
+    nodes: Vec<Milestone>,
+}
+
+/// A stage of a project, such as Beta, that issues in the project can be grouped under
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Milestone {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    sort_order: f32,
+}
+
+impl Display for Milestone {
+    /// This is for rendering in a select
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectCreateResponse {
+    data: Option<ProjectCreateData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ProjectCreateData {
+    project_create: ProjectCreate,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ProjectCreate {
+    success: bool,
+    project: Option<CreatedProject>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct CreatedProject {
+    id: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    url: String,
+}
+
+/// Everything needed to create a project, fields that are None are left to Linear's defaults
+#[derive(Debug, Default)]
+pub struct NewProject {
+    pub name: String,
+    pub description: Option<String>,
+    /// At least one team is required
+    pub team_ids: Vec<String>,
+    pub lead_id: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub target_date: Option<NaiveDate>,
+    pub status_id: Option<String>,
+}
+
+impl NewProject {
+    fn input(self) -> Value {
+        let mut input = json!({
+            "name": self.name,
+            "teamIds": self.team_ids,
+        });
+        let optional = [
+            ("description", self.description),
+            ("leadId", self.lead_id),
+            ("startDate", self.start_date.as_ref().map(date::to_string)),
+            ("targetDate", self.target_date.as_ref().map(date::to_string)),
+            ("statusId", self.status_id),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                input[key] = json!(value);
+            }
+        }
+        input
+    }
+}
+
+/// The project statuses of the workspace in the order Linear shows them
+pub fn statuses(config: &Config, token: &str) -> Result<Vec<Status>, String> {
+    let response: ProjectStatusesResponse = request::Gql::new(config, token, PROJECT_STATUSES_DOC)
+        .cached()
+        .execute()?;
+    let mut statuses = response
+        .data
+        .map(|data| data.project_statuses.nodes)
+        .unwrap_or_default();
+    statuses.sort_by(|a, b| a.position.total_cmp(&b.position));
+    Ok(statuses)
+}
+
+/// Find a status by name, ignoring case
+pub fn find_status(statuses: Vec<Status>, name: &str) -> Result<Status, String> {
+    let names = statuses
+        .iter()
+        .map(|status| status.name.clone())
+        .collect::<Vec<String>>()
+        .join(", ");
+    statuses
+        .into_iter()
+        .find(|status| status.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Project status {name} not found, choose from {names}"))
+}
+
+/// The milestones of a project in the order Linear shows them
+pub fn milestones(
+    config: &Config,
+    token: &str,
+    project_id: &str,
+) -> Result<Vec<Milestone>, String> {
+    let response: ProjectMilestonesResponse =
+        request::Gql::new(config, token, PROJECT_MILESTONES_DOC)
+            .put_string("id", project_id.to_string())
+            .execute()?;
+    let mut milestones = response
+        .data
+        .map(|data| data.project.project_milestones.nodes)
+        .unwrap_or_default();
+    milestones.sort_by(|a, b| a.sort_order.total_cmp(&b.sort_order));
+    Ok(milestones)
+}
+
+/// Find a milestone by name, ignoring case
+pub fn find_milestone(milestones: Vec<Milestone>, name: &str) -> Result<Milestone, String> {
+    let names = milestones
+        .iter()
+        .map(|milestone| milestone.name.clone())
+        .collect::<Vec<String>>()
+        .join(", ");
+    milestones
+        .into_iter()
+        .find(|milestone| milestone.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| match names.is_empty() {
+            true => format!("Milestone {name} not found, the project has no milestones"),
+            false => format!("Milestone {name} not found, choose from {names}"),
+        })
+}
+
+/// Projects with the teams they belong to, as listed by projects_with_teams
+pub fn list(title: &str, projects: Vec<(Project, Vec<String>)>) -> String {
+    if projects.is_empty() {
+        return format!("No projects found for {title}");
+    }
+    let title = color::green_string(&format!("Projects of {title}"));
+    let projects = projects
+        .into_iter()
+        .map(|(project, teams)| {
+            let name = color::cyan_string(&project.name);
+            format!("- {name} | {}", teams.join(", "))
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    format!("\n{title}\n\n{projects}")
+}
+
+/// Create a project and return its name and url
+pub fn create(config: &Config, token: &str, project: NewProject) -> Result<String, String> {
+    if project.team_ids.is_empty() {
+        return Err(String::from("A project needs at least one team"));
+    }
+    let mut gql_variables = HashMap::new();
+    gql_variables.insert(String::from("input"), project.input());
+    let response: ProjectCreateResponse = request::Gql::new(config, token, PROJECT_CREATE_DOC)
+        .put_variables(gql_variables)
+        .execute()?;
+
+    match response.data.map(|data| data.project_create) {
+        Some(ProjectCreate {
+            success: true,
+            project: Some(CreatedProject { name, url, .. }),
+        }) => Ok(format!("Created project {name}\n{url}")),
+        _ => Err(String::from("Could not create project")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_create() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"input\":\\{\"leadId\":\"user-1\",\"name\":\"Batcave, Phase 3\",\"statusId\":\"status-planned\",\"targetDate\":\"2025-12-19\",\"teamIds\":\\[\"123456\",\"654321\"\\]\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::project_create())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let project = NewProject {
+            name: String::from("Batcave, Phase 3"),
+            team_ids: vec![String::from("123456"), String::from("654321")],
+            lead_id: Some(String::from("user-1")),
+            target_date: NaiveDate::from_ymd_opt(2025, 12, 19),
+            status_id: Some(String::from("status-planned")),
+            ..NewProject::default()
+        };
+        assert_eq!(
+            create(&config, "1234", project),
+            Ok(String::from(
+                "Created project Batcave, Phase 3\nhttps://linear.app/vardy/project/batcave-phase-3-1a2b3c"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn create_should_require_a_team() {
+        let config = test::fixtures::config();
+        let project = NewProject {
+            name: String::from("Batcave, Phase 3"),
+            ..NewProject::default()
+        };
+        assert_eq!(
+            create(&config, "1234", project),
+            Err(String::from("A project needs at least one team"))
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        let project = |name: &str| Project {
+            name: name.to_string(),
+            ..Project::default()
+        };
+        assert_eq!(
+            list(
+                "your teams",
+                vec![
+                    (project("Batmobile"), vec![String::from("Thundercats")]),
+                    (
+                        project("Lair"),
+                        vec![String::from("Thundercats"), String::from("Justice League")]
+                    )
+                ]
+            ),
+            "\nProjects of your teams\n\n- Batmobile | Thundercats\n- Lair | Thundercats, Justice League"
+        );
+        assert_eq!(
+            list("Thundercats", Vec::new()),
+            "No projects found for Thundercats"
+        );
+    }
+
+    #[test]
+    fn test_milestones() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("\"id\":\"p1\"")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::project_milestones())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let milestones = milestones(&config, "1234", "p1").unwrap();
+        assert_eq!(
+            milestones
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Alpha", "Beta"]
+        );
+        assert_eq!(
+            find_milestone(milestones.clone(), "beta").map(|m| m.id),
+            Ok(String::from("milestone-beta"))
+        );
+        assert_eq!(
+            find_milestone(milestones, "GA").map(|m| m.id),
+            Err(String::from(
+                "Milestone GA not found, choose from Alpha, Beta"
+            ))
+        );
+        assert_eq!(
+            find_milestone(Vec::new(), "GA").map(|m| m.id),
+            Err(String::from(
+                "Milestone GA not found, the project has no milestones"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_statuses() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::project_statuses())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let statuses = statuses(&config, "1234").unwrap();
+        assert_eq!(
+            statuses
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Backlog", "Planned", "In Progress"]
+        );
+        assert_eq!(
+            find_status(statuses.clone(), "planned").map(|s| s.id),
+            Ok(String::from("status-planned"))
+        );
+        assert_eq!(
+            find_status(statuses, "Shipped").map(|s| s.id),
+            Err(String::from(
+                "Project status Shipped not found, choose from Backlog, Planned, In Progress"
+            ))
+        );
+        mock.assert();
+    }
+}
@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    color,
+    config::{Config, Lock},
+    request,
+};
+
+/// Longest summary shown for a queued mutation
+const SUMMARY_LENGTH: usize = 60;
+
+/// A mutation that couldn't be sent because Linear was unreachable
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Organization whose token is used when the mutation is sent
+    pub org: String,
+    pub summary: String,
+    pub query: String,
+    pub variables: HashMap<String, Value>,
+    pub queued_at: String,
+}
+
+impl Entry {
+    pub fn new(org: &str, query: &str, variables: &HashMap<String, Value>) -> Entry {
+        Entry {
+            org: org.to_string(),
+            summary: summary(variables),
+            query: query.to_string(),
+            variables: variables.clone(),
+            queued_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Queued mutations are kept in $XDG_DATA_HOME/lnr/queue.json
+pub fn path() -> Result<PathBuf, String> {
+    dirs::data_local_dir()
+        .map(|dir| dir.join("lnr").join("queue.json"))
+        .ok_or_else(|| String::from("Could not find a data directory for the offline queue"))
+}
+
+/// Descriptions of issues that couldn't be queued are kept in $XDG_DATA_HOME/lnr/drafts
+pub fn drafts_dir() -> Result<PathBuf, String> {
+    path().map(|path| path.with_file_name("drafts"))
+}
+
+/// Save a description that would otherwise be lost, returning the file it was saved to
+pub fn save_draft(dir: &Path, description: &str) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let path = dir.join(format!("{timestamp}-{}.md", uuid::Uuid::new_v4().simple()));
+    fs::write(&path, description)
+        .map_err(|e| format!("Could not save draft {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+/// Add a mutation to the end of the queue
+pub fn push(path: &Path, entry: Entry) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    // Comments on several issues are queued from several threads at once
+    let _lock = Lock::acquire(&path.to_string_lossy())?;
+    let mut entries = load(path)?;
+    entries.push(entry);
+    save(path, &entries)
+}
+
+/// Send mutations queued for an organization under its new name, after org rename.
+/// Returns how many were moved.
+pub fn rename_org(path: &Path, name: &str, new_name: &str) -> Result<usize, String> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let _lock = Lock::acquire(&path.to_string_lossy())?;
+    let mut entries = load(path)?;
+    let mut renamed = 0;
+    for entry in entries.iter_mut().filter(|entry| entry.org == name) {
+        entry.org = new_name.to_string();
+        renamed += 1;
+    }
+    if renamed > 0 {
+        save(path, &entries)?;
+    }
+    Ok(renamed)
+}
+
+/// Send queued mutations in the order they were made, keeping any that fail
+pub fn sync(config: &Config, path: &Path) -> Result<String, String> {
+    let _lock = match path.exists() {
+        true => Some(Lock::acquire(&path.to_string_lossy())?),
+        false => None,
+    };
+    let entries = load(path)?;
+    if entries.is_empty() {
+        return Ok(String::from("Nothing to sync"));
+    }
+
+    let total = entries.len();
+    let mut remaining = Vec::new();
+    let mut lines = Vec::new();
+    for entry in entries {
+        match send(config, &entry) {
+            Ok(()) => lines.push(format!("- {} {}", entry.summary, color::green_string("✓"))),
+            Err(err) => {
+                lines.push(format!(
+                    "- {} {} {err}",
+                    entry.summary,
+                    color::red_string("✗")
+                ));
+                remaining.push(entry);
+            }
+        }
+    }
+    save(path, &remaining)?;
+
+    let sent = total - remaining.len();
+    let summary = format!(
+        "{}\n\n{sent} of {total} queued changes sent",
+        lines.join("\n")
+    );
+    if remaining.is_empty() {
+        Ok(summary)
+    } else {
+        Err(summary)
+    }
+}
+
+fn send(config: &Config, entry: &Entry) -> Result<(), String> {
+    let token = config.token(&entry.org)?;
+    let response = request::Gql::new(config, &token, &entry.query)
+        .put_variables(entry.variables.clone())
+        .run()?;
+
+    let value: Value = serde_json::from_str(&response).map_err(|e| e.to_string())?;
+    match value.get("errors") {
+        Some(errors) => Err(errors.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// What the mutation was for, i.e. the title of an issue or the start of a comment
+fn summary(variables: &HashMap<String, Value>) -> String {
+    let text = ["title", "body"]
+        .iter()
+        .find_map(|key| variables.get(*key).and_then(Value::as_str))
+        .and_then(|text| text.lines().next())
+        .unwrap_or("Untitled change");
+
+    match text.char_indices().nth(SUMMARY_LENGTH) {
+        Some((index, _)) => format!("{}...", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+fn load(path: &Path) -> Result<Vec<Entry>, String> {
+    match fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json)
+            .map_err(|e| format!("Could not read offline queue {}: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!(
+            "Could not read offline queue {}: {e}",
+            path.display()
+        )),
+    }
+}
+
+fn save(path: &Path, entries: &[Entry]) -> Result<(), String> {
+    if entries.is_empty() {
+        return match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!(
+                "Could not clear offline queue {}: {e}",
+                path.display()
+            )),
+            _ => Ok(()),
+        };
+    }
+    let json = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    fs::write(path, json)
+        .map_err(|e| format!("Could not write offline queue {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn test_path() -> PathBuf {
+        std::env::temp_dir().join(format!("lnr-queue-{}.json", uuid::Uuid::new_v4()))
+    }
+
+    fn entry(title: &str) -> Entry {
+        let mut variables = HashMap::new();
+        variables.insert(String::from("title"), json!(title));
+        Entry::new("Wayne Enterprises", "mutation { issueCreate }", &variables)
+    }
+
+    #[test]
+    fn summary_should_use_title_or_body() {
+        let mut variables = HashMap::new();
+        assert_eq!(summary(&variables), "Untitled change");
+
+        variables.insert(String::from("body"), json!("First line\nSecond line"));
+        assert_eq!(summary(&variables), "First line");
+
+        variables.insert(String::from("title"), json!("a".repeat(70)));
+        assert_eq!(summary(&variables), format!("{}...", "a".repeat(60)));
+    }
+
+    #[test]
+    fn test_sync() {
+        let mut server = mockito::Server::new();
+        let sent = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("Plane ticket")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_create())
+            .create();
+        let rejected = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("Bad ticket")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{\"errors\":[{\"message\":\"Invalid\"}]}")
+            .create();
+        let mut config = test::fixtures::config().mock_url(server.url());
+        config.add_organization(String::from("Wayne Enterprises"), String::from("1234"));
+        let path = test_path();
+        assert_eq!(sync(&config, &path), Ok(String::from("Nothing to sync")));
+
+        push(&path, entry("Plane ticket")).unwrap();
+        push(&path, entry("Bad ticket")).unwrap();
+        assert_eq!(
+            sync(&config, &path),
+            Err(String::from(
+                "- Plane ticket ✓\n- Bad ticket ✗ [{\"message\":\"Invalid\"}]\n\n1 of 2 queued changes sent"
+            ))
+        );
+        let remaining = load(&path).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].summary, "Bad ticket");
+        sent.assert();
+        rejected.assert();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rename_org_should_move_entries() {
+        let path = test_path();
+        assert_eq!(rename_org(&path, "Wayne Enterprises", "Wayne Corp"), Ok(0));
+
+        push(&path, entry("Plane ticket")).unwrap();
+        push(
+            &path,
+            Entry::new("Daily Planet", "mutation", &HashMap::new()),
+        )
+        .unwrap();
+        assert_eq!(rename_org(&path, "Wayne Enterprises", "Wayne Corp"), Ok(1));
+        let orgs = load(&path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.org)
+            .collect::<Vec<String>>();
+        assert_eq!(orgs, vec!["Wayne Corp", "Daily Planet"]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn save_draft_should_write_description() {
+        let dir = test_path();
+        let path = save_draft(&dir, "Worth keeping").unwrap();
+        assert_eq!(path.parent(), Some(dir.as_path()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Worth keeping");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{color, config::Config, request, schema};
+
+const RELATION_CREATE_DOC: &str = "mutation (
+                    $issueId: String!
+                    $relatedIssueId: String!
+                    $type: IssueRelationType!
+                ) {
+                issueRelationCreate(
+                    input: {
+                        issueId: $issueId
+                        relatedIssueId: $relatedIssueId
+                        type: $type
+                    }
+                ) {
+                    success
+                    issueRelation {
+                        type
+                        issue {
+                            identifier
+                            title
+                        }
+                        relatedIssue {
+                            identifier
+                            title
+                        }
+                    }
+                }
+                }
+                ";
+
+/// Kinds of relation that can be created with issue link
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
+pub enum Kind {
+    /// The current issue blocks the other issue
+    Blocks,
+    /// The other issue blocks the current issue
+    BlockedBy,
+    /// The issues are related
+    Related,
+    /// The current issue is a duplicate of the other issue
+    Duplicate,
+}
+
+impl Kind {
+    /// The IssueRelationType, blocked-by is a blocks relation in the other direction
+    fn relation_type(&self) -> &str {
+        match self {
+            Kind::Blocks | Kind::BlockedBy => "blocks",
+            Kind::Related => "related",
+            Kind::Duplicate => "duplicate",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct Relations {
+    nodes: Vec<Relation>,
+}
+
+/// An issueRelation, issue is set on inverse relations and related_issue otherwise
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Relation {
+    #[serde(rename = "type")]
+    relation_type: String,
+    issue: Option<RelatedIssue>,
+    related_issue: Option<RelatedIssue>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct RelatedIssue {
+    identifier: String,
+    #[serde(default)]
+    title: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct RelationCreateResponse {
+    data: Option<RelationCreateData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct RelationCreateData {
+    #[serde(rename = "issueRelationCreate")]
+    issue_relation_create: RelationCreate,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct RelationCreate {
+    success: bool,
+    #[serde(rename = "issueRelation")]
+    issue_relation: Option<Relation>,
+}
+
+/// Create a relation between two issues, ids can be UUIDs or identifiers
+pub fn create(
+    config: &Config,
+    token: &str,
+    issue_id: String,
+    other_id: String,
+    kind: Kind,
+) -> Result<String, String> {
+    let (issue_id, related_issue_id) = match kind {
+        Kind::BlockedBy => (other_id, issue_id),
+        _ => (issue_id, other_id),
+    };
+    let response = request::Gql::new(config, token, RELATION_CREATE_DOC)
+        .put_string("issueId", issue_id)
+        .put_string("relatedIssueId", related_issue_id)
+        .put_string("type", kind.relation_type().to_string())
+        .run()?;
+
+    relation_create_response(response)
+}
+
+/// One line per relation for issue view, i.e. "Blocked by ENG-12 Fix login"
+pub fn describe(relations: &Option<Relations>, inverse_relations: &Option<Relations>) -> String {
+    let outgoing = relations.iter().flat_map(|r| &r.nodes).filter_map(|r| {
+        let label = match r.relation_type.as_str() {
+            "blocks" => "Blocks",
+            "duplicate" => "Duplicate of",
+            _ => "Related to",
+        };
+        r.related_issue.as_ref().map(|issue| (label, issue))
+    });
+    let incoming = inverse_relations
+        .iter()
+        .flat_map(|r| &r.nodes)
+        .filter_map(|r| {
+            let label = match r.relation_type.as_str() {
+                "blocks" => "Blocked by",
+                "duplicate" => "Duplicated by",
+                _ => "Related to",
+            };
+            r.issue.as_ref().map(|issue| (label, issue))
+        });
+
+    outgoing
+        .chain(incoming)
+        .map(|(label, RelatedIssue { identifier, title })| {
+            let identifier = color::blue_string(identifier);
+            format!("{label} {identifier} {title}")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn relation_create_response(response: String) -> Result<String, String> {
+    let data: Result<RelationCreateResponse, _> = schema::parse(&response, "issue relation");
+
+    match data {
+        Ok(RelationCreateResponse {
+            data:
+                Some(RelationCreateData {
+                    issue_relation_create:
+                        RelationCreate {
+                            success: true,
+                            issue_relation:
+                                Some(Relation {
+                                    relation_type,
+                                    issue: Some(issue),
+                                    related_issue: Some(related_issue),
+                                }),
+                        },
+                }),
+        }) => Ok(format!(
+            "{} {relation_type} {}",
+            issue.identifier, related_issue.identifier
+        )),
+        err => Err(format!(
+            "Could not parse response for issue relation:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn create_should_swap_issues_for_blocked_by() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"relatedIssueId\":\"ENG-1\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::relation_create())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = create(
+            &config,
+            "1234",
+            String::from("ENG-1"),
+            String::from("ENG-2"),
+            Kind::BlockedBy,
+        );
+        assert_eq!(result, Ok(String::from("ENG-2 blocks ENG-1")));
+        mock.assert();
+    }
+
+    #[test]
+    fn describe_should_list_both_directions() {
+        let relations: Relations = serde_json::from_str(
+            r#"{"nodes": [{"type": "duplicate", "relatedIssue": {"identifier": "ENG-3", "title": "Original"}}]}"#,
+        )
+        .unwrap();
+        let inverse_relations: Relations = serde_json::from_str(
+            r#"{"nodes": [{"type": "blocks", "issue": {"identifier": "ENG-4", "title": "Blocker"}}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            describe(&Some(relations), &Some(inverse_relations)),
+            "Duplicate of ENG-3 Original\nBlocked by ENG-4 Blocker"
+        );
+        assert_eq!(describe(&None, &None), "");
+    }
+}
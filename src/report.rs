@@ -0,0 +1,750 @@
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    color,
+    config::Config,
+    date, issue,
+    request::{self, PageInfo},
+    schema,
+    team::Team,
+};
+
+const CREATED_OR_COMPLETED_DOC: &str = "query (
+                    $filter: IssueFilter,
+                    $after: String
+                ) {
+                issues (
+                    filter: $filter
+                    first: 250
+                    after: $after
+                ) {
+                    nodes {
+                        createdAt
+                        completedAt
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+                }
+                ";
+
+const STANDUP_DOC: &str = "query (
+                    $assigneeId: ID!
+                    $since: DateTimeOrDuration!
+                ) {
+                completed: issues(
+                    first: 100
+                    filter: {
+                        assignee: { id: { eq: $assigneeId } }
+                        completedAt: { gte: $since }
+                    }
+                ) {
+                    nodes {
+                        identifier
+                        title
+                        url
+                    }
+                }
+                inProgress: issues(
+                    first: 100
+                    filter: {
+                        assignee: { id: { eq: $assigneeId } }
+                        state: { type: { eq: \"started\" } }
+                    }
+                ) {
+                    nodes {
+                        identifier
+                        title
+                        url
+                    }
+                }
+                open: issues(
+                    first: 100
+                    filter: {
+                        assignee: { id: { eq: $assigneeId } }
+                        state: { type: { nin: [\"completed\", \"canceled\"] } }
+                    }
+                ) {
+                    nodes {
+                        identifier
+                        title
+                        url
+                        inverseRelations {
+                            nodes {
+                                type
+                                issue {
+                                    identifier
+                                    title
+                                    state {
+                                        type
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                }
+                ";
+
+const CHANGELOG_ISSUE_DOC: &str = "query (
+                    $id: String!
+                ) {
+                issue(
+                    id: $id
+                ) {
+                    identifier
+                    title
+                    url
+                    labels {
+                        nodes {
+                            name
+                        }
+                    }
+                    project {
+                        name
+                    }
+                }
+                }
+                ";
+
+/// Heading for changelog issues without a label or project
+const CHANGELOG_OTHER: &str = "Other";
+
+/// Longest bar in the chart, counts are scaled down to fit
+const BAR_WIDTH: usize = 40;
+
+#[derive(Deserialize, Serialize, Debug)]
+struct IssuesResponse {
+    data: Option<IssuesData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct IssuesData {
+    issues: IssuesConnection,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct IssuesConnection {
+    nodes: Vec<IssueDates>,
+    #[serde(rename = "pageInfo")]
+    page_info: PageInfo,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct IssueDates {
+    created_at: String,
+    completed_at: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct StandupResponse {
+    data: Option<StandupData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct StandupData {
+    completed: StandupIssues,
+    in_progress: StandupIssues,
+    open: StandupIssues,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct StandupIssues {
+    nodes: Vec<StandupIssue>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct StandupIssue {
+    identifier: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    /// Only fetched for open issues, to find the ones that are blocked
+    inverse_relations: Option<Blockers>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct Blockers {
+    nodes: Vec<Blocker>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct Blocker {
+    #[serde(rename = "type")]
+    relation_type: String,
+    issue: Option<BlockingIssue>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct BlockingIssue {
+    identifier: String,
+    #[serde(default)]
+    title: String,
+    state: Option<StateType>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct StateType {
+    #[serde(rename = "type")]
+    state_type: String,
+}
+
+impl StandupIssue {
+    fn to_markdown(&self) -> String {
+        format!("- [{}]({}) {}", self.identifier, self.url, self.title)
+    }
+
+    /// Issues blocking this one that haven't been completed or canceled
+    fn blocked_by(&self) -> Vec<&BlockingIssue> {
+        self.inverse_relations
+            .iter()
+            .flat_map(|r| &r.nodes)
+            .filter(|r| r.relation_type == "blocks")
+            .filter_map(|r| r.issue.as_ref())
+            .filter(|issue| {
+                !issue
+                    .state
+                    .as_ref()
+                    .is_some_and(|s| s.state_type == "completed" || s.state_type == "canceled")
+            })
+            .collect()
+    }
+}
+
+/// How issues are grouped into sections of the changelog
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
+pub enum GroupBy {
+    /// The first label of each issue
+    Label,
+    /// The project of each issue
+    Project,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ChangelogResponse {
+    data: Option<ChangelogData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ChangelogData {
+    issue: ChangelogIssue,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct ChangelogIssue {
+    identifier: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    labels: Option<ChangelogLabels>,
+    project: Option<Named>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct ChangelogLabels {
+    nodes: Vec<Named>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct Named {
+    name: String,
+}
+
+impl ChangelogIssue {
+    fn group(&self, group_by: &GroupBy) -> String {
+        let name = match group_by {
+            GroupBy::Label => self
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.nodes.first())
+                .map(|label| label.name.clone()),
+            GroupBy::Project => self.project.as_ref().map(|project| project.name.clone()),
+        };
+        name.unwrap_or_else(|| String::from(CHANGELOG_OTHER))
+    }
+}
+
+/// Issues created and completed in the week starting on start
+#[derive(Debug, PartialEq)]
+struct Week {
+    start: NaiveDate,
+    created: usize,
+    completed: usize,
+}
+
+/// Chart of issues created vs completed per week for a team, over the last number of weeks
+pub fn created_vs_completed(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    weeks: u8,
+) -> Result<String, String> {
+    if weeks == 0 {
+        return Err(String::from("Number of weeks must be at least 1"));
+    }
+    let first_week = date::week_start(date::today()) - Duration::weeks(weeks as i64 - 1);
+    let issues = get_created_or_completed(config, token, team, first_week)?;
+    let weeks = tally(first_week, weeks, &issues);
+    let date_format = date::pattern(&config.date_format);
+
+    let title = color::green_string(&format!("Created vs completed for {}", team.name));
+    Ok(format!("\n{title}\n\n{}", chart(&weeks, &date_format)))
+}
+
+/// Markdown for a standup, ready to paste into Slack: issues completed since a date,
+/// issues in progress, and open issues that are blocked by another issue
+pub fn standup(
+    config: &Config,
+    token: &str,
+    assignee_id: &str,
+    since: NaiveDate,
+) -> Result<String, String> {
+    let response = request::Gql::new(config, token, STANDUP_DOC)
+        .put_string("assigneeId", assignee_id.to_string())
+        .put_string("since", date::to_string(&since))
+        .run()?;
+    let StandupData {
+        completed,
+        in_progress,
+        open,
+    } = standup_response(response)?;
+
+    let list = |lines: Vec<String>| match lines.is_empty() {
+        true => String::from("- None"),
+        false => lines.join("\n"),
+    };
+    let completed = list(completed.nodes.iter().map(|i| i.to_markdown()).collect());
+    let in_progress = list(in_progress.nodes.iter().map(|i| i.to_markdown()).collect());
+    let blockers = list(
+        open.nodes
+            .iter()
+            .filter_map(|issue| {
+                let blocked_by = issue
+                    .blocked_by()
+                    .iter()
+                    .map(|b| format!("{} {}", b.identifier, b.title))
+                    .collect::<Vec<String>>();
+                match blocked_by.is_empty() {
+                    true => None,
+                    false => Some(format!(
+                        "{}, blocked by {}",
+                        issue.to_markdown(),
+                        blocked_by.join(", ")
+                    )),
+                }
+            })
+            .collect(),
+    );
+
+    let since = date::display(
+        &date::to_string(&since),
+        &date::pattern(&config.date_format),
+    );
+    Ok(format!(
+        "**Completed since {since}**\n{completed}\n\n**In Progress**\n{in_progress}\n\n**Blockers**\n{blockers}"
+    ))
+}
+
+/// Markdown changelog of the Linear issues mentioned in commit messages, i.e. from git log
+/// between two tags. Sections are sorted by name with Other last, and issues keep the order
+/// of the commits. Identifiers that aren't Linear issues, such as UTF-8, are left out.
+pub fn changelog(
+    config: &Config,
+    token: &str,
+    messages: &str,
+    group_by: &GroupBy,
+    title: &str,
+) -> Result<String, String> {
+    let mut sections: Vec<(String, Vec<ChangelogIssue>)> = Vec::new();
+    for identifier in issue::commit_identifiers(messages) {
+        let response = request::Gql::new(config, token, CHANGELOG_ISSUE_DOC)
+            .put_string("id", identifier.clone())
+            .run()?;
+        if request::is_not_found(&response) {
+            continue;
+        }
+        let issue = match schema::parse(&response, "issue") {
+            Ok(ChangelogResponse {
+                data: Some(ChangelogData { issue }),
+            }) => issue,
+            _ => return Err(format!("Could not look up {identifier}: {response}")),
+        };
+
+        let group = issue.group(group_by);
+        match sections.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, issues)) => issues.push(issue),
+            None => sections.push((group, vec![issue])),
+        }
+    }
+    if sections.is_empty() {
+        return Ok(format!(
+            "# {title}\n\nNo Linear issues found in the commits"
+        ));
+    }
+
+    sections.sort_by(|(a, _), (b, _)| {
+        (a == CHANGELOG_OTHER)
+            .cmp(&(b == CHANGELOG_OTHER))
+            .then(a.cmp(b))
+    });
+    let sections = sections
+        .iter()
+        .map(|(name, issues)| {
+            let lines = issues
+                .iter()
+                .map(|i| format!("- {} ([{}]({}))", i.title, i.identifier, i.url))
+                .collect::<Vec<String>>()
+                .join("\n");
+            format!("## {name}\n\n{lines}")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    Ok(format!("# {title}\n\n{sections}"))
+}
+
+/// Fetch every issue for the team that was created or completed since a date
+fn get_created_or_completed(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    since: NaiveDate,
+) -> Result<Vec<IssueDates>, String> {
+    let since = date::to_string(&since);
+    let filter = json!({
+        "team": {"id": {"eq": team.id}},
+        "or": [
+            {"createdAt": {"gte": since}},
+            {"completedAt": {"gte": since}}
+        ]
+    });
+
+    let mut issues = Vec::new();
+    let mut after = None;
+    loop {
+        let mut gql_variables = std::collections::HashMap::new();
+        gql_variables.insert(String::from("filter"), filter.clone());
+        let response = request::Gql::new(config, token, CREATED_OR_COMPLETED_DOC)
+            .put_variables(gql_variables)
+            .maybe_put_string("after", after)
+            .run()?;
+
+        let IssuesConnection { nodes, page_info } = issues_response(response)?;
+        issues.extend(nodes);
+
+        after = page_info.next_cursor();
+        if after.is_none() {
+            return Ok(issues);
+        }
+    }
+}
+
+/// Count the issues created and completed in each week
+fn tally(first_week: NaiveDate, weeks: u8, issues: &[IssueDates]) -> Vec<Week> {
+    let week_index = |timestamp: &str| {
+        let date = date::timestamp_date(timestamp)?;
+        let index = (date - first_week).num_days().div_euclid(7);
+        usize::try_from(index).ok().filter(|i| *i < weeks as usize)
+    };
+
+    let mut tally = (0..weeks)
+        .map(|n| Week {
+            start: first_week + Duration::weeks(n as i64),
+            created: 0,
+            completed: 0,
+        })
+        .collect::<Vec<Week>>();
+
+    for issue in issues {
+        if let Some(index) = week_index(&issue.created_at) {
+            tally[index].created += 1;
+        }
+        if let Some(index) = issue.completed_at.as_deref().and_then(week_index) {
+            tally[index].completed += 1;
+        }
+    }
+    tally
+}
+
+fn chart(weeks: &[Week], date_format: &str) -> String {
+    let most = weeks
+        .iter()
+        .flat_map(|w| [w.created, w.completed])
+        .max()
+        .unwrap_or_default();
+    let bar = |count: usize| match most {
+        0 => String::new(),
+        most => "#".repeat((count * BAR_WIDTH).div_ceil(most)),
+    };
+    let labels = weeks
+        .iter()
+        .map(|w| date::display(&date::to_string(&w.start), date_format))
+        .collect::<Vec<String>>();
+    let width = labels
+        .iter()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let rows = weeks
+        .iter()
+        .zip(labels)
+        .map(|(week, label)| {
+            let padding = " ".repeat(width);
+            format!(
+                "{label: <width$} | created   {} {}\n{padding} | completed {} {}",
+                bar(week.created),
+                week.created,
+                bar(week.completed),
+                week.completed
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let created = weeks.iter().map(|w| w.created).sum::<usize>();
+    let completed = weeks.iter().map(|w| w.completed).sum::<usize>();
+    let summary = match created.cmp(&completed) {
+        std::cmp::Ordering::Greater => format!("Backlog grew by {}", created - completed),
+        std::cmp::Ordering::Less => format!("Backlog shrank by {}", completed - created),
+        std::cmp::Ordering::Equal => String::from("Backlog held steady"),
+    };
+
+    format!("{rows}\n\n{created} created, {completed} completed. {summary}")
+}
+
+fn issues_response(response: String) -> Result<IssuesConnection, String> {
+    let data: Result<IssuesResponse, _> = schema::parse(&response, "issues");
+
+    match data {
+        Ok(IssuesResponse {
+            data: Some(IssuesData { issues }),
+        }) => Ok(issues),
+        err => Err(format!(
+            "Could not parse response for issues:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+fn standup_response(response: String) -> Result<StandupData, String> {
+    let data: Result<StandupResponse, _> = schema::parse(&response, "standup");
+
+    match data {
+        Ok(StandupResponse { data: Some(data) }) => Ok(data),
+        err => Err(format!(
+            "Could not parse response for standup:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn issue(created_at: &str, completed_at: Option<&str>) -> IssueDates {
+        IssueDates {
+            created_at: created_at.to_string(),
+            completed_at: completed_at.map(String::from),
+        }
+    }
+
+    #[test]
+    fn tally_should_bucket_by_week() {
+        let first_week = NaiveDate::from_ymd_opt(2025, 9, 15).unwrap();
+        let issues = vec![
+            issue("2025-09-10T09:00:00.000Z", Some("2025-09-16T09:00:00.000Z")),
+            issue("2025-09-15T09:00:00.000Z", None),
+            issue("2025-09-21T23:00:00.000Z", Some("2025-09-22T09:00:00.000Z")),
+            issue("2025-09-24T09:00:00.000Z", None),
+        ];
+
+        assert_eq!(
+            tally(first_week, 2, &issues),
+            vec![
+                Week {
+                    start: first_week,
+                    created: 2,
+                    completed: 1
+                },
+                Week {
+                    start: NaiveDate::from_ymd_opt(2025, 9, 22).unwrap(),
+                    created: 1,
+                    completed: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn chart_should_scale_bars_and_summarize() {
+        let weeks = vec![
+            Week {
+                start: NaiveDate::from_ymd_opt(2025, 9, 15).unwrap(),
+                created: 2,
+                completed: 1,
+            },
+            Week {
+                start: NaiveDate::from_ymd_opt(2025, 9, 22).unwrap(),
+                created: 0,
+                completed: 0,
+            },
+        ];
+
+        let bars = "#".repeat(BAR_WIDTH);
+        let half = "#".repeat(BAR_WIDTH / 2);
+        assert_eq!(
+            chart(&weeks, "%d/%m"),
+            format!(
+                "15/09 | created   {bars} 2\n      | completed {half} 1\n22/09 | created    0\n      | completed  0\n\n2 created, 1 completed. Backlog grew by 1"
+            )
+        );
+    }
+
+    #[test]
+    fn test_created_vs_completed() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::created_or_completed())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = created_vs_completed(&config, "1234", &test::fixtures::team(), 4).unwrap();
+        assert!(result.starts_with("\nCreated vs completed for Thundercats\n\n"));
+        assert!(result.ends_with("0 created, 0 completed. Backlog held steady"));
+        mock.assert();
+    }
+
+    #[test]
+    fn test_standup() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"variables\":\\{\"assigneeId\":\"viewer-1\",\"since\":\"2025-09-30\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::standup())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let since = NaiveDate::from_ymd_opt(2025, 9, 30).unwrap();
+
+        assert_eq!(
+            standup(&config, "1234", "viewer-1", since),
+            Ok(String::from(
+                "**Completed since 2025-09-30**\n\
+                 - [ENG-1](https://linear.app/wayne/issue/ENG-1) Fix login\n\n\
+                 **In Progress**\n\
+                 - [ENG-2](https://linear.app/wayne/issue/ENG-2) Add search\n\n\
+                 **Blockers**\n\
+                 - [ENG-3](https://linear.app/wayne/issue/ENG-3) Ship search, blocked by OPS-7 Provision cluster"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_changelog() {
+        let mut server = mockito::Server::new();
+        let mut issue_mock = |identifier: &str, body: String| {
+            server
+                .mock("POST", "/")
+                .match_body(mockito::Matcher::Regex(format!(
+                    "\"variables\":\\{{\"id\":\"{identifier}\"\\}}"
+                )))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .create()
+        };
+        let mocks = [
+            issue_mock(
+                "ENG-3",
+                test::responses::changelog_issue("ENG-3", "Faster search", Some("Feature")),
+            ),
+            issue_mock(
+                "ENG-1",
+                test::responses::changelog_issue("ENG-1", "Fix login", Some("Bug")),
+            ),
+            issue_mock(
+                "UTF-8",
+                String::from("{\"data\":null,\"errors\":[{\"message\":\"Entity not found\"}]}"),
+            ),
+            issue_mock(
+                "ENG-2",
+                test::responses::changelog_issue("ENG-2", "Tidy logs", None),
+            ),
+            issue_mock(
+                "ENG-4",
+                test::responses::changelog_issue("ENG-4", "Dark mode", Some("Feature")),
+            ),
+        ];
+        let config = test::fixtures::config().mock_url(server.url());
+        let messages = "Faster search\n\nCloses ENG-3\nFix ENG-1 login\nHandle UTF-8 names\n\
+                        ENG-2 tidy logs, see ENG-1\nENG-4 dark mode\n";
+
+        assert_eq!(
+            changelog(&config, "1234", messages, &GroupBy::Label, "v1.2.0..HEAD"),
+            Ok(String::from(
+                "# v1.2.0..HEAD\n\n\
+                 ## Bug\n\n\
+                 - Fix login ([ENG-1](https://linear.app/wayne/issue/ENG-1))\n\n\
+                 ## Feature\n\n\
+                 - Faster search ([ENG-3](https://linear.app/wayne/issue/ENG-3))\n\
+                 - Dark mode ([ENG-4](https://linear.app/wayne/issue/ENG-4))\n\n\
+                 ## Other\n\n\
+                 - Tidy logs ([ENG-2](https://linear.app/wayne/issue/ENG-2))"
+            ))
+        );
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn changelog_should_error_on_other_errors() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{\"data\":null,\"errors\":[{\"message\":\"Internal server error\"}]}")
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = changelog(&config, "1234", "ENG-1 fix login", &GroupBy::Label, "v1");
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Could not look up ENG-1: {\"data\":null,\"errors\":[{\"message\":\"Internal server error\"}]}"
+            ))
+        );
+        mock.assert();
+    }
+}
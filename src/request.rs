@@ -1,23 +1,53 @@
 use reqwest::blocking::Client;
+use reqwest::header::HeaderMap;
+use reqwest::header::ACCEPT;
 use reqwest::header::AUTHORIZATION;
 use reqwest::header::CONTENT_TYPE;
 use reqwest::header::USER_AGENT;
-use serde::Deserialize;
+use reqwest::Proxy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use spinners::Spinner;
 use spinners::Spinners;
 use std::collections::HashMap;
 use std::env;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
+use crate::cache;
+use crate::cassette::{self, Cassette};
+use crate::color;
 use crate::config::Config;
+use crate::queue;
+use crate::schema;
+use crate::verbosity;
 
 const LINEAR_URL: &str = "https://api.linear.app/graphql";
 const CARGO_URL: &str = "https://crates.io/api";
+const GITHUB_URL: &str = "https://api.github.com";
 const VERSIONS_URL: &str = "/v1/crates/lnr/versions";
 
+/// Sent as the user agent, which GitHub requires
+const NAME: &str = "lnr";
+
 const SPINNER: Spinners = Spinners::Dots4;
 const MESSAGE: &str = "Querying API";
 
+/// Times a rate limited request is retried before giving up
+const RATE_LIMIT_RETRIES: u8 = 3;
+/// Wait used when Linear doesn't say when the rate limit resets
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(10);
+/// Longest wait before a retry, so a command never looks hung
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+/// Variables whose names contain any of these are left out of debug output
+const REDACTED_KEYS: [&str; 4] = ["token", "secret", "password", "apikey"];
+/// Used when request_timeout_seconds is not configured
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Slowest upload speed allowed for before timing out, in bytes per second
+const MIN_UPLOAD_SPEED: u64 = 256 * 1024;
+
 #[derive(Deserialize)]
 struct CargoResponse {
     versions: Vec<Version>,
@@ -28,11 +58,35 @@ struct Version {
     num: String,
 }
 
+/// Cursor pagination details of a GraphQL connection
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+impl PageInfo {
+    /// The cursor to fetch the next page with, None on the last page
+    pub fn next_cursor(self) -> Option<String> {
+        match self {
+            PageInfo {
+                has_next_page: true,
+                end_cursor,
+            } => end_cursor,
+            _ => None,
+        }
+    }
+}
+
 pub struct Gql {
     config: Config,
     token: String,
     query: String,
     variables: HashMap<String, Value>,
+    cached: bool,
+    /// Skip reading the cache, but still cache the response
+    refresh: bool,
 }
 
 impl Gql {
@@ -42,6 +96,8 @@ impl Gql {
             token: token.to_string(),
             query: query.to_string(),
             variables: HashMap::new(),
+            cached: false,
+            refresh: false,
         }
     }
 
@@ -60,6 +116,18 @@ impl Gql {
 
         self
     }
+    pub fn maybe_put_integer(mut self, key: &str, value: Option<u8>) -> Gql {
+        if let Some(value) = value {
+            self.variables.insert(key.to_string(), json!(value));
+        }
+
+        self
+    }
+    pub fn put_strings(mut self, key: &str, values: Vec<String>) -> Gql {
+        self.variables.insert(key.to_string(), json!(values));
+
+        self
+    }
     pub fn maybe_put_string(mut self, key: &str, value: Option<String>) -> Gql {
         if let Some(value) = value {
             self.variables.insert(key.to_string(), Value::String(value));
@@ -68,43 +136,241 @@ impl Gql {
         self
     }
 
+    /// Use a cached response for queries whose results rarely change, such as teams and states
+    pub fn cached(mut self) -> Gql {
+        self.cached = true;
+
+        self
+    }
+
+    /// Fetch a cached query again, replacing the cached response
+    pub fn refresh(mut self) -> Gql {
+        self.cached = true;
+        self.refresh = true;
+
+        self
+    }
+
+    /// Run the query and deserialize the response, tolerating fields that have become null like schema::parse
+    pub fn execute<T: DeserializeOwned>(self) -> Result<T, String> {
+        let name = operation(&self.query);
+        let response = self.run()?;
+        schema::parse(&response, &name)
+            .map_err(|e| format!("Could not parse response for {name}: {e}"))
+    }
+
     pub fn run(self) -> Result<String, String> {
         let url = get_base_url(&self.config);
 
         let body = json!({"query": self.query, "variables": self.variables});
 
-        let spinner = maybe_start_spinner(&self.config);
-        let response = Client::new()
-            .post(url.clone())
-            .header(CONTENT_TYPE, "application/json")
-            .header(AUTHORIZATION, self.token)
-            .json(&body)
-            .send()
-            .or(Err("Did not get response from server"))?;
+        let operation = operation(&self.query);
+        let cassette = cassette::from_env();
+        if let Some(Cassette::Replay(dir)) = &cassette {
+            verbosity::debug(&format!("{operation} from cassette {}", dir.display()));
+            return cassette::replay(dir, &operation, &body.to_string());
+        }
 
-        maybe_stop_spinner(spinner);
+        let cache = cache_location(&self, &body.to_string());
+        if let Some((dir, key, ttl)) = cache.as_ref().filter(|_| !self.refresh) {
+            if let Some(response) = cache::read(dir, key, *ttl) {
+                verbosity::debug(&format!("{operation} from cache"));
+                return Ok(response);
+            }
+        }
 
-        if response.status().is_success() {
-            Ok(response.text().or(Err("Could not read response text"))?)
-        } else {
-            Err(format!(
-                "
+        let mut retries = 0;
+        loop {
+            verbosity::debug(&format!(
+                "{operation} variables: {}",
+                redact(json!(self.variables))
+            ));
+            let started = Instant::now();
+            let spinner = maybe_start_spinner(&self.config, MESSAGE);
+            let response = client(&self.config)?
+                .post(url.clone())
+                .header(CONTENT_TYPE, "application/json")
+                .header(AUTHORIZATION, &self.token)
+                .json(&body)
+                .send();
+
+            maybe_stop_spinner(spinner);
+            let elapsed = started.elapsed().as_millis();
+            let response = match response {
+                Ok(response) => response,
+                Err(e) => {
+                    verbosity::debug(&format!("{operation} failed after {elapsed}ms: {e}"));
+                    return Err(self.queue_offline(no_response(&self.config, &e)));
+                }
+            };
+
+            let status = response.status();
+            let delay = retry_delay(response.headers(), chrono::Utc::now().timestamp_millis());
+            let text = response.text().or(Err("Could not read response text"))?;
+            verbosity::debug(&format!("{operation} {status} in {elapsed}ms: {text}"));
+
+            if is_rate_limited(status.as_u16(), &text) {
+                if retries == RATE_LIMIT_RETRIES {
+                    return Err(format!(
+                        "Rate limited by Linear, try again in {} seconds",
+                        delay.as_secs()
+                    ));
+                }
+                retries += 1;
+                wait(&self.config, delay);
+                continue;
+            }
+
+            if is_unauthenticated(status.as_u16(), &text) {
+                return Err(unauthenticated(&self.config, &self.token));
+            }
+
+            return if status.is_success() {
+                if let Some((dir, key, _)) = cache.as_ref().filter(|_| !has_errors(&text)) {
+                    cache::write(dir, key, &text);
+                }
+                if let Some(Cassette::Record(dir)) = &cassette {
+                    cassette::record(dir, &operation, &body.to_string(), &text)?;
+                }
+                Ok(text)
+            } else {
+                Err(format!(
+                    "
                 url: {url}
                 ========
                 body: {body}
                 ========
-                Error: {:?}",
-                response
-            ))
+                Error: {status} {text}"
+                ))
+            };
+        }
+    }
+
+    /// Save a mutation to be sent by lnr sync when queueing is turned on,
+    /// returning the error to show in either case
+    fn queue_offline(&self, error: String) -> String {
+        let org = match &self.config.offline_queue {
+            Some(org) if self.query.trim_start().starts_with("mutation") => org,
+            _ => return error,
+        };
+        let entry = queue::Entry::new(org, &self.query, &self.variables);
+        let summary = entry.summary.clone();
+        match queue::path().and_then(|path| queue::push(&path, entry)) {
+            Ok(()) => format!(
+                "Could not reach Linear, queued {summary}. Send it with {}",
+                color::cyan_string("lnr sync")
+            ),
+            Err(e) => format!("Could not reach Linear or queue the change: {e}"),
         }
     }
 }
 
+/// Where a cached request is stored and for how long, None when it isn't cached
+fn cache_location(gql: &Gql, body: &str) -> Option<(PathBuf, String, Duration)> {
+    let ttl = match gql.config.cache_ttl_seconds {
+        Some(seconds) => Duration::from_secs(seconds),
+        None => cache::DEFAULT_TTL,
+    };
+    if !gql.cached || ttl.is_zero() || cfg!(test) {
+        return None;
+    }
+    let dir = cache::dir()?;
+    Some((dir, cache::key(&gql.token, body), ttl))
+}
+
+/// How long to wait for Linear to respond
+pub fn timeout(config: &Config) -> Duration {
+    config
+        .request_timeout_seconds
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TIMEOUT)
+}
+
+/// How long to wait for an upload, the request timeout plus the time to send the file at MIN_UPLOAD_SPEED
+fn upload_timeout(config: &Config, bytes: usize) -> Duration {
+    timeout(config) + Duration::from_secs(bytes as u64 / MIN_UPLOAD_SPEED)
+}
+
+/// Proxies from the environment are used unless one is set in config
+fn client(config: &Config) -> Result<Client, String> {
+    let mut builder = Client::builder().timeout(timeout(config));
+    if let Some(url) = &config.proxy {
+        let proxy = Proxy::all(url).map_err(|e| format!("Invalid proxy {url}: {e}"))?;
+        builder = builder.proxy(proxy);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Could not build HTTP client: {e}"))
+}
+
+/// Error for a request that got no response, timeouts say how to wait longer
+fn no_response(config: &Config, error: &reqwest::Error) -> String {
+    if error.is_timeout() {
+        format!(
+            "Linear did not respond within {} seconds. Set request_timeout_seconds in config or pass --timeout to wait longer",
+            timeout(config).as_secs()
+        )
+    } else {
+        String::from("Did not get response from server")
+    }
+}
+
+/// Check that the Linear API answers at all, without a token
+pub fn reachable(config: &Config) -> Result<(), String> {
+    client(config)?
+        .post(get_base_url(config))
+        .json(&json!({"query": "{ __typename }"}))
+        .send()
+        .map(|_| ())
+        .map_err(|e| no_response(config, &e))
+}
+
+/// PUT a file to a signed upload url, such as one from Linear's fileUpload mutation
+pub fn upload(
+    config: &Config,
+    url: &str,
+    headers: &[(String, String)],
+    content_type: &str,
+    body: Vec<u8>,
+) -> Result<(), String> {
+    let upload_timeout = upload_timeout(config, body.len());
+    let mut request = client(config)?
+        .put(url)
+        .timeout(upload_timeout)
+        .header(CONTENT_TYPE, content_type)
+        .header("Cache-Control", "public, max-age=31536000")
+        .body(body);
+    for (key, value) in headers {
+        request = request.header(key.as_str(), value.as_str());
+    }
+
+    let spinner = maybe_start_spinner(config, "Uploading file");
+    let response = request.send();
+    maybe_stop_spinner(spinner);
+    let response = response.map_err(|e| match e.is_timeout() {
+        true => format!(
+            "Upload did not finish within {} seconds. Set request_timeout_seconds in config or pass --timeout to wait longer",
+            upload_timeout.as_secs()
+        ),
+        false => no_response(config, &e),
+    })?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Could not upload file: {} {}",
+            response.status(),
+            response.text().unwrap_or_default()
+        ))
+    }
+}
+
 /// Get latest version number from Cargo.io
-pub fn get_latest_version() -> Result<String, String> {
+pub fn get_latest_version(config: &Config) -> Result<String, String> {
     let request_url = format!("{CARGO_URL}{VERSIONS_URL}");
 
-    let response = Client::new()
+    let response = client(config)?
         .get(request_url)
         .header(USER_AGENT, "GPTO")
         .send()
@@ -120,19 +386,224 @@ pub fn get_latest_version() -> Result<String, String> {
     }
 }
 
-fn maybe_start_spinner(config: &Config) -> Option<Spinner> {
-    match env::var("DISABLE_SPINNER") {
-        Ok(_) => None,
-        _ => {
-            if let Some(true) = config.spinners {
-                let sp = Spinner::new(SPINNER, MESSAGE.into());
-                Some(sp)
-            } else {
-                None
-            }
-        }
+/// GET a path from the GitHub REST API, i.e. /repos/owner/name/issues. Public repositories
+/// can be read without a token, at a much lower rate limit.
+pub fn github_get(config: &Config, path: &str, token: &Option<String>) -> Result<String, String> {
+    let url = format!("{}{path}", get_github_url(config));
+    let mut request = client(config)?
+        .get(&url)
+        .header(USER_AGENT, NAME)
+        .header(ACCEPT, "application/vnd.github+json");
+    if let Some(token) = token {
+        request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+
+    let spinner = maybe_start_spinner(config, "Querying GitHub");
+    let response = request.send();
+    maybe_stop_spinner(spinner);
+    let response = response.map_err(|e| format!("Did not get response from GitHub: {e}"))?;
+
+    let status = response.status();
+    let text = response.text().or(Err("Could not read response text"))?;
+    if status.is_success() {
+        Ok(text)
+    } else {
+        Err(format!("GitHub returned {status} for {url}: {text}"))
+    }
+}
+
+/// The kind and first field of a GraphQL document for debug output, i.e. "mutation issueCreate"
+fn operation(query: &str) -> String {
+    let query = query.trim_start();
+    let kind = match query.starts_with("mutation") {
+        true => "mutation",
+        false => "query",
+    };
+    let field = query
+        .split_once('{')
+        .and_then(|(_, rest)| {
+            rest.split(|c: char| c.is_whitespace() || c == '(')
+                .find(|w| !w.is_empty())
+        })
+        .unwrap_or_default();
+    format!("{kind} {field}")
+}
+
+/// Variables with anything that looks like a credential replaced, for debug output
+fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let lower = key.to_lowercase();
+                    if REDACTED_KEYS
+                        .iter()
+                        .any(|redacted| lower.contains(redacted))
+                    {
+                        (key, Value::String(String::from("[redacted]")))
+                    } else {
+                        (key, redact(value))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(redact).collect()),
+        value => value,
+    }
+}
+
+/// The extensions.code of each GraphQL error in a response, i.e. RATELIMITED
+fn error_codes(text: &str) -> Vec<String> {
+    match errors(text) {
+        Some(Value::Array(errors)) => errors
+            .iter()
+            .filter_map(|e| e.pointer("/extensions/code").and_then(Value::as_str))
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// True when Linear has no entity with the id that was asked for, i.e. an identifier such as UTF-8
+pub fn is_not_found(text: &str) -> bool {
+    match errors(text) {
+        Some(Value::Array(errors)) => errors.iter().any(|e| {
+            e.get("message")
+                .and_then(Value::as_str)
+                .is_some_and(|message| message == "Entity not found")
+        }),
+        _ => false,
+    }
+}
+
+/// True when a response has GraphQL errors, which can come with a 200 status and are never cached
+fn has_errors(text: &str) -> bool {
+    errors(text).is_some_and(|errors| !errors.is_null())
+}
+
+fn errors(text: &str) -> Option<Value> {
+    serde_json::from_str::<Value>(text)
+        .ok()
+        .and_then(|value| value.get("errors").cloned())
+}
+
+/// True for a 429 status or a RATELIMITED GraphQL error
+fn is_rate_limited(status: u16, text: &str) -> bool {
+    status == 429 || error_codes(text).iter().any(|code| code == "RATELIMITED")
+}
+
+/// True for a 401 status or an AUTHENTICATION_ERROR GraphQL error, which Linear
+/// returns for tokens that are malformed, expired, or revoked
+fn is_unauthenticated(status: u16, text: &str) -> bool {
+    status == 401
+        || error_codes(text)
+            .iter()
+            .any(|code| code == "AUTHENTICATION_ERROR")
+}
+
+/// Error for a token Linear rejected, naming the organization it belongs to,
+/// where in config it came from, and the command that replaces it
+fn unauthenticated(config: &Config, token: &str) -> String {
+    let Some((org_name, profile)) = token_organization(config, token) else {
+        return format!(
+            "Linear rejected the token, it may have expired or been revoked. Replace it with {}",
+            color::cyan_string("lnr org set-token")
+        );
+    };
+    let (source, profile_flag) = match profile {
+        Some(profile) => (
+            format!("profile {profile} in {}", config.path),
+            format!("--profile {profile} "),
+        ),
+        None => (
+            format!("the organizations in {}", config.path),
+            String::new(),
+        ),
+    };
+    let org_arg = match org_name.contains(char::is_whitespace) {
+        true => format!("\"{org_name}\""),
+        false => org_name.clone(),
+    };
+    format!(
+        "Linear rejected the token for {org_name}, it may have expired or been revoked.\n\
+         The token is from {source}. Replace it with {}",
+        color::cyan_string(&format!("lnr {profile_flag}org set-token {org_arg}"))
+    )
+}
+
+/// The organization a token belongs to, and the profile it is in unless it is shared
+fn token_organization(config: &Config, token: &str) -> Option<(String, Option<String>)> {
+    let shared = config
+        .organizations
+        .iter()
+        .find(|(_, org_token)| *org_token == token)
+        .map(|(org_name, _)| (org_name.clone(), config.profile.clone()));
+    shared.or_else(|| {
+        config
+            .profiles
+            .iter()
+            .flatten()
+            .find_map(|(profile_name, profile)| {
+                profile
+                    .organizations
+                    .iter()
+                    .flatten()
+                    .find(|(_, org_token)| *org_token == token)
+                    .map(|(org_name, _)| (org_name.clone(), Some(profile_name.clone())))
+            })
+    })
+}
+
+/// How long to wait before retrying, from the Retry-After header or the reset time
+/// of whichever rate limit has run out
+fn retry_delay(headers: &HeaderMap, now_millis: i64) -> Duration {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let retry_after = header("retry-after")
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_secs);
+    let reset = ["requests", "complexity"]
+        .iter()
+        .filter(|limit| header(&format!("x-ratelimit-{limit}-remaining")) == Some("0"))
+        .filter_map(|limit| header(&format!("x-ratelimit-{limit}-reset")))
+        .filter_map(|v| v.parse::<i64>().ok())
+        .max()
+        .map(|reset| Duration::from_millis((reset - now_millis).max(0) as u64));
+
+    retry_after
+        .or(reset)
+        .unwrap_or(DEFAULT_RETRY_DELAY)
+        .clamp(Duration::from_secs(1), MAX_RETRY_DELAY)
+}
+
+/// Wait out a rate limit, showing why the command has paused
+fn wait(config: &Config, delay: Duration) {
+    let message = format!(
+        "Rate limited by Linear, retrying in {} seconds",
+        delay.as_secs()
+    );
+    let spinner = maybe_start_spinner(config, &message);
+    if spinner.is_none() && !verbosity::is_quiet() {
+        eprintln!("{message}");
+    }
+    if !cfg!(test) {
+        std::thread::sleep(delay);
+    }
+    maybe_stop_spinner(spinner);
+}
+
+fn maybe_start_spinner(config: &Config, message: &str) -> Option<Spinner> {
+    let env_disabled = env::var("DISABLE_SPINNER").is_ok();
+    if spinner_enabled(config, env_disabled, std::io::stdout().is_terminal()) {
+        Some(Spinner::new(SPINNER, message.into()))
+    } else {
+        None
     }
 }
+
+/// Spinners write escape codes, which would end up in the output when it is piped or redirected
+fn spinner_enabled(config: &Config, env_disabled: bool, is_terminal: bool) -> bool {
+    !env_disabled && is_terminal && config.spinners == Some(true)
+}
 fn maybe_stop_spinner(spinner: Option<Spinner>) {
     if let Some(mut sp) = spinner {
         sp.stop();
@@ -147,3 +618,233 @@ fn get_base_url(config: &Config) -> String {
         LINEAR_URL.to_string()
     }
 }
+
+fn get_github_url(config: &Config) -> String {
+    if cfg!(test) {
+        config.mock_url.clone().expect("Mock URL not set")
+    } else {
+        GITHUB_URL.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config, test};
+    use pretty_assertions::assert_eq;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn is_rate_limited_should_check_status_and_errors() {
+        assert!(is_rate_limited(429, ""));
+        assert!(is_rate_limited(
+            400,
+            r#"{"errors":[{"message":"Rate limit exceeded","extensions":{"code":"RATELIMITED"}}]}"#
+        ));
+        assert!(!is_rate_limited(
+            400,
+            r#"{"errors":[{"message":"Invalid","extensions":{"code":"INVALID_INPUT"}}]}"#
+        ));
+        assert!(!is_rate_limited(200, r#"{"data":{}}"#));
+    }
+
+    #[test]
+    fn has_errors_should_check_for_graphql_errors() {
+        assert!(has_errors(
+            r#"{"data":null,"errors":[{"message":"Entity not found"}]}"#
+        ));
+        assert!(!has_errors(r#"{"data":{"viewer":{"id":"1"}}}"#));
+        assert!(!has_errors(r#"{"data":{},"errors":null}"#));
+    }
+
+    #[test]
+    fn is_not_found_should_check_error_messages() {
+        assert!(is_not_found(
+            r#"{"data":null,"errors":[{"message":"Entity not found"}]}"#
+        ));
+        assert!(!is_not_found(
+            r#"{"errors":[{"message":"Rate limit exceeded","extensions":{"code":"RATELIMITED"}}]}"#
+        ));
+        assert!(!is_not_found(r#"{"data":{"issue":null}}"#));
+    }
+
+    #[test]
+    fn is_unauthenticated_should_check_status_and_errors() {
+        assert!(is_unauthenticated(401, ""));
+        assert!(is_unauthenticated(
+            400,
+            r#"{"errors":[{"message":"Authentication required, not authenticated","extensions":{"type":"authentication error","code":"AUTHENTICATION_ERROR"}}]}"#
+        ));
+        assert!(!is_unauthenticated(
+            400,
+            r#"{"errors":[{"message":"Rate limit exceeded","extensions":{"code":"RATELIMITED"}}]}"#
+        ));
+        assert!(!is_unauthenticated(200, r#"{"data":{}}"#));
+    }
+
+    #[test]
+    fn run_should_explain_rejected_tokens() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"errors":[{"message":"Authentication required, not authenticated","extensions":{"code":"AUTHENTICATION_ERROR"}}]}"#)
+            .expect(3)
+            .create();
+        let mut config = test::fixtures::config().mock_url(server.url());
+        config.add_organization(String::from("Wayne Enterprises"), String::from("1234"));
+        let query = "query { viewer { id } }";
+
+        assert_eq!(
+            Gql::new(&config, "1234", query).run(),
+            Err(format!(
+                "Linear rejected the token for Wayne Enterprises, it may have expired or been revoked.\n\
+                 The token is from the organizations in {}. Replace it with lnr org set-token \"Wayne Enterprises\"",
+                config.path
+            ))
+        );
+        assert_eq!(
+            Gql::new(&config, "5678", query).run(),
+            Err(String::from(
+                "Linear rejected the token, it may have expired or been revoked. Replace it with lnr org set-token"
+            ))
+        );
+
+        let profile = config::Profile {
+            organizations: Some(HashMap::from([(
+                String::from("acme"),
+                String::from("5678"),
+            )])),
+            ..Default::default()
+        };
+        let config = Config {
+            profiles: Some(HashMap::from([(String::from("work"), profile)])),
+            ..config
+        };
+        assert_eq!(
+            Gql::new(&config, "5678", query).run(),
+            Err(format!(
+                "Linear rejected the token for acme, it may have expired or been revoked.\n\
+                 The token is from profile work in {}. Replace it with lnr --profile work org set-token acme",
+                config.path
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn retry_delay_should_use_exhausted_limit() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_delay(&headers, 0), DEFAULT_RETRY_DELAY);
+
+        headers.insert(
+            "x-ratelimit-requests-remaining",
+            HeaderValue::from_static("0"),
+        );
+        headers.insert(
+            "x-ratelimit-requests-reset",
+            HeaderValue::from_static("20000"),
+        );
+        headers.insert(
+            "x-ratelimit-complexity-remaining",
+            HeaderValue::from_static("500"),
+        );
+        headers.insert(
+            "x-ratelimit-complexity-reset",
+            HeaderValue::from_static("50000"),
+        );
+        assert_eq!(retry_delay(&headers, 5000), Duration::from_secs(15));
+        assert_eq!(retry_delay(&headers, 30000), Duration::from_secs(1));
+
+        headers.insert("retry-after", HeaderValue::from_static("600"));
+        assert_eq!(retry_delay(&headers, 5000), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn operation_should_name_the_first_field() {
+        assert_eq!(
+            operation("\n    query ($id: String!) { document (id: $id) { id } }"),
+            "query document"
+        );
+        assert_eq!(
+            operation("mutation ($input: IssueCreateInput!) {\n issueCreate(input: $input) {"),
+            "mutation issueCreate"
+        );
+    }
+
+    #[test]
+    fn redact_should_hide_credentials() {
+        let variables =
+            json!({"id": "1", "input": {"accessToken": "lin_api_1234", "labelIds": ["2"]}});
+        assert_eq!(
+            redact(variables),
+            json!({"id": "1", "input": {"accessToken": "[redacted]", "labelIds": ["2"]}})
+        );
+    }
+
+    #[test]
+    fn upload_timeout_should_grow_with_file_size() {
+        let config = Config {
+            request_timeout_seconds: Some(10),
+            ..test::fixtures::config()
+        };
+        assert_eq!(upload_timeout(&config, 1024), Duration::from_secs(10));
+        assert_eq!(
+            upload_timeout(&config, 100 * 1024 * 1024),
+            Duration::from_secs(410)
+        );
+    }
+
+    #[test]
+    fn client_should_reject_invalid_proxy() {
+        let config = Config {
+            proxy: Some(String::from("http://proxy.example.com:8080")),
+            ..test::fixtures::config()
+        };
+        assert!(client(&config).is_ok());
+
+        let config = Config {
+            proxy: Some(String::from("not a proxy")),
+            ..config
+        };
+        assert!(client(&config)
+            .unwrap_err()
+            .starts_with("Invalid proxy not a proxy"));
+    }
+
+    #[test]
+    fn run_should_give_up_after_retries() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(429)
+            .with_header("retry-after", "5")
+            .expect(RATE_LIMIT_RETRIES as usize + 1)
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let result = Gql::new(&config, "1234", "query { viewer { id } }").run();
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Rate limited by Linear, try again in 5 seconds"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn spinner_enabled_should_need_a_terminal() {
+        let config = test::fixtures::config();
+        assert!(spinner_enabled(&config, false, true));
+        assert!(!spinner_enabled(&config, false, false));
+        assert!(!spinner_enabled(&config, true, true));
+
+        let config = Config {
+            spinners: Some(false),
+            ..config
+        };
+        assert!(!spinner_enabled(&config, false, true));
+    }
+}
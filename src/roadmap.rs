@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color,
+    config::Config,
+    date,
+    initiative::{self, Initiative, InitiativeProject},
+    request, schema,
+    team::Project,
+};
+
+const INITIATIVE_TO_PROJECT_CREATE_DOC: &str = "
+        mutation ($initiativeId: String!, $projectId: String!) {
+            initiativeToProjectCreate (
+                input: { initiativeId: $initiativeId, projectId: $projectId }
+            ) {
+                success
+            }
+        }";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct InitiativeToProjectResponse {
+    data: Option<InitiativeToProjectData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct InitiativeToProjectData {
+    initiative_to_project_create: Success,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct Success {
+    success: bool,
+}
+
+/// Status, target date, and owner, i.e. "Active | Target 2025-12-31 | Owner Bruce"
+fn details(initiative: &Initiative, date_format: &str) -> String {
+    [
+        initiative.status.clone(),
+        initiative
+            .target_date
+            .as_ref()
+            .map(|d| format!("Target {}", date::display(d, date_format))),
+        initiative
+            .owner
+            .as_ref()
+            .map(|o| format!("Owner {}", o.display_name)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<String>>()
+    .join(" | ")
+}
+
+/// One line of a table of projects, with the name and status padded to line up
+fn format_project(
+    project: &InitiativeProject,
+    name_width: usize,
+    status_width: usize,
+    date_format: &str,
+) -> String {
+    let status = project.status.as_ref().map_or("", |s| s.name.as_str());
+    let progress = format!("{:.0}%", project.progress.unwrap_or_default() * 100.0);
+    let health = match project.health.as_deref() {
+        Some("onTrack") => color::green_string("On track"),
+        Some("atRisk") => color::red_string("At risk"),
+        Some("offTrack") => color::red_string("Off track"),
+        _ => String::from("No updates"),
+    };
+    let extras = [
+        project
+            .target_date
+            .as_ref()
+            .map(|d| format!("Target {}", date::display(d, date_format))),
+        project
+            .lead
+            .as_ref()
+            .map(|l| format!("Lead {}", l.display_name)),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|extra| format!(" | {extra}"))
+    .collect::<String>();
+    format!(
+        "- {: <name_width$} | {status: <status_width$} | {progress: >4} | {health}{extras}",
+        project.name
+    )
+}
+
+/// Every initiative in the organization, in Linear's order
+pub fn list(config: &Config, token: &str) -> Result<String, String> {
+    let initiatives = initiative::all(config, token)?;
+    if initiatives.is_empty() {
+        return Ok(String::from("No initiatives"));
+    }
+
+    let date_format = date::pattern(&config.date_format);
+    let lines = initiatives
+        .iter()
+        .map(|initiative| {
+            let details = match details(initiative, &date_format).as_str() {
+                "" => String::new(),
+                details => format!(" | {details}"),
+            };
+            format!(
+                "- {} | {}{details}",
+                color::green_string(&initiative.name),
+                initiative.project_count()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    let title = color::green_string("Initiatives");
+    Ok(format!("\n{title}\n\n{lines}"))
+}
+
+/// An initiative with the status, progress, and health of each of its projects
+pub fn view(config: &Config, token: &str, name: &str) -> Result<String, String> {
+    let initiative = initiative::find(config, token, name)?;
+    let projects = initiative::projects(config, token, &initiative)?;
+    let date_format = date::pattern(&config.date_format);
+    let title = color::green_string(&initiative.name);
+    let details = details(&initiative, &date_format);
+    let projects = match projects.is_empty() {
+        true => String::from("<No projects>"),
+        false => {
+            let name_width = projects.iter().map(|p| p.name.len()).max().unwrap_or(0);
+            let status_width = projects
+                .iter()
+                .map(|p| p.status.as_ref().map_or(0, |s| s.name.len()))
+                .max()
+                .unwrap_or(0);
+            projects
+                .iter()
+                .map(|project| format_project(project, name_width, status_width, &date_format))
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+    };
+    Ok(format!("{title}\n{details}\n\n{projects}"))
+}
+
+/// Add a project to an initiative
+pub fn add_project(
+    config: &Config,
+    token: &str,
+    name: &str,
+    project: Project,
+) -> Result<String, String> {
+    let initiative = initiative::find(config, token, name)?;
+    let response = request::Gql::new(config, token, INITIATIVE_TO_PROJECT_CREATE_DOC)
+        .put_string("initiativeId", initiative.id)
+        .put_string("projectId", project.id)
+        .run()?;
+    let data: Result<InitiativeToProjectResponse, _> =
+        schema::parse(&response, "initiativeToProjectCreate");
+
+    match data {
+        Ok(InitiativeToProjectResponse {
+            data:
+                Some(InitiativeToProjectData {
+                    initiative_to_project_create: Success { success: true },
+                }),
+        }) => Ok(format!("Added {} to {}", project.name, initiative.name)),
+        _ => Err(format!(
+            "Could not add {} to {}: {response}",
+            project.name, initiative.name
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_list() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::initiatives())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            list(&config, "1234"),
+            Ok(String::from(
+                "\nInitiatives\n\n- Platform Hardening | 2 projects | Active | Target 2025-12-31 | Owner Bruce\n- Gotham Outreach | 0 projects | Planned"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_view() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"name\":\"platform hardening\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::initiative())
+            .create();
+        let projects_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("\"id\":\"i1\"")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::initiative_projects(false))
+            .create();
+        let config = Config {
+            date_format: Some(String::from("us")),
+            ..test::fixtures::config().mock_url(server.url())
+        };
+
+        assert_eq!(
+            view(&config, "1234", "platform hardening"),
+            Ok(String::from(
+                "Platform Hardening\nActive | Target 12/31/2025 | Owner Bruce\n\n\
+                 - Lair      | In Progress |  40% | On track | Target 11/01/2025 | Lead Bruce\n\
+                 - Batmobile | Planned     |   0% | No updates"
+            ))
+        );
+        mock.assert();
+        projects_mock.assert();
+    }
+
+    #[test]
+    fn test_add_project() {
+        let mut server = mockito::Server::new();
+        let find_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("eqIgnoreCase")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::initiative())
+            .create();
+        let add_mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"initiativeId\":\"i1\",\"projectId\":\"p1\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{\"data\":{\"initiativeToProjectCreate\":{\"success\":true}}}")
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let project = Project {
+            id: String::from("p1"),
+            name: String::from("Lair"),
+        };
+
+        assert_eq!(
+            add_project(&config, "1234", "Platform Hardening", project),
+            Ok(String::from("Added Lair to Platform Hardening"))
+        );
+        find_mock.assert();
+        add_mock.assert();
+    }
+}
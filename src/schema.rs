@@ -0,0 +1,121 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Bump when the GraphQL documents change shape, so warnings can be matched to a release
+pub const SCHEMA_VERSION: u8 = 1;
+
+/// Deserialize a response from Linear, tolerating fields that have become null or been removed.
+/// Fields opt in one at a time with #[serde(default)], ids never do so that a null or missing id is
+/// still an error rather than an empty string.
+pub fn parse<T: DeserializeOwned>(response: &str, name: &str) -> Result<T, String> {
+    let strict_error = match serde_json::from_str::<T>(response) {
+        Ok(data) => return Ok(data),
+        Err(err) => err,
+    };
+
+    let mut value: Value = serde_json::from_str(response).map_err(|e| format!("{e:?}"))?;
+    strip_nulls(&mut value);
+
+    match serde_json::from_value::<T>(value) {
+        Ok(data) => {
+            eprintln!("{}", warning(name, &strict_error.to_string()));
+            Ok(data)
+        }
+        Err(_) => Err(format!("{strict_error:?}")),
+    }
+}
+
+fn warning(name: &str, error: &str) -> String {
+    format!(
+        "Warning: response for {name} does not match schema version {SCHEMA_VERSION} ({error}), some fields may be blank. Check for a newer version of lnr."
+    )
+}
+
+/// Remove null values from objects so that defaults are used in their place
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_nulls),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Deserialize, Debug, Default, PartialEq)]
+    struct Lenient {
+        id: String,
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        count: u8,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Strict {
+        name: String,
+    }
+
+    #[test]
+    fn parse_should_accept_matching_response() {
+        let result = parse::<Lenient>(
+            r#"{"id": "1", "name": "Bob", "count": 2, "extra": true}"#,
+            "test",
+        );
+        assert_eq!(
+            result,
+            Ok(Lenient {
+                id: String::from("1"),
+                name: String::from("Bob"),
+                count: 2
+            })
+        );
+    }
+
+    #[test]
+    fn parse_should_default_null_and_missing_fields() {
+        let result = parse::<Lenient>(r#"{"id": "1", "name": null}"#, "test");
+        assert_eq!(
+            result,
+            Ok(Lenient {
+                id: String::from("1"),
+                ..Lenient::default()
+            })
+        );
+    }
+
+    #[test]
+    fn parse_should_error_on_null_or_missing_id() {
+        assert!(parse::<Lenient>(r#"{"id": null, "name": "Bob"}"#, "test").is_err());
+        assert!(parse::<Lenient>(r#"{"name": "Bob"}"#, "test").is_err());
+    }
+
+    #[test]
+    fn parse_should_error_when_fields_cannot_default() {
+        let result = parse::<Strict>(r#"{"name": null}"#, "test");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn strip_nulls_should_recurse() {
+        let mut value = json!({"a": null, "b": [{"c": null, "d": 1}]});
+        strip_nulls(&mut value);
+        assert_eq!(value, json!({"b": [{"d": 1}]}));
+    }
+
+    #[test]
+    fn warning_should_include_schema_version() {
+        assert_eq!(
+            warning("issue", "oops"),
+            "Warning: response for issue does not match schema version 1 (oops), some fields may be blank. Check for a newer version of lnr."
+        );
+    }
+}
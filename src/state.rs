@@ -0,0 +1,345 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::{
+    color,
+    config::Config,
+    request,
+    team::{self, State, Team},
+};
+
+const WORKFLOW_STATE_CREATE_DOC: &str = "mutation (
+                    $input: WorkflowStateCreateInput!
+                ) {
+                workflowStateCreate(
+                    input: $input
+                ) {
+                    success
+                    workflowState {
+                        id
+                        name
+                        position
+                        type
+                    }
+                }
+                }
+                ";
+
+const WORKFLOW_STATE_UPDATE_DOC: &str = "mutation (
+                    $id: String!
+                    $input: WorkflowStateUpdateInput!
+                ) {
+                workflowStateUpdate(
+                    id: $id,
+                    input: $input
+                ) {
+                    success
+                    workflowState {
+                        id
+                        name
+                        position
+                        type
+                    }
+                }
+                }
+                ";
+
+/// The kinds of workflow state Linear groups a team's states into
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
+pub enum StateType {
+    Triage,
+    Backlog,
+    Unstarted,
+    Started,
+    Completed,
+    Canceled,
+}
+
+impl StateType {
+    fn as_str(&self) -> &str {
+        match self {
+            StateType::Triage => "triage",
+            StateType::Backlog => "backlog",
+            StateType::Unstarted => "unstarted",
+            StateType::Started => "started",
+            StateType::Completed => "completed",
+            StateType::Canceled => "canceled",
+        }
+    }
+
+    /// The color Linear gives new states of this type
+    fn default_color(&self) -> &str {
+        match self {
+            StateType::Triage => "#fc7840",
+            StateType::Backlog => "#bec2c8",
+            StateType::Unstarted => "#e2e2e2",
+            StateType::Started => "#f2c94c",
+            StateType::Completed => "#5e6ad2",
+            StateType::Canceled => "#95a2b3",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct WorkflowStateCreateResponse {
+    data: Option<WorkflowStateCreateData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct WorkflowStateCreateData {
+    #[serde(rename = "workflowStateCreate")]
+    workflow_state_create: WorkflowStatePayload,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct WorkflowStateUpdateResponse {
+    data: Option<WorkflowStateUpdateData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct WorkflowStateUpdateData {
+    #[serde(rename = "workflowStateUpdate")]
+    workflow_state_update: WorkflowStatePayload,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct WorkflowStatePayload {
+    success: bool,
+    #[serde(rename = "workflowState")]
+    workflow_state: Option<State>,
+}
+
+/// List the workflow states of a team in workflow order, marking the one new issues start in
+pub fn list(config: &Config, token: &str, team: &Team) -> Result<String, String> {
+    let states = team::get_states(config, token, team)?;
+    if states.is_empty() {
+        return Ok(format!("No workflow states found in {}", team.name));
+    }
+    let default_id = team::get_default_state_id(config, token, team)?;
+
+    let title = color::green_string(&format!("Workflow states of {}", team.name));
+    let states = states
+        .iter()
+        .map(|state| {
+            let name = color::cyan_string(&state.name);
+            let default = match default_id.as_ref() == Some(&state.id) {
+                true => " | default",
+                false => "",
+            };
+            format!("- {name} | {}{default}", state.state_type)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    Ok(format!("\n{title}\n\n{states}"))
+}
+
+/// Find one of a team's workflow states by name, ignoring case
+pub fn find(team: &Team, states: &[State], name: &str) -> Result<State, String> {
+    match states
+        .iter()
+        .find(|state| state.name.eq_ignore_ascii_case(name))
+    {
+        Some(state) => Ok(state.clone()),
+        None => {
+            let names = states
+                .iter()
+                .map(|state| state.name.clone())
+                .collect::<Vec<String>>()
+                .join(", ");
+            Err(format!(
+                "Team {} has no state named {name}, choose from {names}",
+                team.name
+            ))
+        }
+    }
+}
+
+/// Add a workflow state to a team, in the color Linear uses for the type unless one is given
+pub fn create(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    name: String,
+    state_type: &StateType,
+    color: Option<String>,
+) -> Result<String, String> {
+    let states = team::get_states(config, token, team)?;
+    if states
+        .iter()
+        .any(|state| state.name.eq_ignore_ascii_case(&name))
+    {
+        return Err(format!(
+            "Team {} already has a state named {name}",
+            team.name
+        ));
+    }
+
+    let color = color.unwrap_or_else(|| state_type.default_color().to_string());
+    let mut gql_variables = HashMap::new();
+    gql_variables.insert(
+        String::from("input"),
+        json!({
+            "teamId": team.id,
+            "name": name,
+            "type": state_type.as_str(),
+            "color": color,
+        }),
+    );
+    let response: WorkflowStateCreateResponse =
+        request::Gql::new(config, token, WORKFLOW_STATE_CREATE_DOC)
+            .put_variables(gql_variables)
+            .execute()?;
+
+    match response.data.map(|data| data.workflow_state_create) {
+        Some(WorkflowStatePayload {
+            success: true,
+            workflow_state: Some(state),
+        }) => Ok(format!(
+            "Created {} state {} in {}",
+            state.state_type, state.name, team.name
+        )),
+        _ => Err(format!("Could not create state {name} in {}", team.name)),
+    }
+}
+
+/// Rename one of a team's workflow states, matching the current name case insensitively
+pub fn rename(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    state_name: &str,
+    new_name: String,
+) -> Result<String, String> {
+    let states = team::get_states(config, token, team)?;
+    let state = find(team, &states, state_name)?;
+
+    let mut gql_variables = HashMap::new();
+    gql_variables.insert(String::from("id"), Value::String(state.id.clone()));
+    gql_variables.insert(String::from("input"), json!({ "name": new_name }));
+    let response: WorkflowStateUpdateResponse =
+        request::Gql::new(config, token, WORKFLOW_STATE_UPDATE_DOC)
+            .put_variables(gql_variables)
+            .execute()?;
+
+    match response.data.map(|data| data.workflow_state_update) {
+        Some(WorkflowStatePayload { success: true, .. }) => Ok(format!(
+            "Renamed {} to {new_name} in {}",
+            state.name, team.name
+        )),
+        _ => Err(format!("Could not rename state {}", state.name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn mock_states(server: &mut mockito::Server, hits: usize) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("team \\(id")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::team_states())
+            .expect(hits)
+            .create()
+    }
+
+    #[test]
+    fn test_list() {
+        let mut server = mockito::Server::new();
+        let mock = mock_states(&mut server, 2);
+        let config = test::fixtures::config().mock_url(server.url());
+
+        assert_eq!(
+            list(&config, "1234", &test::fixtures::team()),
+            Ok(String::from(
+                "\nWorkflow states of Thundercats\n\n\
+                 - Backlog | backlog\n\
+                 - Todo | unstarted | default\n\
+                 - In Progress | started\n\
+                 - Done | completed"
+            ))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_create() {
+        let mut server = mockito::Server::new();
+        let states = mock_states(&mut server, 2);
+        let mutation = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"input\":\\{\"color\":\"#f2c94c\",\"name\":\"In Review\",\"teamId\":\"123456\",\"type\":\"started\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::workflow_state_create())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let team = test::fixtures::team();
+
+        assert_eq!(
+            create(
+                &config,
+                "1234",
+                &team,
+                String::from("In Review"),
+                &StateType::Started,
+                None
+            ),
+            Ok(String::from(
+                "Created started state In Review in Thundercats"
+            ))
+        );
+        assert_eq!(
+            create(
+                &config,
+                "1234",
+                &team,
+                String::from("todo"),
+                &StateType::Unstarted,
+                None
+            ),
+            Err(String::from(
+                "Team Thundercats already has a state named todo"
+            ))
+        );
+        states.assert();
+        mutation.assert();
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut server = mockito::Server::new();
+        let states = mock_states(&mut server, 2);
+        let mutation = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"id\":\"state-progress\",\"input\":\\{\"name\":\"Doing\"\\}",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::workflow_state_update())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let team = test::fixtures::team();
+
+        assert_eq!(
+            rename(&config, "1234", &team, "in progress", String::from("Doing")),
+            Ok(String::from("Renamed In Progress to Doing in Thundercats"))
+        );
+        assert_eq!(
+            rename(&config, "1234", &team, "Review", String::from("Doing")),
+            Err(String::from(
+                "Team Thundercats has no state named Review, choose from Backlog, Todo, In Progress, Done"
+            ))
+        );
+        states.assert();
+        mutation.assert();
+    }
+}
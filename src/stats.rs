@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    color,
+    config::Config,
+    issue::{self, Filter},
+    priority,
+    request::{self, PageInfo},
+    schema,
+};
+
+const ISSUE_STATS_DOC: &str = "query (
+                    $filter: IssueFilter,
+                    $after: String
+                ) {
+                issues (
+                    filter: $filter
+                    first: 250
+                    after: $after
+                ) {
+                    nodes {
+                        priority
+                        estimate
+                        state {
+                            name
+                            position
+                        }
+                        project {
+                            name
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+                }
+                ";
+
+#[derive(Deserialize, Serialize, Debug)]
+struct IssuesResponse {
+    data: Option<IssuesData>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct IssuesData {
+    issues: IssuesConnection,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct IssuesConnection {
+    nodes: Vec<IssueCounts>,
+    #[serde(rename = "pageInfo")]
+    page_info: PageInfo,
+}
+
+/// Only the fields that are counted
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct IssueCounts {
+    priority: Option<f32>,
+    estimate: Option<f32>,
+    state: Option<State>,
+    project: Option<Named>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct State {
+    name: String,
+    position: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+struct Named {
+    name: String,
+}
+
+/// Issues and estimate points for one state, priority, or project
+#[derive(Debug, Default, PartialEq)]
+struct Group {
+    name: String,
+    issues: usize,
+    points: f32,
+}
+
+/// Counts of issues by state, priority, and project, with estimate totals
+pub fn summary(config: &Config, token: &str, filter: Filter) -> Result<String, String> {
+    let issues = get_issues(config, token, filter)?;
+    if issues.is_empty() {
+        return Ok(String::from("No issues"));
+    }
+
+    let points = issues.iter().filter_map(|i| i.estimate).sum::<f32>();
+    let unestimated = issues.iter().filter(|i| i.estimate.is_none()).count();
+    let totals = format!(
+        "{} | {} estimated | {unestimated} unestimated",
+        count(issues.len()),
+        points_label(points)
+    );
+
+    let sections = [
+        ("By state", by_state(&issues)),
+        ("By priority", by_priority(&issues)),
+        ("By project", by_project(&issues)),
+    ]
+    .into_iter()
+    .map(|(heading, groups)| format!("{}\n{}", color::green_string(heading), table(&groups)))
+    .collect::<Vec<String>>()
+    .join("\n\n");
+
+    let title = color::green_string("Issue stats");
+    Ok(format!("\n{title}\n\n{totals}\n\n{sections}"))
+}
+
+/// Fetch every issue matching the filter, without the limit that issue list has
+fn get_issues(config: &Config, token: &str, filter: Filter) -> Result<Vec<IssueCounts>, String> {
+    let filter = issue::issue_filter(filter);
+    let mut issues = Vec::new();
+    let mut after = None;
+    loop {
+        let mut gql_variables = HashMap::new();
+        gql_variables.insert(String::from("filter"), filter.clone());
+        let response = request::Gql::new(config, token, ISSUE_STATS_DOC)
+            .put_variables(gql_variables)
+            .maybe_put_string("after", after)
+            .run()?;
+
+        let IssuesConnection { nodes, page_info } = issues_response(response)?;
+        issues.extend(nodes);
+
+        after = page_info.next_cursor();
+        if after.is_none() {
+            return Ok(issues);
+        }
+    }
+}
+
+/// States in workflow order
+fn by_state(issues: &[IssueCounts]) -> Vec<Group> {
+    let mut positions: HashMap<String, f32> = HashMap::new();
+    for state in issues.iter().filter_map(|i| i.state.as_ref()) {
+        positions.insert(state.name.clone(), state.position);
+    }
+    let mut groups = group(issues, |issue| {
+        issue
+            .state
+            .as_ref()
+            .map_or_else(|| String::from("No state"), |s| s.name.clone())
+    });
+    groups.sort_by(|a, b| {
+        let position = |group: &Group| positions.get(&group.name).copied().unwrap_or(f32::MAX);
+        position(a).total_cmp(&position(b))
+    });
+    groups
+}
+
+/// Urgent first, with no priority last
+fn by_priority(issues: &[IssueCounts]) -> Vec<Group> {
+    let mut groups = group(issues, |issue| {
+        priority::from_int(issue.priority.unwrap_or_default() as u8).to_string()
+    });
+    let order = [1, 2, 3, 4, 0].map(|value| priority::from_int(value).to_string());
+    groups.sort_by_key(|group| order.iter().position(|name| *name == group.name));
+    groups
+}
+
+/// Most issues first, with issues outside of a project last
+fn by_project(issues: &[IssueCounts]) -> Vec<Group> {
+    let mut groups = group(issues, |issue| {
+        issue
+            .project
+            .as_ref()
+            .map_or_else(|| String::from("No project"), |p| p.name.clone())
+    });
+    groups.sort_by(|a, b| {
+        (a.name == "No project")
+            .cmp(&(b.name == "No project"))
+            .then(b.issues.cmp(&a.issues))
+            .then(a.name.cmp(&b.name))
+    });
+    groups
+}
+
+fn group(issues: &[IssueCounts], key: impl Fn(&IssueCounts) -> String) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    for issue in issues {
+        let name = key(issue);
+        let index = match groups.iter().position(|g| g.name == name) {
+            Some(index) => index,
+            None => {
+                groups.push(Group {
+                    name,
+                    ..Group::default()
+                });
+                groups.len() - 1
+            }
+        };
+        groups[index].issues += 1;
+        groups[index].points += issue.estimate.unwrap_or_default();
+    }
+    groups
+}
+
+fn table(groups: &[Group]) -> String {
+    let width = groups.iter().map(|g| g.name.len()).max().unwrap_or(0);
+    let count_width = groups
+        .iter()
+        .map(|g| g.issues.to_string().len())
+        .max()
+        .unwrap_or(0);
+    groups
+        .iter()
+        .map(|group| {
+            let points = match group.points {
+                0.0 => String::new(),
+                points => format!(" | {}", points_label(points)),
+            };
+            format!(
+                "- {: <width$}  {: >count_width$}{points}",
+                group.name, group.issues
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// "1 issue" or "N issues"
+pub(crate) fn count(issues: usize) -> String {
+    match issues {
+        1 => String::from("1 issue"),
+        issues => format!("{issues} issues"),
+    }
+}
+
+/// "1 point" or "N points"
+pub(crate) fn points_label(points: f32) -> String {
+    match points {
+        1.0 => String::from("1 point"),
+        points => format!("{points} points"),
+    }
+}
+
+fn issues_response(response: String) -> Result<IssuesConnection, String> {
+    let data: Result<IssuesResponse, _> = schema::parse(&response, "issue stats");
+
+    match data {
+        Ok(IssuesResponse {
+            data: Some(IssuesData { issues }),
+        }) => Ok(issues),
+        err => Err(format!(
+            "Could not parse response for issue stats:
+            ---
+            {err:?}
+            ---
+            {response:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_summary() {
+        let mut server = mockito::Server::new();
+        let first_page = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"variables\":\\{\"filter\":\\{\"and\":\\[\\{\"assignee\":\\{\"id\":\\{\"eq\":\"viewer-1\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_stats(true))
+            .create();
+        let second_page = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"after\":\"cursor-1\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::issue_stats(false))
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let filter = Filter {
+            assignee: issue::Assignee::User(String::from("viewer-1")),
+            ..Filter::default()
+        };
+
+        assert_eq!(
+            summary(&config, "1234", filter),
+            Ok(String::from(
+                "\nIssue stats\n\n\
+                 6 issues | 8.5 points estimated | 2 unestimated\n\n\
+                 By state\n\
+                 - Todo         4 | 5.5 points\n\
+                 - In Progress  2 | 3 points\n\n\
+                 By priority\n\
+                 - Urgent  2 | 4 points\n\
+                 - High    1\n\
+                 - Low     1 | 2 points\n\
+                 - None    2 | 2.5 points\n\n\
+                 By project\n\
+                 - Lair        3 | 6 points\n\
+                 - Batmobile   1\n\
+                 - No project  2 | 2.5 points"
+            ))
+        );
+        first_page.assert();
+        second_page.assert();
+    }
+}
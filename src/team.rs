@@ -2,7 +2,13 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{config::Config, request};
+use crate::{
+    config::Config,
+    request::{self, PageInfo},
+    schema,
+    user::User,
+    verbosity,
+};
 
 const TEAM_STATES_DOC: &str = "
         query ($id: String!) {
@@ -14,6 +20,47 @@ const TEAM_STATES_DOC: &str = "
                         name
                         id
                         position
+                        type
+                    }
+                }
+                defaultIssueState {
+                    id
+                }
+            }
+        }";
+
+const TEAM_MEMBERS_DOC: &str = "
+        query ($id: String!) {
+            team (id: $id) {
+                name
+                id
+                members {
+                    nodes {
+                        id
+                        name
+                        displayName
+                        email
+                        statusEmoji
+                        statusLabel
+                        statusUntilAt
+                    }
+                }
+            }
+        }";
+
+const TEAM_PROJECTS_DOC: &str = "
+        query ($id: String!, $after: String) {
+            team (id: $id) {
+                name
+                id
+                projects (first: 50, after: $after) {
+                    nodes {
+                        name
+                        id
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
                     }
                 }
             }
@@ -31,33 +78,58 @@ struct Data {
 
 #[derive(Serialize, Default, Deserialize, Debug, Clone)]
 pub struct Team {
+    #[serde(default)]
     pub name: String,
     pub id: String,
     pub projects: Option<ProjectNode>,
     pub states: Option<StateNode>,
+    pub members: Option<MemberNode>,
+    /// One of notUsed, exponential, fibonacci, linear, or tShirt
+    #[serde(rename = "issueEstimationType")]
+    pub issue_estimation_type: Option<String>,
+    #[serde(rename = "issueEstimationExtended")]
+    pub issue_estimation_extended: Option<bool>,
+    #[serde(rename = "issueEstimationAllowZero")]
+    pub issue_estimation_allow_zero: Option<bool>,
+    /// State that new issues start in, as set in the team's settings
+    #[serde(rename = "defaultIssueState")]
+    pub default_issue_state: Option<State>,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct ProjectNode {
     pub nodes: Vec<Project>,
+    #[serde(rename = "pageInfo")]
+    pub page_info: Option<PageInfo>,
 }
 
 #[derive(Serialize, Default, Deserialize, Debug, Clone)]
 pub struct Project {
+    #[serde(default)]
     pub name: String,
     pub id: String,
 }
 
+#[derive(Serialize, Default, Deserialize, Debug, Clone)]
+pub struct MemberNode {
+    pub nodes: Vec<User>,
+}
+
 #[derive(Serialize, Default, Deserialize, Debug, Clone)]
 pub struct StateNode {
     pub nodes: Vec<State>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct State {
+    #[serde(default)]
     pub name: String,
     pub id: String,
+    #[serde(default)]
     pub position: f32,
+    /// One of triage, backlog, unstarted, started, completed, or canceled
+    #[serde(rename = "type", default)]
+    pub state_type: String,
 }
 
 impl Display for State {
@@ -68,16 +140,177 @@ impl Display for State {
     }
 }
 pub fn get_states(config: &Config, token: &str, team: &Team) -> Result<Vec<State>, String> {
+    let mut states = get_team_states(config, token, team)?.states.unwrap().nodes;
+    states.sort_unstable_by_key(|s| s.position as i32);
+    Ok(states)
+}
+
+/// Id of the state that new issues start in, as set in the team's settings
+pub fn get_default_state_id(
+    config: &Config,
+    token: &str,
+    team: &Team,
+) -> Result<Option<String>, String> {
+    let team = get_team_states(config, token, team)?;
+    Ok(team.default_issue_state.map(|state| state.id))
+}
+
+/// The first state of a type in workflow order, i.e. started, so that commands work across teams whose states are named differently
+pub fn state_of_type(team: &Team, states: Vec<State>, state_type: &str) -> Result<State, String> {
+    states
+        .into_iter()
+        .filter(|state| state.state_type == state_type)
+        .min_by(|a, b| a.position.total_cmp(&b.position))
+        .ok_or_else(|| format!("Team {} has no {state_type} state", team.name))
+}
+
+/// The team with its states and default state, from the same cached request
+fn get_team_states(config: &Config, token: &str, team: &Team) -> Result<Team, String> {
+    let request = request::Gql::new(config, token, TEAM_STATES_DOC)
+        .cached()
+        .put_string("id", team.id.clone());
+    let fetched = team_states_response(request.run()?)?;
+
+    // Responses cached before states were fetched with their type would break state_of_type
+    let untyped = fetched
+        .states
+        .as_ref()
+        .is_some_and(|states| states.nodes.iter().any(|s| s.state_type.is_empty()));
+    if !untyped {
+        return Ok(fetched);
+    }
+    verbosity::detail("Refreshing cached states that have no type");
     let response = request::Gql::new(config, token, TEAM_STATES_DOC)
+        .refresh()
+        .put_string("id", team.id.clone())
+        .run()?;
+    team_states_response(response)
+}
+
+fn team_states_response(response: String) -> Result<Team, String> {
+    let result: Result<TeamData, _> = schema::parse(&response, "team");
+    match result {
+        Ok(body) => Ok(body.data.team),
+        Err(err) => Err(format!("Could not parse response for states: {err:?}")),
+    }
+}
+
+/// Fetch the members of a team, sorted by display name
+pub fn get_members(config: &Config, token: &str, team: &Team) -> Result<Vec<User>, String> {
+    let response = request::Gql::new(config, token, TEAM_MEMBERS_DOC)
         .put_string("id", team.id.clone())
         .run()?;
-    let result: Result<TeamData, _> = serde_json::from_str(&response);
+    let result: Result<TeamData, _> = schema::parse(&response, "team");
     match result {
         Ok(body) => {
-            let mut states = body.data.team.states.unwrap().nodes;
-            states.sort_unstable_by_key(|s| s.position as i32);
-            Ok(states)
+            let mut members = body.data.team.members.unwrap_or_default().nodes;
+            members.sort_by_key(|m| m.display_name.to_lowercase());
+            Ok(members)
         }
-        Err(err) => Err(format!("Could not parse response for states: {err:?}")),
+        Err(err) => Err(format!("Could not parse response for members: {err:?}")),
+    }
+}
+
+/// Fetch the projects of a team that come after a cursor, following every page
+pub fn get_projects_after(
+    config: &Config,
+    token: &str,
+    team: &Team,
+    after: String,
+) -> Result<Vec<Project>, String> {
+    let mut projects = Vec::new();
+    let mut after = Some(after);
+    while let Some(cursor) = after {
+        let response = request::Gql::new(config, token, TEAM_PROJECTS_DOC)
+            .cached()
+            .put_string("id", team.id.clone())
+            .put_string("after", cursor)
+            .run()?;
+        let result: Result<TeamData, _> = schema::parse(&response, "team");
+        let ProjectNode { nodes, page_info } = match result {
+            Ok(body) => body.data.team.projects.unwrap_or_default(),
+            Err(err) => return Err(format!("Could not parse response for projects: {err:?}")),
+        };
+        projects.extend(nodes);
+        after = page_info.and_then(PageInfo::next_cursor);
+    }
+    Ok(projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn state_of_type_should_use_fetched_states() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("type")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(test::responses::team_states())
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let team = test::fixtures::team();
+
+        let states = get_states(&config, "1234", &team).unwrap();
+        assert_eq!(
+            state_of_type(&team, states.clone(), "started").map(|s| s.name),
+            Ok(String::from("In Progress"))
+        );
+        assert_eq!(
+            state_of_type(&team, states, "completed").map(|s| s.id),
+            Ok(String::from("state-done"))
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn get_states_should_refetch_states_without_a_type() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                "{\"data\":{\"team\":{\"id\":\"123456\",\"name\":\"Thundercats\",\"states\":{\"nodes\":[{\"id\":\"state-todo\",\"name\":\"Todo\",\"position\":1}]}}}}",
+            )
+            .expect(2)
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+
+        get_states(&config, "1234", &test::fixtures::team()).unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn state_of_type_should_find_the_first_in_workflow_order() {
+        let state = |name: &str, position: f32, state_type: &str| State {
+            name: name.to_string(),
+            position,
+            state_type: state_type.to_string(),
+            ..State::default()
+        };
+        let team = Team {
+            name: String::from("Backend"),
+            ..Team::default()
+        };
+        let states = vec![
+            state("Review", 3.0, "started"),
+            state("Todo", 1.0, "unstarted"),
+            state("Doing", 2.0, "started"),
+        ];
+
+        assert_eq!(
+            state_of_type(&team, states.clone(), "started").map(|s| s.name),
+            Ok(String::from("Doing"))
+        );
+        assert_eq!(
+            state_of_type(&team, states, "canceled").map(|s| s.name),
+            Err(String::from("Team Backend has no canceled state"))
+        );
     }
 }
@@ -1,49 +1,25 @@
 use handlebars::Handlebars;
-use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde::Deserialize;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 extern crate walkdir;
 
 use walkdir::WalkDir;
 
+use crate::color;
 use crate::config::Config;
 use crate::priority::{self, Priority};
 use crate::request;
 use crate::team::{Project, State, Team};
+use crate::verbosity;
 use crate::viewer::Viewer;
 
-const ISSUE_CREATE_DOC: &str = "mutation (
-                    $title: String!
-                    $teamId: String!
-                    $priority: Int
-                    $assigneeId: String
-                    $description: String,
-                    $parentId: String
-                    $stateId: String
-                    $projectId: String
-                ) {
-                issueCreate(
-                    input: {
-                        title: $title
-                        priority: $priority
-                        teamId: $teamId
-                        assigneeId: $assigneeId
-                        stateId: $stateId
-                        description: $description
-                        parentId: $parentId
-                        projectId: $projectId
-                    }
-                ) {
-                    issue {
-                        id
-                        url
-                    }
-                }
-                }
-                ";
+/// Most issues created in a single request, keeps each request under Linear's complexity limit
+const BATCH_SIZE: usize = 25;
 
 #[derive(Deserialize)]
 struct Template {
@@ -63,22 +39,7 @@ struct ChildIssue {
     description: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
-struct IssueCreateResponse {
-    data: Option<Data>,
-}
-#[derive(Deserialize, Serialize, Debug)]
-#[allow(non_snake_case)]
-struct Data {
-    issueCreate: IssueCreate,
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-struct IssueCreate {
-    issue: Issue,
-}
-
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
 struct Issue {
     id: String,
     url: String,
@@ -95,27 +56,72 @@ pub fn evaluate(
     path: &String,
     state: &State,
     priority: &Priority,
+    label_ids: &[String],
+    concurrency: usize,
 ) -> Result<String, String> {
-    if Path::is_dir(Path::new(&path)) {
-        for entry in WalkDir::new(path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(is_issue_toml)
-        {
-            create_issues(
-                config,
-                token,
-                team,
-                viewer,
-                project,
-                &entry.path().to_str().unwrap().to_string(),
-                state,
-                priority,
-            )?;
+    // Output for a file is printed in one go, so that files finishing at the same time don't interleave
+    let evaluate_file = |config: &Config, path: &String| {
+        let mut output = match verbosity::is_quiet() {
+            true => Vec::new(),
+            false => vec![format!("Processing {path}")],
+        };
+        let result = create_issues(
+            config,
+            token,
+            team,
+            viewer,
+            project,
+            path,
+            state,
+            priority,
+            label_ids,
+            &mut output,
+        );
+        (output.join("\n"), result)
+    };
+
+    if !Path::is_dir(Path::new(&path)) {
+        let (output, result) = evaluate_file(config, path);
+        println!("{output}");
+        return result.map(|_| "Done".to_string());
+    }
+
+    let paths = WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(is_issue_toml)
+        .map(|entry| entry.path().to_str().unwrap().to_string())
+        .collect::<Vec<String>>();
+    // Spinners from several threads at once garble the terminal
+    let config = Config {
+        spinners: config.spinners.filter(|_| concurrency <= 1),
+        ..config.clone()
+    };
+    let next = AtomicUsize::new(0);
+    let failed = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, paths.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(path) = paths.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    match evaluate_file(&config, path) {
+                        (output, Ok(())) => println!("{output}"),
+                        (output, Err(err)) => {
+                            failed.fetch_add(1, Ordering::SeqCst);
+                            println!("{output}\n{}", color::red_string(&err));
+                        }
+                    }
+                }
+            });
         }
-        Ok("Done".to_string())
-    } else {
-        create_issues(config, token, team, viewer, project, path, state, priority)
+    });
+
+    match failed.into_inner() {
+        0 => Ok("Done".to_string()),
+        failed => Err(format!(
+            "{failed} of {} templates could not be fully created",
+            paths.len()
+        )),
     }
 }
 
@@ -129,7 +135,9 @@ fn create_issues(
     path: &String,
     state: &State,
     priority: &Priority,
-) -> Result<String, String> {
+    label_ids: &[String],
+    output: &mut Vec<String>,
+) -> Result<(), String> {
     let mut toml_string = String::new();
 
     fs::File::open(path.clone())
@@ -137,53 +145,95 @@ fn create_issues(
         .read_to_string(&mut toml_string)
         .or(Err("Could not read to string"))?;
 
-    println!("Processing {path}");
-
     let Template {
         parent,
         children,
         variables,
-    } = toml::from_str(&toml_string).unwrap();
+    } = toml::from_str(&toml_string).map_err(|e| format!("Could not parse {path}: {e}"))?;
 
-    let title = fill_in_variables(parent.title.clone(), variables.clone())?;
-    let description_template = parent.description.unwrap_or_default();
-    let description = fill_in_variables(description_template, variables.clone())?;
     let project_id = project.clone().map(|p| p.id);
     let priority = priority::priority_to_int(priority);
+    let input = |title: String, description: Option<String>, parent_id: Option<&String>| {
+        let description = fill_in_variables(description.unwrap_or_default(), variables.clone())?;
+        Ok::<Value, String>(json!({
+            "title": fill_in_variables(title, variables.clone())?,
+            "description": description,
+            "teamId": team.id,
+            "priority": priority,
+            "stateId": state.id,
+            "assigneeId": viewer.id,
+            "projectId": project_id,
+            "labelIds": label_ids,
+            "parentId": parent_id,
+        }))
+    };
+
+    let parent_input = input(parent.title, parent.description, None)?;
+    let Issue { id, url } = batch_create(config, token, vec![parent_input])?
+        .pop()
+        .unwrap_or_else(|| Err(String::from("Issue was not created")))?;
+    output.push(format!("- [{}] {}", id, url));
+
+    let children = children.unwrap_or_default();
+    let child_inputs = children
+        .iter()
+        .map(|child| input(child.title.clone(), child.description.clone(), Some(&id)))
+        .collect::<Result<Vec<Value>, String>>()?;
+    let results = batch_create(config, token, child_inputs)?;
+
+    let total = results.len();
+    let mut failed = 0;
+    for (child, result) in children.iter().zip(results) {
+        match result {
+            Ok(Issue { id, url }) => output.push(format!("  - [{}] {}", id, url)),
+            Err(err) => {
+                failed += 1;
+                output.push(format!("  - ✗ {}: {err}", child.title));
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{failed} of {total} child issues in {path} could not be created"
+        )),
+    }
+}
 
-    let response = request::Gql::new(config, token, ISSUE_CREATE_DOC)
-        .put_string("title", title)
-        .put_string("teamId", team.id.clone())
-        .put_integer("priority", priority)
-        .put_string("stateId", state.id.clone())
-        .put_string("assigneeId", viewer.id.clone())
-        .put_string("description", description)
-        .maybe_put_string("projectId", project_id.clone())
-        .run()?;
-
-    let Issue { id, url } = extract_id_from_response(response)?;
-
-    println!("- [{}] {}", id, url);
-
-    for child in children.unwrap_or_default().iter() {
-        let title = fill_in_variables(child.title.clone(), variables.clone())?;
-        let child_description_template = child.description.clone().unwrap_or_default();
-        let child_description = fill_in_variables(child_description_template, variables.clone())?;
-
-        let response = request::Gql::new(config, token, ISSUE_CREATE_DOC)
-            .put_string("title", title)
-            .put_string("teamId", team.id.clone())
-            .put_string("stateId", state.id.clone())
-            .put_string("parentId", id.clone())
-            .put_integer("priority", priority)
-            .put_string("assigneeId", viewer.id.clone())
-            .put_string("description", child_description)
-            .maybe_put_string("projectId", project_id.clone())
+/// A mutation creating several issues at once, with one aliased issueCreate per input
+fn batch_create_doc(count: usize) -> String {
+    let variables = (0..count)
+        .map(|i| format!("$input{i}: IssueCreateInput!"))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let mutations = (0..count)
+        .map(|i| {
+            format!("issue{i}: issueCreate(input: $input{i}) {{ success issue {{ id url }} }}")
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    format!("mutation ({variables}) {{\n{mutations}\n}}")
+}
+
+/// Create issues in batches, with a result for each input in the same order
+fn batch_create(
+    config: &Config,
+    token: &str,
+    inputs: Vec<Value>,
+) -> Result<Vec<Result<Issue, String>>, String> {
+    let mut results = Vec::new();
+    for batch in inputs.chunks(BATCH_SIZE) {
+        let variables = batch
+            .iter()
+            .enumerate()
+            .map(|(i, input)| (format!("input{i}"), input.clone()))
+            .collect::<HashMap<String, Value>>();
+        let response = request::Gql::new(config, token, &batch_create_doc(batch.len()))
+            .put_variables(variables)
             .run()?;
-        let Issue { id, url } = extract_id_from_response(response)?;
-        println!("  - [{}] {}", id, url);
+        results.extend(batch_create_response(&response, batch.len())?);
     }
-    Ok("Done".to_string())
+    Ok(results)
 }
 
 /// Returns true if it is a TOML file that can be processed
@@ -192,24 +242,43 @@ fn is_issue_toml(entry: &walkdir::DirEntry) -> bool {
         && !entry.file_name().to_str().unwrap().contains("Cargo.toml")
 }
 
-/// Get the id from an issue response, needed for parent issues and terminal output
-fn extract_id_from_response(response: String) -> Result<Issue, String> {
-    let data: Result<IssueCreateResponse, _> = serde_json::from_str(&response);
-
-    match data {
-        Ok(IssueCreateResponse {
-            data: Some(Data {
-                issueCreate: IssueCreate { issue },
-            }),
-        }) => Ok(issue),
-        err => Err(format!(
-            "Could not parse response for issue:
+/// Split a batch response into the issue or error for each aliased issueCreate
+fn batch_create_response(
+    response: &str,
+    count: usize,
+) -> Result<Vec<Result<Issue, String>>, String> {
+    let value: Value = serde_json::from_str(response).map_err(|err| {
+        format!(
+            "Could not parse response for issues:
             ---
             {err:?}
             ---
             {response:?}"
-        )),
-    }
+        )
+    })?;
+
+    let results = (0..count)
+        .map(|i| {
+            let alias = format!("issue{i}");
+            value
+                .pointer(&format!("/data/{alias}/issue"))
+                .and_then(|issue| serde_json::from_value::<Issue>(issue.clone()).ok())
+                .ok_or_else(|| error_message(&value, &alias))
+        })
+        .collect();
+    Ok(results)
+}
+
+/// The message of the GraphQL error for an aliased mutation
+fn error_message(value: &Value, alias: &str) -> String {
+    value["errors"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|e| e["path"].get(0).and_then(Value::as_str) == Some(alias))
+        .and_then(|e| e["message"].as_str())
+        .unwrap_or("Issue was not created")
+        .to_string()
 }
 
 fn fill_in_variables(
@@ -227,3 +296,141 @@ fn fill_in_variables(
         .render("t1", &json!(variables))
         .map_err(|e| format!("Could not render template: {e:?}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn batch_create_doc_should_alias_each_issue() {
+        assert_eq!(
+            batch_create_doc(2),
+            "mutation ($input0: IssueCreateInput!, $input1: IssueCreateInput!) {\nissue0: issueCreate(input: $input0) { success issue { id url } }\nissue1: issueCreate(input: $input1) { success issue { id url } }\n}"
+        );
+    }
+
+    #[test]
+    fn batch_create_response_should_report_each_issue() {
+        let response = r#"{
+            "data": {
+                "issue0": {"success": true, "issue": {"id": "1", "url": "https://linear.app/1"}},
+                "issue1": null
+            },
+            "errors": [{"message": "Title is too long", "path": ["issue1"]}]
+        }"#;
+
+        assert_eq!(
+            batch_create_response(response, 2),
+            Ok(vec![
+                Ok(Issue {
+                    id: String::from("1"),
+                    url: String::from("https://linear.app/1")
+                }),
+                Err(String::from("Title is too long"))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut server = mockito::Server::new();
+        let parent = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(
+                "\"variables\":\\{\"input0\":\\{[^}]*\"title\":\"Launch rockets\"",
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"data": {"issue0": {"success": true, "issue": {"id": "parent", "url": "https://linear.app/parent"}}}}"#,
+            )
+            .create();
+        let children = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from("\"input1\"")))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"data": {
+                    "issue0": {"success": true, "issue": {"id": "child", "url": "https://linear.app/child"}},
+                    "issue1": null
+                }}"#,
+            )
+            .create();
+        let config = test::fixtures::config().mock_url(server.url());
+        let path = std::env::temp_dir().join(format!("lnr-template-{}.toml", uuid::Uuid::new_v4()));
+        fs::write(
+            &path,
+            r#"
+            [parent]
+            title = "Launch {{thing}}"
+
+            [[children]]
+            title = "Fuel {{thing}}"
+
+            [[children]]
+            title = "Count down"
+            description = "From {{count}}"
+
+            [variables]
+            thing = "rockets"
+            count = "10"
+            "#,
+        )
+        .unwrap();
+        let path = path.to_str().unwrap().to_string();
+
+        let result = evaluate(
+            &config,
+            "1234",
+            &test::fixtures::team(),
+            &None,
+            &Viewer::default(),
+            &path,
+            &test::fixtures::state(),
+            &Priority::None,
+            &[],
+            1,
+        );
+        assert_eq!(
+            result,
+            Err(format!(
+                "1 of 2 child issues in {path} could not be created"
+            ))
+        );
+        parent.assert();
+        children.assert();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn evaluate_should_count_failed_templates_in_directory() {
+        let config = test::fixtures::config();
+        let dir = std::env::temp_dir().join(format!("lnr-templates-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["one.toml", "two.toml", "three.toml"] {
+            fs::write(dir.join(name), "not a template").unwrap();
+        }
+        let path = dir.to_str().unwrap().to_string();
+
+        let result = evaluate(
+            &config,
+            "1234",
+            &test::fixtures::team(),
+            &None,
+            &Viewer::default(),
+            &path,
+            &test::fixtures::state(),
+            &Priority::None,
+            &[],
+            2,
+        );
+        assert_eq!(
+            result,
+            Err(String::from("3 of 3 templates could not be fully created"))
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -11,18 +11,24 @@ pub mod fixtures {
         Config {
             organizations: HashMap::new(),
             path: config::generate_path().unwrap(),
-            mock_url: None,
-            mock_string: None,
-            mock_select: None,
             spinners: Some(true),
+            ..Default::default()
         }
     }
     pub fn team() -> Team {
         Team {
             name: "Thundercats".to_string(),
             id: "123456".to_string(),
-            projects: Some(ProjectNode { nodes: Vec::new() }),
+            projects: Some(ProjectNode {
+                nodes: Vec::new(),
+                page_info: None,
+            }),
             states: Some(StateNode { nodes: Vec::new() }),
+            members: None,
+            issue_estimation_type: None,
+            issue_estimation_extended: None,
+            issue_estimation_allow_zero: None,
+            default_issue_state: None,
         }
     }
     pub fn state() -> State {
@@ -30,6 +36,7 @@ pub mod fixtures {
             name: "Thundercats".to_string(),
             id: "123456".to_string(),
             position: 1.0,
+            state_type: String::from("unstarted"),
         }
     }
 }
@@ -58,6 +65,20 @@ pub mod responses {
         .to_string()
     }
 
+    pub fn comment_create() -> String {
+        "{
+            \"data\":{
+              \"commentCreate\":{
+                \"success\":true,
+                \"comment\":{
+                  \"url\":\"https://linear.app/vardy/issue/BE-3354/test#comment-1\"
+                }
+              }
+            }
+          }\n"
+        .to_string()
+    }
+
     pub fn issue_list() -> String {
         "{\"data\":
             {\"issues\":{
@@ -69,6 +90,8 @@ pub mod responses {
                     \"description\":\"* Make item_name_id nullable\\n* Add non-null field for listing_url\\n* Unique index on listing_url\\n\\n* [ ] Migration\\n* [ ] Change schema\\n* [ ] Add to GQL type\",
                     \"url\":\"https://linear.app/vardy/issue/SHO-2148/modify-schema\",
                     \"branchName\":\"sho-2148-modify-schema\",
+                    \"customerNeeds\":{\"nodes\":[{\"id\":\"need-1\"},{\"id\":\"need-2\"}]},
+                    \"team\":{\"id\":\"123456\",\"name\":\"Thundercats\"},
                     \"children\":{
                       \"nodes\":[]
                     },
@@ -83,4 +106,800 @@ pub mod responses {
             }
         }\n".to_string()
     }
+
+    pub fn issue_list_archived() -> String {
+        "{\"data\":
+            {\"issues\":{
+              \"nodes\":[
+                {
+                    \"id\":\"438bced3-9999-9999-9999-a51423f24fc6\",
+                    \"identifier\":\"SHO-2148\",
+                    \"title\":\"Modify schema\",
+                    \"description\":null,
+                    \"url\":\"https://linear.app/vardy/issue/SHO-2148/modify-schema\",
+                    \"branchName\":\"sho-2148-modify-schema\",
+                    \"archivedAt\":\"2025-09-30T08:15:00.000Z\",
+                    \"children\":{\"nodes\":[]},
+                    \"state\":{\"id\":\"state-done\",\"position\":3,\"name\":\"Done\"}
+                }
+              ]
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_unarchive() -> String {
+        "{\"data\":
+            {\"issueUnarchive\":{
+              \"success\":true,
+              \"entity\":{
+                \"identifier\":\"SHO-2148\",
+                \"url\":\"https://linear.app/vardy/issue/SHO-2148/modify-schema\"
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_list_unsorted() -> String {
+        "{\"data\":
+            {\"issues\":{
+              \"nodes\":[
+                {
+                    \"id\":\"1\",
+                    \"identifier\":\"SHO-11\",
+                    \"title\":\"Eleven\",
+                    \"description\":null,
+                    \"url\":\"https://linear.app/vardy/issue/SHO-11/eleven\",
+                    \"branchName\":\"sho-11-eleven\",
+                    \"slaBreachesAt\":\"2024-05-12T09:00:00.000Z\",
+                    \"priority\":0,
+                    \"createdAt\":\"2024-05-02T00:00:00.000Z\",
+                    \"updatedAt\":\"2024-05-09T00:00:00.000Z\",
+                    \"labels\":{\"nodes\":[{\"id\":\"label-bug\",\"name\":\"bug\"},{\"id\":\"label-review\",\"name\":\"needs review\"}]},
+                    \"parent\":{\"id\":\"3\"},
+                    \"children\":{\"nodes\":[]},
+                    \"state\":{\"id\":\"1\",\"position\":1,\"name\":\"Todo\"}
+                },
+                {
+                    \"id\":\"2\",
+                    \"identifier\":\"SHO-9\",
+                    \"title\":\"Nine\",
+                    \"description\":null,
+                    \"url\":\"https://linear.app/vardy/issue/SHO-9/nine\",
+                    \"branchName\":\"sho-9-nine\",
+                    \"project\":{\"id\":\"p1\",\"name\":\"Batcave, Phase 2\"},
+                    \"priority\":3,
+                    \"createdAt\":\"2024-05-01T00:00:00.000Z\",
+                    \"updatedAt\":\"2024-05-08T00:00:00.000Z\",
+                    \"children\":{\"nodes\":[]},
+                    \"state\":{\"id\":\"2\",\"position\":2,\"name\":\"In Progress\"}
+                },
+                {
+                    \"id\":\"3\",
+                    \"identifier\":\"SHO-10\",
+                    \"title\":\"Ten\",
+                    \"description\":null,
+                    \"url\":\"https://linear.app/vardy/issue/SHO-10/ten\",
+                    \"branchName\":\"sho-10-ten\",
+                    \"slaBreachesAt\":\"2024-05-10T09:00:00.000Z\",
+                    \"priority\":1,
+                    \"createdAt\":\"2024-05-03T00:00:00.000Z\",
+                    \"updatedAt\":\"2024-05-07T00:00:00.000Z\",
+                    \"children\":{\"nodes\":[]},
+                    \"state\":{\"id\":\"1\",\"position\":1,\"name\":\"Todo\"}
+                }
+              ]
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_view() -> String {
+        "{\"data\":
+            {\"issue\":{
+                \"id\":\"cbe16d8a-9999-9999-9999-9f2e79c3cb7e\",
+                \"identifier\":\"BE-3354\",
+                \"title\":\"Test <issue>\",
+                \"description\":\"A description\",
+                \"url\":\"https://linear.app/vardy/issue/BE-3354/test\",
+                \"branchName\":\"be-3354-test\",
+                \"state\":{\"id\":\"1\",\"position\":1,\"name\":\"Todo\"}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_with_team() -> String {
+        "{\"data\":
+            {\"issue\":{
+                \"id\":\"cbe16d8a-9999-9999-9999-9f2e79c3cb7e\",
+                \"identifier\":\"BE-3354\",
+                \"title\":\"Test <issue>\",
+                \"url\":\"https://linear.app/vardy/issue/BE-3354/test\",
+                \"branchName\":\"be-3354-test\",
+                \"state\":{\"id\":\"state-todo\",\"position\":1,\"name\":\"Todo\"},
+                \"team\":{\"id\":\"123456\",\"name\":\"Thundercats\"}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_comments_page_1() -> String {
+        "{\"data\":
+            {\"issue\":{
+              \"comments\":{
+                \"nodes\":[
+                  {
+                    \"id\":\"c2\",
+                    \"body\":\"Second\",
+                    \"createdAt\":\"2024-05-03T00:00:00.000Z\",
+                    \"editedAt\":null,
+                    \"url\":\"https://linear.app/vardy/issue/BE-3354/test#comment-2\",
+                    \"parent\":null,
+                    \"user\":{\"displayName\":\"Bruce\"}
+                  },
+                  {
+                    \"id\":\"c1\",
+                    \"body\":\"First\",
+                    \"createdAt\":\"2024-05-01T00:00:00.000Z\",
+                    \"editedAt\":null,
+                    \"url\":\"https://linear.app/vardy/issue/BE-3354/test#comment-1\",
+                    \"parent\":null,
+                    \"user\":{\"displayName\":\"Bruce\"}
+                  }
+                ],
+                \"pageInfo\":{\"hasNextPage\":true,\"endCursor\":\"page2\"}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_comments_page_2() -> String {
+        "{\"data\":
+            {\"issue\":{
+              \"comments\":{
+                \"nodes\":[
+                  {
+                    \"id\":\"c4\",
+                    \"body\":\"A nested reply\",
+                    \"createdAt\":\"2024-05-02T12:00:00.000Z\",
+                    \"editedAt\":null,
+                    \"url\":\"https://linear.app/vardy/issue/BE-3354/test#comment-4\",
+                    \"parent\":{\"id\":\"c3\"},
+                    \"user\":{\"displayName\":\"Bruce\"}
+                  },
+                  {
+                    \"id\":\"c3\",
+                    \"body\":\"A reply\",
+                    \"createdAt\":\"2024-05-02T00:00:00.000Z\",
+                    \"editedAt\":null,
+                    \"url\":\"https://linear.app/vardy/issue/BE-3354/test#comment-3\",
+                    \"parent\":{\"id\":\"c1\"},
+                    \"user\":{\"displayName\":\"Alfred\"}
+                  }
+                ],
+                \"pageInfo\":{\"hasNextPage\":false,\"endCursor\":\"page3\"}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_branch_view() -> String {
+        "{\"data\":
+            {\"issueVcsBranchSearch\":{
+                \"id\":\"cbe16d8a-9999-9999-9999-9f2e79c3cb7e\",
+                \"identifier\":\"BE-3354\",
+                \"title\":\"Test\",
+                \"description\":\"A description\",
+                \"url\":\"https://linear.app/vardy/issue/BE-3354/test\",
+                \"branchName\":\"be-3354-test\",
+                \"estimate\":null,
+                \"priority\":2,
+                \"createdAt\":\"2024-05-01T00:00:00.000Z\",
+                \"assignee\":{\"displayName\":\"bruce\"},
+                \"project\":{\"id\":\"p1\",\"name\":\"Lair\"},
+                \"labels\":{\"nodes\":[{\"id\":\"label-bug\",\"name\":\"bug\"},{\"id\":\"label-cli\",\"name\":\"cli-created\"}]},
+                \"state\":{\"id\":\"1\",\"position\":1,\"name\":\"Todo\"}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_update() -> String {
+        "{\"data\":
+            {\"issueUpdate\":{
+              \"issue\":{
+                \"id\":\"cbe16d8a-9999-9999-9999-9f2e79c3cb7e\",
+                \"identifier\":\"BE-3354\",
+                \"title\":\"Test\",
+                \"description\":\"A description\",
+                \"url\":\"https://linear.app/vardy/issue/BE-3354/test\",
+                \"branchName\":\"be-3354-test\",
+                \"estimate\":3,
+                \"state\":{\"id\":\"1\",\"position\":1,\"name\":\"Todo\"}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn relation_create() -> String {
+        "{\"data\":
+            {\"issueRelationCreate\":{
+              \"success\":true,
+              \"issueRelation\":{
+                \"type\":\"blocks\",
+                \"issue\":{\"identifier\":\"ENG-2\",\"title\":\"Blocker\"},
+                \"relatedIssue\":{\"identifier\":\"ENG-1\",\"title\":\"Blocked\"}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn created_or_completed() -> String {
+        "{\"data\":
+            {\"issues\":{
+              \"nodes\":[
+                {\"createdAt\":\"2020-01-06T00:00:00.000Z\",\"completedAt\":null}
+              ],
+              \"pageInfo\":{\"hasNextPage\":false,\"endCursor\":null}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn attachment_create() -> String {
+        "{\"data\":
+            {\"attachmentCreate\":{
+              \"success\":true,
+              \"attachment\":{
+                \"title\":\"GH#99\",
+                \"url\":\"https://github.com/org/repo/issues/99\",
+                \"subtitle\":\"Cross-reference\",
+                \"metadata\":{\"source\":\"lnr-xref\"}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn team_members() -> String {
+        "{\"data\":
+            {\"team\":{
+              \"id\":\"123456\",
+              \"name\":\"Thundercats\",
+              \"members\":{
+                \"nodes\":[
+                  {
+                    \"id\":\"1\",
+                    \"name\":\"Bruce Wayne\",
+                    \"displayName\":\"Bruce\",
+                    \"email\":\"bruce@wayne.com\",
+                    \"statusEmoji\":\"🦇\",
+                    \"statusLabel\":\"On patrol\",
+                    \"statusUntilAt\":null
+                  },
+                  {
+                    \"id\":\"2\",
+                    \"name\":\"Alfred Pennyworth\",
+                    \"displayName\":\"alfred\",
+                    \"email\":\"alfred@wayne.com\",
+                    \"statusEmoji\":null,
+                    \"statusLabel\":null,
+                    \"statusUntilAt\":null
+                  }
+                ]
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn documents() -> String {
+        "{\"data\":
+            {\"documents\":{
+              \"nodes\":[
+                {
+                  \"id\":\"d1\",
+                  \"slugId\":\"8f2c1a9b3d4e\",
+                  \"title\":\"Lair Specs\",
+                  \"updatedAt\":\"2025-10-01T14:30:00.000Z\",
+                  \"project\":{\"name\":\"Lair\"}
+                },
+                {
+                  \"id\":\"d2\",
+                  \"slugId\":\"1b2c3d\",
+                  \"title\":\"Gadget Ideas\",
+                  \"updatedAt\":\"2025-09-12T08:00:00.000Z\",
+                  \"project\":null,
+                  \"team\":{\"name\":\"Thundercats\"}
+                }
+              ]
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn document() -> String {
+        "{\"data\":
+            {\"document\":{
+              \"id\":\"d1\",
+              \"slugId\":\"8f2c1a9b3d4e\",
+              \"title\":\"Lair Specs\",
+              \"content\":\"## Requirements\\n- Hidden entrance\\n- [ ] Sonar\\n\\nSee [the blueprints](https://wayne.com/lair)\\n\",
+              \"url\":\"https://linear.app/wayne/document/lair-specs-8f2c1a9b3d4e\",
+              \"createdAt\":\"2025-09-01T09:00:00.000Z\",
+              \"updatedAt\":\"2025-10-01T14:30:00.000Z\",
+              \"creator\":{\"displayName\":\"Bruce\"},
+              \"project\":{\"name\":\"Lair\"}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_stats(first_page: bool) -> String {
+        let (nodes, has_next_page) = match first_page {
+            true => (
+                "{\"priority\":1,\"estimate\":1,\"state\":{\"name\":\"Todo\",\"position\":1},\"project\":{\"name\":\"Lair\"}},
+                 {\"priority\":1,\"estimate\":3,\"state\":{\"name\":\"In Progress\",\"position\":2},\"project\":{\"name\":\"Lair\"}},
+                 {\"priority\":2,\"estimate\":null,\"state\":{\"name\":\"In Progress\",\"position\":2},\"project\":{\"name\":\"Batmobile\"}}",
+                true,
+            ),
+            false => (
+                "{\"priority\":4,\"estimate\":2,\"state\":{\"name\":\"Todo\",\"position\":1},\"project\":{\"name\":\"Lair\"}},
+                 {\"priority\":0,\"estimate\":2.5,\"state\":{\"name\":\"Todo\",\"position\":1},\"project\":null},
+                 {\"priority\":null,\"estimate\":null,\"state\":{\"name\":\"Todo\",\"position\":1},\"project\":null}",
+                false,
+            ),
+        };
+        format!(
+            "{{\"data\":{{\"issues\":{{\"nodes\":[{nodes}],\"pageInfo\":{{\"hasNextPage\":{has_next_page},\"endCursor\":\"cursor-1\"}}}}}}}}"
+        )
+    }
+
+    pub fn standup() -> String {
+        "{\"data\":{
+            \"completed\":{\"nodes\":[
+              {\"identifier\":\"ENG-1\",\"title\":\"Fix login\",\"url\":\"https://linear.app/wayne/issue/ENG-1\"}
+            ]},
+            \"inProgress\":{\"nodes\":[
+              {\"identifier\":\"ENG-2\",\"title\":\"Add search\",\"url\":\"https://linear.app/wayne/issue/ENG-2\"}
+            ]},
+            \"open\":{\"nodes\":[
+              {
+                \"identifier\":\"ENG-2\",
+                \"title\":\"Add search\",
+                \"url\":\"https://linear.app/wayne/issue/ENG-2\",
+                \"inverseRelations\":{\"nodes\":[
+                  {\"type\":\"related\",\"issue\":{\"identifier\":\"ENG-9\",\"title\":\"Search design\",\"state\":{\"type\":\"started\"}}},
+                  {\"type\":\"blocks\",\"issue\":{\"identifier\":\"ENG-8\",\"title\":\"Index issues\",\"state\":{\"type\":\"completed\"}}}
+                ]}
+              },
+              {
+                \"identifier\":\"ENG-3\",
+                \"title\":\"Ship search\",
+                \"url\":\"https://linear.app/wayne/issue/ENG-3\",
+                \"inverseRelations\":{\"nodes\":[
+                  {\"type\":\"blocks\",\"issue\":{\"identifier\":\"OPS-7\",\"title\":\"Provision cluster\",\"state\":{\"type\":\"unstarted\"}}}
+                ]}
+              }
+            ]}
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn changelog_issue(identifier: &str, title: &str, label: Option<&str>) -> String {
+        let labels = label
+            .map(|label| format!("{{\"name\":\"{label}\"}}"))
+            .unwrap_or_default();
+        format!(
+            "{{\"data\":{{\"issue\":{{
+                \"identifier\":\"{identifier}\",
+                \"title\":\"{title}\",
+                \"url\":\"https://linear.app/wayne/issue/{identifier}\",
+                \"labels\":{{\"nodes\":[{labels}]}},
+                \"project\":null
+            }}}}}}"
+        )
+    }
+
+    pub fn initiatives() -> String {
+        "{\"data\":
+            {\"initiatives\":{
+              \"nodes\":[
+                {
+                  \"id\":\"i1\",
+                  \"name\":\"Platform Hardening\",
+                  \"status\":\"Active\",
+                  \"targetDate\":\"2025-12-31\",
+                  \"owner\":{\"displayName\":\"Bruce\"},
+                  \"projects\":{\"nodes\":[{\"id\":\"p1\",\"name\":\"Lair\"},{\"id\":\"p2\",\"name\":\"Batmobile\"}]}
+                },
+                {
+                  \"id\":\"i2\",
+                  \"name\":\"Gotham Outreach\",
+                  \"status\":\"Planned\",
+                  \"targetDate\":null,
+                  \"owner\":null,
+                  \"projects\":{\"nodes\":[]}
+                }
+              ]
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn initiative() -> String {
+        "{\"data\":
+            {\"initiatives\":{
+              \"nodes\":[
+                {
+                  \"id\":\"i1\",
+                  \"name\":\"Platform Hardening\",
+                  \"status\":\"Active\",
+                  \"targetDate\":\"2025-12-31\",
+                  \"owner\":{\"displayName\":\"Bruce\"}
+                }
+              ]
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn initiative_projects(has_next_page: bool) -> String {
+        format!(
+            "{{\"data\":
+            {{\"initiative\":{{
+              \"projects\":{{
+                \"nodes\":[
+                  {{\"id\":\"p1\",\"name\":\"Lair\",\"health\":\"onTrack\",\"progress\":0.4,\"targetDate\":\"2025-11-01\",\"status\":{{\"name\":\"In Progress\"}},\"lead\":{{\"displayName\":\"Bruce\"}}}},
+                  {{\"id\":\"p2\",\"name\":\"Batmobile\",\"health\":null,\"progress\":0,\"targetDate\":null,\"status\":{{\"name\":\"Planned\"}},\"lead\":null}}
+                ],
+                \"pageInfo\":{{\"hasNextPage\":{has_next_page},\"endCursor\":\"projects-1\"}}
+              }}
+            }}
+          }}
+        }}\n"
+        )
+    }
+
+    pub fn issue_labels() -> String {
+        "{\"data\":
+            {\"issueLabels\":{
+              \"nodes\":[
+                {\"id\":\"label-workspace\",\"name\":\"cli-created\",\"team\":null},
+                {\"id\":\"label-other\",\"name\":\"cli-created\",\"team\":{\"id\":\"654321\"}},
+                {\"id\":\"label-team\",\"name\":\"cli-created\",\"team\":{\"id\":\"123456\"}},
+                {\"id\":\"label-cats\",\"name\":\"cats\",\"team\":null}
+              ]
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_label_create() -> String {
+        "{\"data\":
+            {\"issueLabelCreate\":{
+              \"success\":true,
+              \"issueLabel\":{\"id\":\"label-hairball\",\"name\":\"Hairball\"}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_label_archive() -> String {
+        "{\"data\":{\"issueLabelArchive\":{\"success\":true}}}\n".to_string()
+    }
+
+    pub fn project_create() -> String {
+        "{\"data\":
+            {\"projectCreate\":{
+              \"success\":true,
+              \"project\":{
+                \"id\":\"project-3\",
+                \"name\":\"Batcave, Phase 3\",
+                \"url\":\"https://linear.app/vardy/project/batcave-phase-3-1a2b3c\"
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn project_milestones() -> String {
+        "{\"data\":
+            {\"project\":{
+              \"projectMilestones\":{\"nodes\":[
+                {\"id\":\"milestone-beta\",\"name\":\"Beta\",\"sortOrder\":2},
+                {\"id\":\"milestone-alpha\",\"name\":\"Alpha\",\"sortOrder\":1}
+              ]}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn project_statuses() -> String {
+        "{\"data\":
+            {\"projectStatuses\":{
+              \"nodes\":[
+                {\"id\":\"status-started\",\"name\":\"In Progress\",\"type\":\"started\",\"position\":2},
+                {\"id\":\"status-backlog\",\"name\":\"Backlog\",\"type\":\"backlog\",\"position\":0},
+                {\"id\":\"status-planned\",\"name\":\"Planned\",\"type\":\"planned\",\"position\":1}
+              ]
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn cycles() -> String {
+        "{\"data\":
+            {\"cycles\":{
+              \"nodes\":[
+                {\"id\":\"c13\",\"number\":13,\"name\":\"Hardening\",\"startsAt\":\"2025-10-15T00:00:00.000Z\",\"endsAt\":\"2025-10-29T00:00:00.000Z\"},
+                {\"id\":\"c12\",\"number\":12,\"name\":null,\"startsAt\":\"2025-10-01T00:00:00.000Z\",\"endsAt\":\"2025-10-15T00:00:00.000Z\"}
+              ]
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn active_cycle(has_next_page: bool) -> String {
+        format!(
+            "{{\"data\":
+            {{\"team\":{{
+              \"activeCycle\":{{
+                \"id\":\"c12\",\"number\":12,\"name\":null,\"startsAt\":\"2025-10-01T00:00:00.000Z\",\"endsAt\":\"2025-10-15T00:00:00.000Z\",
+                \"issues\":{{\"nodes\":[
+                  {{\"identifier\":\"BE-1\",\"title\":\"Sonar\",\"estimate\":3,\"addedToCycleAt\":\"2025-09-30T12:00:00.000Z\",\"state\":{{\"type\":\"completed\"}},\"assignee\":{{\"displayName\":\"Bruce\"}}}},
+                  {{\"identifier\":\"BE-2\",\"title\":\"Grapple\",\"estimate\":2,\"addedToCycleAt\":\"2025-09-30T12:00:00.000Z\",\"state\":{{\"type\":\"completed\"}},\"assignee\":{{\"displayName\":\"Bruce\"}}}},
+                  {{\"identifier\":\"BE-3\",\"title\":\"Cave lights\",\"estimate\":3,\"addedToCycleAt\":\"2025-09-30T12:00:00.000Z\",\"state\":{{\"type\":\"unstarted\"}},\"assignee\":null}},
+                  {{\"identifier\":\"BE-4\",\"title\":\"Hotfix\",\"estimate\":2,\"addedToCycleAt\":\"2025-10-06T09:00:00.000Z\",\"state\":{{\"type\":\"started\"}},\"assignee\":{{\"displayName\":\"Alfred\"}}}},
+                  {{\"identifier\":\"BE-5\",\"title\":\"Dropped\",\"estimate\":8,\"addedToCycleAt\":\"2025-10-02T09:00:00.000Z\",\"state\":{{\"type\":\"canceled\"}},\"assignee\":{{\"displayName\":\"Alfred\"}}}}
+                ],\"pageInfo\":{{\"hasNextPage\":{has_next_page},\"endCursor\":\"issues-1\"}}}}
+              }}
+            }}
+          }}
+        }}\n"
+        )
+    }
+
+    pub fn cycle_issues() -> String {
+        "{\"data\":
+            {\"cycle\":{
+              \"issues\":{\"nodes\":[
+                {\"identifier\":\"BE-6\",\"title\":\"Batarang\",\"estimate\":1,\"addedToCycleAt\":\"2025-09-30T12:00:00.000Z\",\"state\":{\"type\":\"unstarted\"},\"assignee\":{\"displayName\":\"Robin\"}}
+              ],\"pageInfo\":{\"hasNextPage\":false,\"endCursor\":null}}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn issue_history() -> String {
+        "{\"data\":
+            {\"issue\":{
+              \"history\":{
+                \"nodes\":[
+                  {\"createdAt\":\"2024-05-03T00:00:00.000Z\",\"actor\":null,\"fromState\":{\"name\":\"Todo\"},\"toState\":{\"name\":\"Canceled\"}},
+                  {\"createdAt\":\"2024-05-02T12:00:00.000Z\",\"actor\":{\"displayName\":\"Bruce\"},\"fromState\":null,\"toState\":null},
+                  {\"createdAt\":\"2024-05-02T00:00:00.000Z\",\"actor\":{\"displayName\":\"Alfred\"},\"fromState\":{\"name\":\"In Progress\"},\"toState\":{\"name\":\"Todo\"}},
+                  {\"createdAt\":\"2024-05-01T00:00:00.000Z\",\"actor\":{\"displayName\":\"Bruce\"},\"fromAssignee\":null,\"toAssignee\":{\"displayName\":\"Bruce\"},\"fromPriority\":0,\"toPriority\":2}
+                ],
+                \"pageInfo\":{\"hasNextPage\":false,\"endCursor\":null}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn github_issues() -> String {
+        "[
+            {\"number\":1,\"title\":\"Batmobile won't start\",\"body\":\"It makes a noise\",\"html_url\":\"https://github.com/wayne/batcave/issues/1\",\"labels\":[{\"name\":\"Cats\"},{\"name\":\"garage\"}],\"assignees\":[{\"login\":\"bruce\"}]},
+            {\"number\":2,\"title\":\"Add grappling hook\",\"body\":null,\"html_url\":\"https://github.com/wayne/batcave/pull/2\",\"labels\":[],\"assignees\":[],\"pull_request\":{\"url\":\"https://api.github.com/repos/wayne/batcave/pulls/2\"}},
+            {\"number\":3,\"title\":\"Restock utility belt\",\"body\":null,\"html_url\":\"https://github.com/wayne/batcave/issues/3\",\"labels\":[],\"assignees\":[{\"login\":\"alfred\"}]}
+        ]"
+        .to_string()
+    }
+
+    pub fn viewer_page_1() -> String {
+        "{\"data\":
+            {\"viewer\":{
+              \"id\":\"viewer-1\",
+              \"name\":\"Bruce Wayne\",
+              \"teamMemberships\":{
+                \"nodes\":[
+                  {\"team\":{
+                    \"id\":\"123456\",
+                    \"name\":\"Thundercats\",
+                    \"projects\":{
+                      \"nodes\":[{\"id\":\"p1\",\"name\":\"Lair\"}],
+                      \"pageInfo\":{\"hasNextPage\":true,\"endCursor\":\"projects-1\"}
+                    }
+                  }}
+                ],
+                \"pageInfo\":{\"hasNextPage\":true,\"endCursor\":\"memberships-1\"}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn viewer_page_2() -> String {
+        "{\"data\":
+            {\"viewer\":{
+              \"id\":\"viewer-1\",
+              \"name\":\"Bruce Wayne\",
+              \"teamMemberships\":{
+                \"nodes\":[
+                  {\"team\":{
+                    \"id\":\"654321\",
+                    \"name\":\"Justice League\",
+                    \"projects\":{
+                      \"nodes\":[],
+                      \"pageInfo\":{\"hasNextPage\":false,\"endCursor\":null}
+                    }
+                  }}
+                ],
+                \"pageInfo\":{\"hasNextPage\":false,\"endCursor\":null}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn team_projects() -> String {
+        "{\"data\":
+            {\"team\":{
+              \"id\":\"123456\",
+              \"name\":\"Thundercats\",
+              \"projects\":{
+                \"nodes\":[{\"id\":\"p2\",\"name\":\"Batmobile\"}],
+                \"pageInfo\":{\"hasNextPage\":false,\"endCursor\":\"projects-2\"}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn merge_duplicate() -> String {
+        "{\"data\":
+            {\"issue\":{
+              \"id\":\"dup-uuid\",
+              \"identifier\":\"ENG-2\",
+              \"title\":\"Login broken\",
+              \"url\":\"https://linear.app/vardy/issue/ENG-2/login-broken\",
+              \"labels\":{\"nodes\":[
+                {\"id\":\"label-1\",\"name\":\"bug\"},
+                {\"id\":\"label-2\",\"name\":\"auth\"}
+              ]},
+              \"attachments\":{\"nodes\":[
+                {\"title\":\"GH#2\",\"url\":\"https://github.com/org/repo/issues/2\",\"subtitle\":null,\"metadata\":{},\"sourceType\":\"github\"}
+              ]},
+              \"team\":{
+                \"id\":\"123456\",
+                \"name\":\"Thundercats\",
+                \"states\":{\"nodes\":[
+                  {\"id\":\"state-done\",\"name\":\"Done\",\"position\":3,\"type\":\"completed\"},
+                  {\"id\":\"state-canceled\",\"name\":\"Canceled\",\"position\":4,\"type\":\"canceled\"},
+                  {\"id\":\"state-duplicate\",\"name\":\"Duplicate\",\"position\":5,\"type\":\"canceled\"}
+                ]}
+              }
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn merge_canonical() -> String {
+        "{\"data\":
+            {\"issue\":{
+              \"id\":\"canonical-uuid\",
+              \"identifier\":\"ENG-1\",
+              \"title\":\"Cannot log in\",
+              \"url\":\"https://linear.app/vardy/issue/ENG-1/cannot-log-in\",
+              \"labels\":{\"nodes\":[{\"id\":\"label-1\",\"name\":\"bug\"}]},
+              \"attachments\":{\"nodes\":[]},
+              \"team\":{\"id\":\"123456\",\"name\":\"Thundercats\",\"states\":{\"nodes\":[]}}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn customer_needs() -> String {
+        "{\"data\":
+            {\"issue\":{
+              \"identifier\":\"BE-3354\",
+              \"customerNeeds\":{\"nodes\":[
+                {
+                  \"body\":null,
+                  \"priority\":0,
+                  \"createdAt\":\"2025-10-01T09:00:00.000Z\",
+                  \"customer\":{\"name\":\"Daily Planet\"},
+                  \"creator\":null
+                },
+                {
+                  \"body\":\"Needs this before the board meeting\\nSecond line\",
+                  \"priority\":1,
+                  \"createdAt\":\"2025-10-02T14:30:00.000Z\",
+                  \"customer\":{\"name\":\"Wayne Enterprises\"},
+                  \"creator\":{\"displayName\":\"Alfred\"}
+                }
+              ]}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn team_states() -> String {
+        "{\"data\":
+            {\"team\":{
+              \"id\":\"123456\",
+              \"name\":\"Thundercats\",
+              \"states\":{\"nodes\":[
+                {\"id\":\"state-progress\",\"name\":\"In Progress\",\"position\":2,\"type\":\"started\"},
+                {\"id\":\"state-backlog\",\"name\":\"Backlog\",\"position\":0,\"type\":\"backlog\"},
+                {\"id\":\"state-todo\",\"name\":\"Todo\",\"position\":1,\"type\":\"unstarted\"},
+                {\"id\":\"state-done\",\"name\":\"Done\",\"position\":3,\"type\":\"completed\"}
+              ]},
+              \"defaultIssueState\":{\"id\":\"state-todo\"}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn workflow_state_create() -> String {
+        "{\"data\":
+            {\"workflowStateCreate\":{
+              \"success\":true,
+              \"workflowState\":{\"id\":\"state-review\",\"name\":\"In Review\",\"position\":2.5,\"type\":\"started\"}
+            }
+          }
+        }\n"
+        .to_string()
+    }
+
+    pub fn workflow_state_update() -> String {
+        "{\"data\":
+            {\"workflowStateUpdate\":{
+              \"success\":true,
+              \"workflowState\":{\"id\":\"state-progress\",\"name\":\"Doing\",\"position\":2,\"type\":\"started\"}
+            }
+          }
+        }\n"
+        .to_string()
+    }
 }
@@ -0,0 +1,274 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::date;
+
+/// A work session that has been started and not yet stopped
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Session {
+    pub identifier: String,
+    pub started_at: String,
+}
+
+/// Time logged against an issue, either a stopped session or logged by hand
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub identifier: String,
+    pub minutes: u64,
+    pub logged_at: String,
+    pub note: Option<String>,
+}
+
+/// Sessions and logged time, kept in $XDG_DATA_HOME/lnr/time.json
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Log {
+    pub running: Option<Session>,
+    pub entries: Vec<Entry>,
+}
+
+pub fn path() -> Result<PathBuf, String> {
+    dirs::data_local_dir()
+        .map(|dir| dir.join("lnr").join("time.json"))
+        .ok_or_else(|| String::from("Could not find a data directory for time tracking"))
+}
+
+impl Log {
+    pub fn load(path: &Path) -> Result<Log, String> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("Could not read time log {}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Log::default()),
+            Err(e) => Err(format!("Could not read time log {}: {e}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json)
+            .map_err(|e| format!("Could not write time log {}: {e}", path.display()))
+    }
+
+    /// Start a session, only one can run at a time
+    pub fn start(&mut self, identifier: &str, now: DateTime<Utc>) -> Result<(), String> {
+        if let Some(Session { identifier, .. }) = &self.running {
+            return Err(format!(
+                "Already tracking time on {identifier}, stop it with lnr issue time stop"
+            ));
+        }
+        self.running = Some(Session {
+            identifier: identifier.to_string(),
+            started_at: now.to_rfc3339(),
+        });
+        Ok(())
+    }
+
+    /// Stop the running session, logging at least a minute
+    pub fn stop(&mut self, now: DateTime<Utc>, note: Option<String>) -> Result<Entry, String> {
+        let Some(session) = self.running.take() else {
+            return Err(String::from(
+                "Not tracking time, start with lnr issue time start",
+            ));
+        };
+        let started_at = DateTime::parse_from_rfc3339(&session.started_at)
+            .map_err(|e| format!("Could not read start of session: {e}"))?;
+        let minutes = (now - started_at.with_timezone(&Utc)).num_minutes().max(1) as u64;
+        Ok(self.log(&session.identifier, minutes, now, note))
+    }
+
+    pub fn log(
+        &mut self,
+        identifier: &str,
+        minutes: u64,
+        now: DateTime<Utc>,
+        note: Option<String>,
+    ) -> Entry {
+        let entry = Entry {
+            identifier: identifier.to_string(),
+            minutes,
+            logged_at: now.to_rfc3339(),
+            note,
+        };
+        self.entries.push(entry.clone());
+        entry
+    }
+
+    /// Total time per issue for the week that today falls in, Monday to Sunday
+    pub fn report(&self, today: NaiveDate) -> String {
+        let week_start = date::week_start(today);
+        let mut totals: BTreeMap<&str, u64> = BTreeMap::new();
+        for entry in &self.entries {
+            let logged_on = DateTime::parse_from_rfc3339(&entry.logged_at)
+                .map(|logged_at| logged_at.with_timezone(&Local).date_naive());
+            if logged_on.is_ok_and(|day| day >= week_start && day <= today) {
+                *totals.entry(&entry.identifier).or_default() += entry.minutes;
+            }
+        }
+
+        let heading = format!("Week of {}", date::to_string(&week_start));
+        if totals.is_empty() {
+            return format!("{heading}\n\nNo time logged");
+        }
+        let width = totals.keys().map(|id| id.len()).max().unwrap_or(0).max(5);
+        let total = totals.values().sum();
+        let lines = totals
+            .iter()
+            .map(|(identifier, minutes)| format!("{identifier: <width$}  {}", format(*minutes)))
+            .collect::<Vec<String>>()
+            .join("\n");
+        format!(
+            "{heading}\n\n{lines}\n{: <width$}  {}",
+            "Total",
+            format(total)
+        )
+    }
+}
+
+/// Parse a duration such as 1h30m, 45m, 2h, or 1.5h into minutes
+pub fn parse(input: &str) -> Result<u64, String> {
+    let invalid = || format!("Could not parse duration {input}, use i.e. 1h30m, 45m, or 1.5h");
+    let mut minutes = 0.0;
+    let mut number = String::new();
+    for c in input
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+    {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'h' | 'm' => {
+                let value = number.parse::<f64>().map_err(|_| invalid())?;
+                minutes += if c == 'h' { value * 60.0 } else { value };
+                number.clear();
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    if !number.is_empty() || minutes < 1.0 {
+        return Err(invalid());
+    }
+    Ok(minutes.round() as u64)
+}
+
+/// Minutes as hours and minutes, i.e. 1h 30m
+pub fn format(minutes: u64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
+
+/// The comment posted to an issue when time is logged
+pub fn comment_body(entry: &Entry) -> String {
+    let day = date::timestamp_date(&entry.logged_at)
+        .map(|day| date::to_string(&day))
+        .unwrap_or_default();
+    let body = format!(
+        "**Time logged:** {}\n**Date:** {day}",
+        format(entry.minutes)
+    );
+    match &entry.note {
+        Some(note) if !note.trim().is_empty() => format!("{body}\n\n{note}"),
+        _ => body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parse_should_accept_hours_and_minutes() {
+        assert_eq!(parse("1h30m"), Ok(90));
+        assert_eq!(parse("1h 30m"), Ok(90));
+        assert_eq!(parse("45m"), Ok(45));
+        assert_eq!(parse("1.5h"), Ok(90));
+        assert_eq!(parse("2H"), Ok(120));
+        let error = Err(String::from(
+            "Could not parse duration 90, use i.e. 1h30m, 45m, or 1.5h",
+        ));
+        assert_eq!(parse("90"), error);
+        assert!(parse("0m").is_err());
+        assert!(parse("an hour").is_err());
+    }
+
+    #[test]
+    fn format_should_show_hours_and_minutes() {
+        assert_eq!(format(45), "45m");
+        assert_eq!(format(120), "2h");
+        assert_eq!(format(95), "1h 35m");
+    }
+
+    #[test]
+    fn stop_should_log_the_running_session() {
+        let mut log = Log::default();
+        assert!(log.stop(at("2025-10-01T10:00:00Z"), None).is_err());
+
+        log.start("ENG-1", at("2025-10-01T09:00:00Z")).unwrap();
+        assert_eq!(
+            log.start("ENG-2", at("2025-10-01T09:05:00Z")),
+            Err(String::from(
+                "Already tracking time on ENG-1, stop it with lnr issue time stop"
+            ))
+        );
+        let entry = log
+            .stop(
+                at("2025-10-01T10:30:20Z"),
+                Some(String::from("Fixed login")),
+            )
+            .unwrap();
+        assert_eq!(entry.minutes, 90);
+        assert_eq!(log.running, None);
+        assert_eq!(log.entries, vec![entry.clone()]);
+        assert_eq!(
+            comment_body(&entry),
+            "**Time logged:** 1h 30m\n**Date:** 2025-10-01\n\nFixed login"
+        );
+    }
+
+    #[test]
+    fn report_should_total_the_week() {
+        let mut log = Log::default();
+        log.log("ENG-2", 45, at("2025-09-26T12:00:00Z"), None);
+        log.log("ENG-10", 30, at("2025-09-30T12:00:00Z"), None);
+        log.log("ENG-2", 60, at("2025-10-01T12:00:00Z"), None);
+        log.log("ENG-10", 15, at("2025-10-01T12:00:00Z"), None);
+        let today = NaiveDate::from_ymd_opt(2025, 10, 2).unwrap();
+
+        assert_eq!(
+            log.report(today),
+            "Week of 2025-09-29\n\nENG-10  45m\nENG-2   1h\nTotal   1h 45m"
+        );
+        assert_eq!(
+            Log::default().report(today),
+            "Week of 2025-09-29\n\nNo time logged"
+        );
+    }
+
+    #[test]
+    fn log_should_round_trip_through_a_file() {
+        let path = std::env::temp_dir().join(format!("lnr-time-{}.json", uuid::Uuid::new_v4()));
+        assert_eq!(Log::load(&path), Ok(Log::default()));
+
+        let mut log = Log::default();
+        log.start("ENG-1", at("2025-10-01T09:00:00Z")).unwrap();
+        log.save(&path).unwrap();
+        assert_eq!(Log::load(&path), Ok(log));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub display_name: String,
+    pub status_emoji: Option<String>,
+    pub status_label: Option<String>,
+    pub status_until_at: Option<String>,
+}
+
+impl User {
+    /// Status set in Linear, i.e. "🌴 On vacation until 2025-10-01"
+    pub fn status(&self) -> Option<String> {
+        let status = [&self.status_emoji, &self.status_label]
+            .into_iter()
+            .flatten()
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        if status.is_empty() {
+            return None;
+        }
+
+        match &self.status_until_at {
+            Some(until) => {
+                let until = until.split('T').next().unwrap_or(until);
+                Some(format!("{status} until {until}"))
+            }
+            None => Some(status),
+        }
+    }
+
+    /// Display name followed by status, for showing when a user is away or focusing
+    pub fn name_with_status(&self) -> String {
+        match self.status() {
+            Some(status) => format!("{} ({status})", self.display_name),
+            None => self.display_name.clone(),
+        }
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        [
+            Some(&self.display_name),
+            self.name.as_ref(),
+            self.email.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|value| value.eq_ignore_ascii_case(query))
+    }
+}
+
+/// Find a user by display name, name, or email
+pub fn find(users: Vec<User>, query: &str) -> Result<User, String> {
+    users
+        .into_iter()
+        .find(|u| u.matches(query))
+        .ok_or_else(|| format!("User {query} not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn user() -> User {
+        User {
+            id: Some(String::from("1")),
+            name: Some(String::from("Bruce Wayne")),
+            email: Some(String::from("bruce@wayne.com")),
+            display_name: String::from("Bruce"),
+            status_emoji: None,
+            status_label: None,
+            status_until_at: None,
+        }
+    }
+
+    #[test]
+    fn name_with_status_should_include_status() {
+        assert_eq!(user().name_with_status(), "Bruce");
+
+        let user = User {
+            status_emoji: Some(String::from("🌴")),
+            status_label: Some(String::from("On vacation")),
+            status_until_at: Some(String::from("2025-10-01T00:00:00.000Z")),
+            ..user()
+        };
+        assert_eq!(
+            user.name_with_status(),
+            "Bruce (🌴 On vacation until 2025-10-01)"
+        );
+    }
+
+    #[test]
+    fn find_should_match_any_name() {
+        assert_eq!(find(vec![user()], "bruce").unwrap().display_name, "Bruce");
+        assert_eq!(
+            find(vec![user()], "Bruce@Wayne.com").unwrap().display_name,
+            "Bruce"
+        );
+        assert_eq!(
+            find(vec![user()], "Alfred").unwrap_err(),
+            "User Alfred not found"
+        );
+    }
+
+    #[test]
+    fn status_should_work_with_partial_status() {
+        let user = User {
+            status_label: Some(String::from("Focusing")),
+            ..user()
+        };
+        assert_eq!(user.status(), Some(String::from("Focusing")));
+    }
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::{git, verbosity};
+
+/// Version control that issues are looked up by branch in
+pub trait Vcs {
+    fn name(&self) -> &'static str;
+    /// The current branch, or the bookmark of the working copy for jj and Sapling
+    fn get_branch(&self) -> Result<String, String>;
+    /// Switch to a branch, creating it if it doesn't exist
+    fn checkout_branch(&self, name: &str) -> Result<String, String>;
+    /// Check a branch out in its own directory, only git has worktrees
+    fn checkout_worktree(&self, _name: &str, _path: &Option<String>) -> Result<String, String> {
+        Err(format!(
+            "--worktree only works in git repositories, this is a {} repository",
+            self.name()
+        ))
+    }
+}
+
+pub struct Git;
+
+/// jj, whose bookmarks take the place of branches
+pub struct Jujutsu;
+
+pub struct Sapling;
+
+impl Vcs for Git {
+    fn name(&self) -> &'static str {
+        "git"
+    }
+
+    fn get_branch(&self) -> Result<String, String> {
+        git::get_branch()
+    }
+
+    fn checkout_branch(&self, name: &str) -> Result<String, String> {
+        git::checkout_branch(name)
+    }
+
+    fn checkout_worktree(&self, name: &str, path: &Option<String>) -> Result<String, String> {
+        git::checkout_worktree(name, path)
+    }
+}
+
+impl Vcs for Jujutsu {
+    fn name(&self) -> &'static str {
+        "jj"
+    }
+
+    fn get_branch(&self) -> Result<String, String> {
+        // The working copy is usually a new change on top of the bookmarked one
+        let output = run(
+            "jj",
+            &[
+                "log",
+                "--no-graph",
+                "-r",
+                "latest(::@ & bookmarks())",
+                "-T",
+                "local_bookmarks.map(|b| b.name()).join(\"\\n\")",
+            ],
+        )?;
+        bookmark(output.lines().next().unwrap_or_default())
+    }
+
+    fn checkout_branch(&self, name: &str) -> Result<String, String> {
+        let revset = format!("bookmarks(exact:{})", quote(name));
+        if run(
+            "jj",
+            &["log", "--no-graph", "-r", &revset, "-T", "change_id"],
+        )?
+        .is_empty()
+        {
+            run("jj", &["bookmark", "create", name, "-r", "@"])?;
+            Ok(format!("Created bookmark {name}"))
+        } else {
+            run("jj", &["new", name])?;
+            Ok(format!("Started a new change on {name}"))
+        }
+    }
+}
+
+impl Vcs for Sapling {
+    fn name(&self) -> &'static str {
+        "Sapling"
+    }
+
+    fn get_branch(&self) -> Result<String, String> {
+        bookmark(&run("sl", &["log", "-r", ".", "-T", "{activebookmark}"])?)
+    }
+
+    fn checkout_branch(&self, name: &str) -> Result<String, String> {
+        let revset = format!("bookmark({})", quote(name));
+        if run("sl", &["log", "-r", &revset, "-T", "{node}"]).is_ok() {
+            run("sl", &["goto", name])?;
+            Ok(format!("Switched to {name}"))
+        } else {
+            run("sl", &["bookmark", name])?;
+            Ok(format!("Created bookmark {name}"))
+        }
+    }
+}
+
+/// The kind of repository the current directory is in
+pub fn detect() -> Box<dyn Vcs> {
+    let dir = std::env::current_dir().unwrap_or_default();
+    let vcs = detect_in(&dir);
+    verbosity::detail(&format!("Version control: {}", vcs.name()));
+    vcs
+}
+
+/// The closest directory with a .jj, .sl, or .git in it decides. A jj repository
+/// colocated with git has both, and is treated as jj.
+fn detect_in(dir: &Path) -> Box<dyn Vcs> {
+    for dir in dir.ancestors() {
+        if dir.join(".jj").is_dir() {
+            return Box::new(Jujutsu);
+        }
+        if dir.join(".sl").is_dir() {
+            return Box::new(Sapling);
+        }
+        if dir.join(".git").exists() {
+            return Box::new(Git);
+        }
+    }
+    Box::new(Git)
+}
+
+/// The current branch in whichever version control the repository uses
+pub fn get_branch() -> Result<String, String> {
+    detect().get_branch()
+}
+
+/// Switch to a branch in whichever version control the repository uses
+pub fn checkout_branch(name: &str) -> Result<String, String> {
+    detect().checkout_branch(name)
+}
+
+/// Check a branch out in its own worktree, erroring outside of git
+pub fn checkout_worktree(name: &str, path: &Option<String>) -> Result<String, String> {
+    detect().checkout_worktree(name, path)
+}
+
+/// The bookmark issues are looked up by, an error when the working copy has none
+fn bookmark(output: &str) -> Result<String, String> {
+    match output.trim() {
+        "" => Err(String::from(
+            "No bookmark on the working copy, create one or pass an issue identifier",
+        )),
+        name => Ok(name.to_string()),
+    }
+}
+
+/// A string literal for jj and Sapling revsets, which only escape backslashes and double quotes
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Could not run {program}: {e}"))?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|e| e.to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn quote_should_escape_for_revsets() {
+        assert_eq!(quote("eng-12-fix"), "\"eng-12-fix\"");
+        assert_eq!(quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(quote("café"), "\"café\"");
+    }
+
+    #[test]
+    fn bookmark_should_error_without_one() {
+        assert_eq!(bookmark("eng-12\n"), Ok(String::from("eng-12")));
+        assert!(bookmark("\n").is_err());
+    }
+
+    #[test]
+    fn detect_in_should_prefer_the_closest_repository() {
+        let root = std::env::temp_dir().join(format!("lnr-vcs-{}", uuid::Uuid::new_v4()));
+        let nested = root.join("jj").join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        assert_eq!(detect_in(&root).name(), "git");
+
+        std::fs::create_dir_all(root.join("jj").join(".jj")).unwrap();
+        std::fs::create_dir_all(root.join("jj").join(".git")).unwrap();
+        assert_eq!(detect_in(&nested).name(), "jj");
+
+        std::fs::create_dir_all(root.join("sl").join(".sl")).unwrap();
+        assert_eq!(detect_in(&root.join("sl")).name(), "Sapling");
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
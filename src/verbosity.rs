@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much is printed besides the result of a command
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+    /// Verbose, plus each request to Linear and its response
+    Debug,
+}
+
+/// Set by --quiet, --verbose, and --debug
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Use this verbosity for the rest of the process
+pub fn set(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn get() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        2 => Verbosity::Verbose,
+        3 => Verbosity::Debug,
+        _ => Verbosity::Normal,
+    }
+}
+
+pub fn is_quiet() -> bool {
+    get() == Verbosity::Quiet
+}
+
+/// Print progress, such as which file is being processed. Left out with --quiet
+pub fn progress(text: &str) {
+    if !is_quiet() {
+        println!("{text}");
+    }
+}
+
+/// Print how something was resolved before it is used, only with --verbose.
+/// Goes to stderr so that it doesn't mix with results that are piped elsewhere.
+pub fn detail(text: &str) {
+    if get() >= Verbosity::Verbose {
+        eprintln!("{text}");
+    }
+}
+
+/// Print what is sent to and received from Linear, only with --debug. Goes to stderr like detail.
+pub fn debug(text: &str) {
+    if get() == Verbosity::Debug {
+        eprintln!("[debug] {text}");
+    }
+}
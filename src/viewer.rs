@@ -2,27 +2,41 @@ use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
 use crate::input;
-use crate::request;
+use crate::request::{self, PageInfo};
+use crate::schema;
+use crate::team;
+use crate::verbosity;
 use crate::{Project, Team};
 
 const FETCH_IDS_DOC: &str = "
-        query {
+        query ($after: String) {
             viewer {
                 name
                 id
-                teamMemberships {
+                teamMemberships (first: 50, after: $after) {
                     nodes {
                         team {
                             name
                             id
-                            projects {
+                            issueEstimationType
+                            issueEstimationExtended
+                            issueEstimationAllowZero
+                            projects (first: 50) {
                                 nodes {
                                     name
                                     id
                                 }
+                                pageInfo {
+                                    hasNextPage
+                                    endCursor
+                                }
                             }
                         }
                     }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
                 }
             }
         }";
@@ -37,28 +51,74 @@ struct Data {
     viewer: Viewer,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Viewer {
     pub id: String,
+    #[serde(default)]
     name: String,
+    #[serde(default)]
     team_memberships: TeamMemberships,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 struct TeamMemberships {
     nodes: Vec<TeamNode>,
+    #[serde(rename = "pageInfo")]
+    page_info: Option<PageInfo>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(default)]
 struct TeamNode {
     team: Team,
 }
 
+/// Fetch the viewer with every team membership and every project of those teams
 pub fn get_viewer(config: &Config, token: &str) -> Result<Viewer, String> {
-    let response = request::Gql::new(config, token, FETCH_IDS_DOC).run()?;
+    let mut viewer = get_viewer_page(config, token, None)?;
+    let mut after = viewer
+        .team_memberships
+        .page_info
+        .take()
+        .and_then(PageInfo::next_cursor);
+    while after.is_some() {
+        let mut page = get_viewer_page(config, token, after)?;
+        after = page
+            .team_memberships
+            .page_info
+            .take()
+            .and_then(PageInfo::next_cursor);
+        viewer
+            .team_memberships
+            .nodes
+            .extend(page.team_memberships.nodes);
+    }
 
-    let result: Result<ViewerData, _> = serde_json::from_str(&response);
+    for TeamNode { team } in viewer.team_memberships.nodes.iter_mut() {
+        let after = team
+            .projects
+            .as_mut()
+            .and_then(|p| p.page_info.take())
+            .and_then(PageInfo::next_cursor);
+        if let Some(after) = after {
+            let projects = team::get_projects_after(config, token, team, after)?;
+            if let Some(p) = team.projects.as_mut() {
+                p.nodes.extend(projects)
+            }
+        }
+    }
+
+    Ok(viewer)
+}
+
+fn get_viewer_page(config: &Config, token: &str, after: Option<String>) -> Result<Viewer, String> {
+    let response = request::Gql::new(config, token, FETCH_IDS_DOC)
+        .cached()
+        .maybe_put_string("after", after)
+        .run()?;
+
+    let result: Result<ViewerData, _> = schema::parse(&response, "viewer");
     match result {
         Ok(body) => Ok(body.data.viewer),
         Err(err) => Err(format!("Could not parse response for item: {err:?}")),
@@ -95,6 +155,22 @@ pub fn project_names(team: &Option<Team>) -> Result<Vec<String>, String> {
         Ok(Vec::new())
     }
 }
+/// Every project of the viewer's teams once, with the names of its teams, sorted by name.
+/// Projects can span teams, so the same project may be nested under several memberships.
+pub fn projects_with_teams(viewer: &Viewer) -> Vec<(Project, Vec<String>)> {
+    let mut projects: Vec<(Project, Vec<String>)> = Vec::new();
+    for TeamNode { team } in &viewer.team_memberships.nodes {
+        for project in team.projects.iter().flat_map(|p| p.nodes.iter()) {
+            match projects.iter_mut().find(|(p, _)| p.id == project.id) {
+                Some((_, teams)) => teams.push(team.name.clone()),
+                None => projects.push((project.clone(), vec![team.name.clone()])),
+            }
+        }
+    }
+    projects.sort_by_key(|(project, _)| project.name.to_lowercase());
+    projects
+}
+
 /// Fetch the team by name
 pub fn team_by_name(viewer: &Viewer, team_name: &String) -> Result<Team, String> {
     let nodes = viewer.team_memberships.nodes.clone();
@@ -113,7 +189,10 @@ pub fn team_by_name(viewer: &Viewer, team_name: &String) -> Result<Team, String>
                 "Team {team_name} not found, options are: {team_names}"
             ))
         }
-        Some(team_node) => Ok(team_node.team.clone()),
+        Some(team_node) => {
+            verbosity::detail(&format!("Team: {}", team_node.team.name));
+            Ok(team_node.team.clone())
+        }
     }
 }
 
@@ -130,8 +209,16 @@ pub fn team(viewer: &Viewer, team_name: &Option<String>) -> Result<Team, String>
         Err("No teams found".to_string())
     } else if team_names.len() == 1 {
         team_by_name(viewer, team_names.first().unwrap())
+    } else if input::defaults_enabled() {
+        let team_name = team_names.first().unwrap();
+        verbosity::detail(&format!(
+            "Picked {team_name}, the first of {} teams, because of --yes. Choose another with --team",
+            team_names.len()
+        ));
+        team_by_name(viewer, team_name)
     } else {
-        let team_name = input::select("Select a team", team_names, None)?;
+        input::require("--team")?;
+        let team_name = input::select("Select a team", team_names)?;
         team_by_name(viewer, &team_name)
     }
 }
@@ -151,10 +238,101 @@ pub fn project(team: &Option<Team>, project_name: String) -> Result<Option<Proje
             .into_iter()
             .find(|n| n.name == project_name)
         {
-            Some(project) => Ok(Some(project)),
+            Some(project) => {
+                verbosity::detail(&format!("Project: {}", project.name));
+                Ok(Some(project))
+            }
             None => Err(String::from("Project not found")),
         }
     } else {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn mock_page(server: &mut mockito::ServerGuard, pattern: &str, body: String) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex(String::from(pattern)))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create()
+    }
+
+    #[test]
+    fn get_viewer_should_follow_every_page() {
+        let mut server = mockito::Server::new();
+        let first = mock_page(
+            &mut server,
+            "\"variables\":\\{\\}",
+            test::responses::viewer_page_1(),
+        );
+        let second = mock_page(
+            &mut server,
+            "\"after\":\"memberships-1\"",
+            test::responses::viewer_page_2(),
+        );
+        let projects = mock_page(
+            &mut server,
+            "\"after\":\"projects-1\"",
+            test::responses::team_projects(),
+        );
+        let config = test::fixtures::config().mock_url(server.url());
+
+        let viewer = get_viewer(&config, "1234").unwrap();
+        assert_eq!(
+            team_names(&viewer),
+            Ok(vec![
+                String::from("Thundercats"),
+                String::from("Justice League")
+            ])
+        );
+        let team = team_by_name(&viewer, &String::from("Thundercats")).ok();
+        assert_eq!(
+            project_names(&team),
+            Ok(vec![String::from("Lair"), String::from("Batmobile")])
+        );
+        first.assert();
+        second.assert();
+        projects.assert();
+    }
+
+    #[test]
+    fn projects_with_teams_should_list_shared_projects_once() {
+        let team = |id: &str, name: &str, projects: serde_json::Value| serde_json::json!({"team": {"id": id, "name": name, "projects": {"nodes": projects}}});
+        let viewer: Viewer = serde_json::from_value(serde_json::json!({
+            "id": "viewer-1",
+            "teamMemberships": {"nodes": [
+                team("123456", "Thundercats", serde_json::json!([
+                    {"id": "p1", "name": "Lair"},
+                    {"id": "p2", "name": "batmobile"}
+                ])),
+                team("654321", "Justice League", serde_json::json!([
+                    {"id": "p1", "name": "Lair"}
+                ]))
+            ]}
+        }))
+        .unwrap();
+
+        let projects = projects_with_teams(&viewer)
+            .into_iter()
+            .map(|(project, teams)| (project.name, teams.join(", ")))
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            projects,
+            vec![
+                (String::from("batmobile"), String::from("Thundercats")),
+                (
+                    String::from("Lair"),
+                    String::from("Thundercats, Justice League")
+                )
+            ]
+        );
+    }
+}
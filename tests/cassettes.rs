@@ -0,0 +1,53 @@
+//! Runs lnr end to end against responses recorded in tests/cassettes, without calling Linear.
+//! Re-record a cassette with LNR_RECORD=1 and a real token after changing its queries.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Run lnr with a throwaway config and home, replaying the named cassette
+fn replay(cassette: &str, args: &[&str]) -> Output {
+    let home = std::env::temp_dir().join(format!("lnr-cassette-{cassette}-{}", std::process::id()));
+    fs::create_dir_all(&home).unwrap();
+    let config = home.join("config.toml");
+    let path = config.display().to_string();
+    fs::write(
+        &config,
+        format!(
+            "path = {path:?}\nversion_check = false\nspinners = false\n\n[organizations]\nWayne = \"lin_api_test\"\n"
+        ),
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_lnr"))
+        .args(["--config", &path])
+        .args(args)
+        .env("LNR_CASSETTE", cassette_dir(cassette))
+        .env_remove("LNR_RECORD")
+        .env("HOME", &home)
+        .env("XDG_CACHE_HOME", home.join("cache"))
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env("TZ", "UTC")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    fs::remove_dir_all(home).unwrap();
+    output
+}
+
+fn cassette_dir(cassette: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("cassettes")
+        .join(cassette)
+}
+
+#[test]
+fn issue_view() {
+    let output = replay("issue-view", &["issue", "view", "BE-3354"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{stdout}");
+    assert_eq!(
+        stdout,
+        "Fix the Batmobile\nBE-3354 | Todo\nPriority: High\nLabels: bug\nhttps://linear.app/wayne/issue/BE-3354/fix-the-batmobile\nbe-3354-fix-the-batmobile\n\nIt makes a noise\n\n--- COMMENTS ---\n\nSounds like the turbine\n\n- Alfred 2024-05-01 00:00\n\n----------------\n"
+    );
+}